@@ -19,6 +19,33 @@ pub struct ZvConfig {
     /// Zig -> ZLS compatibility mappings.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub zls: Option<ZlsConfig>,
+    /// Number of master builds to keep after installing a new one; `0` disables pruning.
+    /// Defaults to [DEFAULT_MASTER_KEEP] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub master_keep: Option<usize>,
+    /// Community mirror preferences
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<MirrorConfig>,
+}
+
+/// Master builds kept by the post-install prune when `master_keep` is unset
+pub const DEFAULT_MASTER_KEEP: usize = 2;
+
+/// Commented starting point written by `zv config edit` when zv.toml doesn't exist yet
+pub fn config_template() -> String {
+    format!(
+        r#"# zv configuration. Lines starting with '#' are comments.
+version = "{}"
+
+# Master builds kept after installing a new one (0 disables pruning)
+# master_keep = {DEFAULT_MASTER_KEEP}
+
+# Pin community mirror ranks (1 = most preferred)
+# [mirror.priority]
+# "https://zig.example.com" = 1
+"#,
+        env!("CARGO_PKG_VERSION")
+    )
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +54,13 @@ pub struct ZlsConfig {
     pub mappings: HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorConfig {
+    /// Mirror URL -> rank overrides that take precedence over performance-based ranking
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub priority: HashMap<String, u8>,
+}
+
 /// Active Zig installation information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveZig {
@@ -36,6 +70,12 @@ pub struct ActiveZig {
     pub path: String,
     /// Whether this installation is from master
     pub is_master: bool,
+    /// SHA-256 of the zig binary, recorded at activation time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zig_sha256: Option<String>,
+    /// Whether this is a system zig outside of zv's versions directory (`zv use system`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
 }
 
 /// Persistent config I/O errors.
@@ -71,3 +111,15 @@ pub fn save_zv_config(path: &Path, config: &ZvConfig) -> Result<(), ConfigError>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_template_parses_with_everything_commented_out() {
+        let config: ZvConfig = toml::from_str(&config_template()).unwrap();
+        assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
+        assert!(config.master_keep.is_none() && config.mirror.is_none());
+    }
+}
@@ -11,5 +11,24 @@ pub const ZLS_MINISIGN_PUBKEY: &str = r#"RWR+9B91GBZ0zOjh6Lr17+zKf5BoSuFvrx2xSeD
 /// Zigtools API endpoint for selecting ZLS compatible with an active Zig.
 pub const ZLS_SELECT_VERSION_ENDPOINT: &str = "https://releases.zigtools.org/v1/zls/select-version";
 
+/// Gzip-compressed copy of the upstream index.json, kept next to the cached index
+pub const ZV_RAW_INDEX_FILE: &str = "index.json.gz";
+
 /// Zv's knowledge of what the current master semver is
 pub const ZV_MASTER_FILE: &str = "master";
+
+/// Timestamp file marking the last time the zig shim verified the active binary's hash
+pub const ZV_LAST_VERIFY_FILE: &str = "last-verify";
+
+/// Timestamp file marking the last time the zig shim warned that another zig shadows it on PATH
+pub const ZV_LAST_SHADOW_WARNING_FILE: &str = "last-shadow-warning";
+
+/// Version `zv use` falls back to when given neither a version nor `--channel`, and the `zig`
+/// shim falls back to when no version is active (ahead of the `zv default` file)
+pub const ZV_DEFAULT_VERSION: &str = "ZV_DEFAULT_VERSION";
+
+/// Set to `1` to answer every prompt with its default, like `--no-interactive`
+pub const ZV_NON_INTERACTIVE: &str = "ZV_NON_INTERACTIVE";
+
+/// Set in the environment of `zv shell` subshells to the Zig version they override
+pub const ZV_ACTIVE_OVERRIDE: &str = "ZV_ACTIVE_OVERRIDE";
@@ -0,0 +1,85 @@
+//! Global default Zig version (`default.json`) recorded by `zv default`
+//!
+//! The `zig` shim picks a version in this order: `+<version>` on the command line, the
+//! `ZIG_VERSION` environment variable, a `.zigversion` beside the nearest `build.zig`, the active
+//! version set by `zv use`, the `ZV_DEFAULT_VERSION` environment variable, and finally this global
+//! default. The active version follows every `zv use`; the global default only changes when set
+//! with `zv default`.
+
+use crate::{CfgErr, ZigVersion};
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GlobalDefault {
+    /// Version as the user wrote it, e.g. `0.14.1`, `stable` or `master`
+    version: String,
+}
+
+/// Read the global default version from `path`. A missing file yields `None`; an unreadable
+/// one is ignored with a warning.
+pub fn load(path: &Path) -> Option<ZigVersion> {
+    let version = load_raw(path)?;
+    ZigVersion::from_str(&version)
+        .inspect_err(|e| tracing::warn!("Ignoring unreadable {}: {}", path.display(), e))
+        .ok()
+}
+
+/// The global default exactly as it was set, e.g. `stable` rather than its resolved form
+pub fn load_raw(path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str::<GlobalDefault>(&contents)
+        .inspect_err(|e| tracing::warn!("Ignoring unreadable {}: {}", path.display(), e))
+        .ok()
+        .map(|d| d.version)
+}
+
+/// Record `version` as the global default in `path`. The version must parse as a [ZigVersion].
+pub fn save(path: &Path, version: &str) -> Result<(), CfgErr> {
+    ZigVersion::from_str(version.trim()).map_err(|e| CfgErr::ParseFail(eyre!("{e}")))?;
+    let contents = serde_json::to_string_pretty(&GlobalDefault {
+        version: version.trim().to_string(),
+    })
+    .map_err(|e| CfgErr::ParseFail(e.into()))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|io_err| {
+            CfgErr::WriteFail(io_err.into(), parent.to_string_lossy().to_string())
+        })?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|io_err| CfgErr::WriteFail(io_err.into(), path.to_string_lossy().to_string()))
+}
+
+/// Forget the global default. Returns `true` if one was set.
+pub fn clear(path: &Path) -> std::io::Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_round_trips_and_clears() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("default.json");
+        assert!(load(&path).is_none());
+
+        save(&path, "0.14.1").unwrap();
+        assert!(matches!(load(&path), Some(ZigVersion::Semver(v)) if v.to_string() == "0.14.1"));
+        save(&path, " stable\n").unwrap();
+        assert!(matches!(load(&path), Some(ZigVersion::Stable(None))));
+        assert_eq!(load_raw(&path).as_deref(), Some("stable"));
+        assert!(save(&path, "not-a-version").is_err());
+
+        assert!(clear(&path).unwrap());
+        assert!(!clear(&path).unwrap());
+        assert!(load(&path).is_none());
+    }
+}
@@ -0,0 +1,78 @@
+//! Lock serializing installs across concurrent zv processes
+//!
+//! Two `zv use 0.14.1` started at once would otherwise both download and extract the same
+//! archive. The lock is an exclusive advisory lock on `ZV_DIR/install.lock`, released when the
+//! [InstallLock] is dropped or the process exits.
+
+use crate::ZvError;
+use fs2::FileExt;
+use std::fs::File;
+use std::path::Path;
+use yansi::Paint;
+
+/// Held while installing; dropping it lets the next waiting zv process continue
+#[derive(Debug)]
+pub struct InstallLock {
+    file: File,
+}
+
+impl InstallLock {
+    /// Take the install lock at `path`, waiting for any other zv process holding it
+    pub async fn acquire(path: &Path) -> Result<Self, ZvError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(ZvError::Io)?;
+        }
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .map_err(ZvError::Io)?;
+
+        match file.try_lock_exclusive() {
+            Ok(()) => return Ok(Self { file }),
+            Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {}
+            Err(e) => return Err(ZvError::Io(e)),
+        }
+        println!(
+            "{} Waiting for another zv process to finish installing...",
+            Paint::cyan("ℹ")
+        );
+        tracing::debug!("Waiting for install lock {}", path.display());
+        tokio::task::spawn_blocking(move || file.lock_exclusive().map(|()| Self { file }))
+            .await
+            .map_err(|e| ZvError::Io(std::io::Error::other(e)))?
+            .map_err(ZvError::Io)
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn second_acquire_waits_for_release() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("install.lock");
+        let first = InstallLock::acquire(&path).await.unwrap();
+
+        let waiting_path = path.clone();
+        let second = tokio::spawn(async move { InstallLock::acquire(&waiting_path).await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!second.is_finished());
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(5), second)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+}
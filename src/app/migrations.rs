@@ -63,7 +63,7 @@ pub async fn migrate(zv_root: &Path, config_file: &Path) -> Result<()> {
     };
 
     if needs_legacy_migration {
-        println!(
+        eprintln!(
             "Performing zv  -> {} migrations",
             Paint::green(current_version)
         );
@@ -77,6 +77,8 @@ pub async fn migrate(zv_root: &Path, config_file: &Path) -> Result<()> {
             active_zig: migrated_active_zig,
             local_master_zig: read_local_master_zig(zv_root),
             zls: None,
+            master_keep: None,
+            mirror: None,
         };
 
         save_zv_config(&zv_toml_path, &config)?;
@@ -279,6 +281,8 @@ async fn migrate_active_json(active_json_path: &Path) -> Result<ActiveZig> {
         version: zig_install.version.to_string(),
         path: path.to_string_lossy().to_string(),
         is_master: zig_install.is_master,
+        zig_sha256: None,
+        external: false,
     };
 
     tracing::info!(
@@ -335,9 +339,13 @@ mod tests {
                     version: "0.14.0".to_string(),
                     path: "/tmp/zv/versions/0.14.0".to_string(),
                     is_master: false,
+                    zig_sha256: None,
+                    external: false,
                 }),
                 local_master_zig: None,
                 zls: Some(ZlsConfig { mappings }),
+                master_keep: None,
+                mirror: None,
             },
         )
         .unwrap();
@@ -356,4 +364,20 @@ mod tests {
         let zls = config.zls.unwrap();
         assert_eq!(zls.mappings.get("0.14.0").unwrap(), "0.14.0-zls");
     }
+
+    #[test]
+    fn loads_active_zig_written_before_hashes_were_recorded() {
+        let temp = tempfile::tempdir().unwrap();
+        let config_file = temp.path().join("zv.toml");
+
+        sync_fs::write(
+            &config_file,
+            "version = \"0.11.0\"\n\n[active_zig]\nversion = \"0.14.0\"\npath = \"/tmp/zv/versions/0.14.0\"\nis_master = false\n",
+        )
+        .unwrap();
+
+        let active_zig = load_zv_config(&config_file).unwrap().active_zig.unwrap();
+        assert_eq!(active_zig.version, "0.14.0");
+        assert!(active_zig.zig_sha256.is_none());
+    }
 }
@@ -20,6 +20,67 @@ fn extract_filename_from_trusted_comment(trusted_comment: &str) -> Result<String
     )))
 }
 
+/// Fail unless the signature's trusted comment names `expected_filename`
+fn ensure_signed_filename(trusted_comment: &str, expected_filename: &str) -> Result<(), ZvError> {
+    let actual_filename = extract_filename_from_trusted_comment(trusted_comment)?;
+
+    if actual_filename != expected_filename {
+        return Err(ZvError::MinisignError(eyre!(
+            "Signature filename mismatch: expected '{}', got '{}'",
+            expected_filename,
+            actual_filename
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a `.minisig` was issued for `expected_filename` before trusting it any further.
+/// Only the trusted comment line is read; the signature itself is not verified here.
+pub fn check_signature_filename(
+    signature: &std::path::Path,
+    expected_filename: &str,
+) -> Result<(), ZvError> {
+    let contents = std::fs::read_to_string(signature)
+        .map_err(|e| ZvError::MinisignError(eyre!("Failed to read signature file: {e}")))?;
+    let trusted_comment = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("trusted comment: "))
+        .ok_or_else(|| ZvError::MinisignError(eyre!("Signature file has no trusted comment")))?;
+
+    ensure_signed_filename(trusted_comment.trim_end(), expected_filename)
+}
+
+/// Sign `file` with the minisign secret key at `secret_key`, writing `<file>.minisig`.
+/// Runs the `minisign` CLI, which prompts for the key's password if it has one.
+pub fn sign_with_minisign(
+    file: &std::path::Path,
+    secret_key: &std::path::Path,
+) -> Result<std::path::PathBuf, ZvError> {
+    let status = std::process::Command::new("minisign")
+        .arg("-S")
+        .arg("-s")
+        .arg(secret_key)
+        .arg("-m")
+        .arg(file)
+        .status()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ZvError::MinisignError(eyre!(
+                "minisign was not found on PATH; install it to sign exported archives"
+            )),
+            _ => ZvError::MinisignError(eyre!("Failed to run minisign: {e}")),
+        })?;
+    if !status.success() {
+        return Err(ZvError::MinisignError(eyre!(
+            "minisign failed to sign {} ({status})",
+            file.display()
+        )));
+    }
+
+    let mut signature = file.as_os_str().to_owned();
+    signature.push(".minisig");
+    Ok(signature.into())
+}
+
 pub fn verify_minisign_signature(
     pubkey_base64: &str,
     expected_filename: &str,
@@ -32,16 +93,7 @@ pub fn verify_minisign_signature(
     let sig = Signature::from_file(signature)
         .map_err(|e| ZvError::MinisignError(eyre!("Failed to read signature file: {e}")))?;
 
-    let trusted_comment = sig.trusted_comment();
-    let actual_filename = extract_filename_from_trusted_comment(trusted_comment)?;
-
-    if actual_filename != expected_filename {
-        return Err(ZvError::MinisignError(eyre!(
-            "Signature filename mismatch: expected '{}', got '{}'",
-            expected_filename,
-            actual_filename
-        )));
-    }
+    ensure_signed_filename(sig.trusted_comment(), expected_filename)?;
 
     // Stream verifier
     let mut verifier = pubkey
@@ -68,3 +120,40 @@ pub fn verify_minisign_signature(
         .map_err(|e| ZvError::MinisignError(eyre!("Signature verification failed: {e}")))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature_naming(dir: &std::path::Path, trusted_comment: &str) -> std::path::PathBuf {
+        let path = dir.join("zig.tar.xz.minisig");
+        std::fs::write(
+            &path,
+            format!("untrusted comment: test\nAAAA\ntrusted comment: {trusted_comment}\nAAAA\n"),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn signature_filename_must_match_the_tarball() {
+        let temp = tempfile::tempdir().unwrap();
+        let sig = signature_naming(
+            temp.path(),
+            "timestamp:1755707121\tfile:zig-x86_64-linux-0.15.1.tar.xz\thashed",
+        );
+
+        assert!(check_signature_filename(&sig, "zig-x86_64-linux-0.15.1.tar.xz").is_ok());
+        assert!(check_signature_filename(&sig, "zig-x86_64-linux-0.15.2.tar.xz").is_err());
+    }
+
+    #[test]
+    fn signature_without_file_name_is_rejected() {
+        let temp = tempfile::tempdir().unwrap();
+        let sig = signature_naming(temp.path(), "timestamp:1755707121");
+        assert!(check_signature_filename(&sig, "zig.tar.xz").is_err());
+
+        std::fs::write(&sig, "not a signature").unwrap();
+        assert!(check_signature_filename(&sig, "zig.tar.xz").is_err());
+    }
+}
@@ -1,15 +1,20 @@
 pub(crate) mod config;
 pub mod constants;
+pub(crate) mod global_default;
+pub(crate) mod install_lock;
 pub(crate) mod migrations;
 pub(crate) mod network;
 pub(crate) mod toolchain;
 pub(crate) mod utils;
+pub(crate) mod zig_source;
 pub(crate) mod zls_download;
 pub(crate) mod zls_source;
 use crate::app::network::{ZigDownload, ZigRelease};
 use crate::app::utils::{remove_files, zig_tarball};
 use crate::types::*;
 mod minisign;
+#[cfg(test)]
+mod tests;
 use crate::path_utils;
 use color_eyre::eyre::{Context as _, eyre};
 pub use network::CacheStrategy;
@@ -18,6 +23,7 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use std::sync::LazyLock;
 use toolchain::ToolchainManager;
+use tracing::Instrument;
 
 /// 21 days default TTL for index
 pub static INDEX_TTL_DAYS: LazyLock<i64> = LazyLock::new(|| {
@@ -47,6 +53,19 @@ pub static MASTER_CACHE_TTL_HOURS: LazyLock<i64> = LazyLock::new(|| {
         .and_then(|v| v.parse().ok())
         .unwrap_or(22)
 });
+/// 365 days default horizon for entries in masters_seen.toml
+pub static MASTERS_SEEN_DAYS: LazyLock<i64> = LazyLock::new(|| {
+    std::env::var("ZV_MASTERS_SEEN_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(365)
+});
+/// Retry a mirror over plain HTTP when its HTTPS connection fails (opt-in, default off)
+pub static ALLOW_HTTP_FALLBACK: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("ZV_ALLOW_HTTP_FALLBACK")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+});
 /// Maximum number of retry attempts for downloads
 pub static MAX_RETRIES: LazyLock<u32> = LazyLock::new(|| {
     std::env::var("ZV_MAX_RETRIES")
@@ -59,6 +78,52 @@ impl App {
     pub fn download_cache(&self) -> &Path {
         &self.paths.downloads_dir
     }
+
+    /// Cached files `zv cache` manages: the zig index (plus a leftover uncompressed
+    /// `index.toml`), the mirrors list and every entry in `downloads/` except `tmp/`
+    pub fn cached_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = [
+            self.paths.index_file.clone(),
+            self.paths.index_file.with_extension(""),
+            self.paths.raw_index_file(),
+            self.paths.mirrors_file.clone(),
+        ]
+        .into_iter()
+        .filter(|p| p.exists())
+        .collect();
+        if let Ok(entries) = std::fs::read_dir(&self.paths.downloads_dir) {
+            let mut downloads: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.file_name() != Some("tmp".as_ref()))
+                .collect();
+            downloads.sort();
+            files.extend(downloads);
+        }
+        files
+    }
+
+    /// Remove everything in [`App::cached_files`], so the next command fetches the index and
+    /// mirrors afresh. Returns the number of entries removed and the bytes they occupied.
+    pub async fn clear_all_caches(&mut self) -> Result<(usize, u64), ZvError> {
+        let (mut removed, mut freed_bytes) = (0, 0);
+        for path in self.cached_files() {
+            if path.is_dir() {
+                freed_bytes += crate::tools::dir_size(&path);
+                tokio::fs::remove_dir_all(&path)
+                    .await
+                    .map_err(ZvError::Io)?;
+            } else {
+                freed_bytes += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                tokio::fs::remove_file(&path).await.map_err(ZvError::Io)?;
+            }
+            tracing::debug!(path = %path.display(), "Removed cached file");
+            removed += 1;
+        }
+        // Drop the loaded index and mirrors so nothing writes them back
+        self.network = None;
+        Ok((removed, freed_bytes))
+    }
 }
 
 /// Zv App State
@@ -75,6 +140,8 @@ pub struct App {
     zls: Option<PathBuf>,
     /// Network client
     network: Option<network::ZvNetwork>,
+    /// Timeouts the network client is created with
+    net_timeouts: network::NetTimeouts,
     /// Toolchain manager
     pub(crate) toolchain_manager: ToolchainManager,
     /// public_bin_dir (or bin_dir) in $PATH?
@@ -118,9 +185,24 @@ pub enum Either {
 
 impl App {
     /// Minimal App path initialization & directory creation
-    pub async fn init(UserConfig { paths, shell }: UserConfig) -> Result<Self, ZvError> {
+    pub async fn init(UserConfig { mut paths, shell }: UserConfig) -> Result<Self, ZvError> {
         /* data_dir is canonicalized in ZvPaths::resolve() -> fetch_zv_dir() */
 
+        // ZV_DOWNLOAD_DIR lets several zv instances share one download cache
+        if let Some(download_dir) = Self::writable_dir_from_env("ZV_DOWNLOAD_DIR")? {
+            paths.downloads_dir = download_dir;
+        }
+        // ZV_VERSIONS_DIR keeps installations apart from downloads, e.g. on a network share
+        if let Some(versions_dir) = Self::writable_dir_from_env("ZV_VERSIONS_DIR")? {
+            paths.versions_dir = versions_dir;
+        }
+
+        // ZV_BIN_DIR is applied by ZvPaths::resolve() so the shims see it too; check it here,
+        // once the versions dir is final
+        if crate::tools::bin_dir_override().is_some() {
+            Self::check_bin_dir_override(&paths.bin_dir, &paths.versions_dir)?;
+        }
+
         // Ensure internal bin dir exists
         if !paths.bin_dir.try_exists().unwrap_or_default() {
             std::fs::create_dir_all(&paths.bin_dir)
@@ -141,7 +223,9 @@ impl App {
         }
 
         let toolchain_manager = ToolchainManager::new(
-            &paths.data_dir,
+            &paths.bin_dir,
+            &paths.versions_dir,
+            &paths.installations_file,
             &paths.config_file,
             paths.public_bin_dir.clone(),
         )
@@ -179,6 +263,7 @@ impl App {
 
         let app = App {
             network: None,
+            net_timeouts: network::NetTimeouts::default(),
             zig,
             zls,
             source_set,
@@ -191,6 +276,59 @@ impl App {
         Ok(app)
     }
 
+    /// App rooted at `path` as if `ZV_DIR` pointed there, with every directory created up front.
+    /// No network client is set up and no shell is detected, so nothing touches the real `~/.zv`.
+    #[cfg(test)]
+    pub async fn with_zv_dir(path: &Path) -> Result<App, ZvError> {
+        let config = UserConfig::with_no_shell(path.to_path_buf());
+        let paths = &config.paths;
+        for dir in [
+            &paths.data_dir,
+            &paths.bin_dir,
+            &paths.versions_dir,
+            &paths.downloads_dir,
+        ] {
+            std::fs::create_dir_all(dir).map_err(ZvError::Io)?;
+        }
+        Self::init(config).await
+    }
+
+    /// Directory override from env var `var`, created if missing and checked for writability
+    fn writable_dir_from_env(var: &str) -> Result<Option<PathBuf>, ZvError> {
+        let Some(dir) = std::env::var_os(var).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+        let dir = PathBuf::from(dir);
+        std::fs::create_dir_all(&dir)
+            .and_then(|_| utils::ensure_dir_writable(&dir))
+            .map_err(ZvError::Io)
+            .wrap_err_with(|| format!("{var} {} is not a writable directory", dir.display()))?;
+        tracing::debug!(dir = %dir.display(), "Using {var} override");
+        Ok(Some(dir))
+    }
+
+    /// Refuse a `ZV_BIN_DIR` inside the versions directory, where cleaning up a version could
+    /// delete the shims, and warn when zv can't write its shims there
+    fn check_bin_dir_override(bin_dir: &Path, versions_dir: &Path) -> Result<(), ZvError> {
+        if utils::is_within(bin_dir, versions_dir) {
+            return Err(ZvError::General(eyre!(
+                "ZV_BIN_DIR {} is inside the versions directory {}",
+                bin_dir.display(),
+                versions_dir.display()
+            )));
+        }
+        if let Err(e) =
+            std::fs::create_dir_all(bin_dir).and_then(|_| utils::ensure_dir_writable(bin_dir))
+        {
+            crate::tools::warn(format!(
+                "ZV_BIN_DIR {} is not writable ({e}); zv can't install or update its shims there",
+                bin_dir.display()
+            ));
+        }
+        tracing::debug!(dir = %bin_dir.display(), "Using ZV_BIN_DIR override");
+        Ok(())
+    }
+
     /// Set the active Zig version. Optionally provide the installed path to skip re-checking installation
     pub async fn set_active_version<'b>(
         &mut self,
@@ -211,6 +349,17 @@ impl App {
         self.toolchain_manager.set_active_version(version).await
     }
 
+    /// Timeouts used for network requests
+    pub(crate) fn net_timeouts(&self) -> network::NetTimeouts {
+        self.net_timeouts
+    }
+
+    /// Override every network timeout with `secs` (`--timeout`). Takes effect for network
+    /// clients created afterwards.
+    pub(crate) fn set_timeout(&mut self, secs: u64) {
+        self.net_timeouts = network::NetTimeouts::uniform(secs);
+    }
+
     /// Initialize network client if not already done
     pub async fn ensure_network(&mut self) -> Result<(), ZvError> {
         if self.network.is_none() {
@@ -219,6 +368,8 @@ impl App {
                     self.paths.index_file.clone(),
                     self.paths.mirrors_file.clone(),
                     self.paths.downloads_dir.clone(),
+                    self.mirror_priorities(),
+                    self.net_timeouts,
                 )
                 .await?,
             );
@@ -232,6 +383,8 @@ impl App {
                 self.paths.index_file.clone(),
                 self.paths.mirrors_file.clone(),
                 self.paths.downloads_dir.clone(),
+                self.mirror_priorities(),
+                self.net_timeouts,
             )
             .await?;
             net.ensure_mirror_manager().await?;
@@ -272,6 +425,23 @@ impl App {
         Ok(())
     }
 
+    /// Write the upstream index.json kept by the last index refresh to `dest`, uncompressed
+    pub async fn dump_raw_index(&mut self, dest: &Path) -> Result<(), ZvError> {
+        self.ensure_network().await?;
+        let network = self.network.as_ref().expect("network initialized above");
+        let Some(json) = network.index_manager.read_raw_index().await? else {
+            return Err(ZvError::CacheNotFound {
+                path: network
+                    .index_manager
+                    .raw_index_path()
+                    .to_string_lossy()
+                    .to_string(),
+                context: None,
+            });
+        };
+        tokio::fs::write(dest, json).await.map_err(ZvError::Io)
+    }
+
     /// Force refresh the community mirrors list from network
     pub async fn sync_mirrors(&mut self) -> Result<usize, ZvError> {
         self.ensure_network_with_mirrors().await?;
@@ -283,6 +453,16 @@ impl App {
         Ok(0)
     }
 
+    /// Add a community mirror by URL, see [`network::ZvNetwork::add_mirror`]
+    pub async fn add_mirror(&mut self, url: &str) -> Result<(), ZvError> {
+        self.ensure_network_with_mirrors().await?;
+        if let Some(network) = self.network.as_mut() {
+            return network.add_mirror(url).await;
+        }
+
+        Ok(())
+    }
+
     /// Get the current active Zig version
     pub fn get_active_version(&self) -> Option<ZigVersion> {
         self.toolchain_manager.get_active_install().map(|zi| {
@@ -332,6 +512,8 @@ impl App {
                 active_zig: None,
                 local_master_zig: None,
                 zls: None,
+                master_keep: None,
+                mirror: None,
             },
         );
         config.version = env!("CARGO_PKG_VERSION").to_string();
@@ -348,6 +530,47 @@ impl App {
             .map_err(|e| ZvError::General(eyre!("Failed to save zls mapping: {e}")))
     }
 
+    /// Mirror rank overrides configured under `mirror.priority` in zv.toml
+    fn mirror_priorities(&self) -> HashMap<String, u8> {
+        crate::app::config::load_zv_config(&self.paths.config_file)
+            .ok()
+            .and_then(|c| c.mirror)
+            .map(|m| m.priority)
+            .unwrap_or_default()
+    }
+
+    /// Persist a manual rank override for a community mirror and apply it to the
+    /// loaded mirror manager, if any. Returns `true` if a known mirror matched `url`.
+    pub fn set_mirror_priority(&mut self, url: &str, rank: u8) -> Result<bool, ZvError> {
+        let mut config = crate::app::config::load_zv_config(&self.paths.config_file).unwrap_or(
+            crate::app::config::ZvConfig {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                active_zig: None,
+                local_master_zig: None,
+                zls: None,
+                master_keep: None,
+                mirror: None,
+            },
+        );
+        config.version = env!("CARGO_PKG_VERSION").to_string();
+
+        let mirror_config = config
+            .mirror
+            .get_or_insert_with(|| crate::app::config::MirrorConfig {
+                priority: HashMap::new(),
+            });
+        mirror_config.priority.insert(url.to_string(), rank);
+
+        crate::app::config::save_zv_config(&self.paths.config_file, &config)
+            .map_err(|e| ZvError::General(eyre!("Failed to save mirror priority: {e}")))?;
+
+        Ok(self
+            .network
+            .as_mut()
+            .and_then(|net| net.mirror_manager.as_mut())
+            .is_some_and(|manager| manager.set_mirror_priority(url, rank)))
+    }
+
     /// Get the app's data directory (ZV_DIR)
     pub fn path(&self) -> &PathBuf {
         &self.paths.data_dir
@@ -363,6 +586,12 @@ impl App {
         self.bin_path().join(Shim::Zv.executable_name()).exists()
     }
 
+    /// Check if PATH already reaches zv's bin dir (the public bin dir on XDG), i.e. whether
+    /// `zv setup` has nothing left to do. Computed once during `init`.
+    pub fn is_setup(&self) -> bool {
+        self.source_set
+    }
+
     /// Get the installed versions directory
     pub fn versions_path(&self) -> &PathBuf {
         &self.paths.versions_dir
@@ -383,6 +612,11 @@ impl App {
         self.zig.clone()
     }
 
+    /// Compare the active zig binary against the hash recorded when it was activated
+    pub fn verify_active_zig(&self) -> toolchain::ActiveZigIntegrity {
+        self.toolchain_manager.verify_active_zig()
+    }
+
     /// Spawn a zig process with recursion guard management
     /// Only bumps the recursion count if we're spawning our own shim
     pub(crate) fn spawn_zig_with_guard(
@@ -391,8 +625,8 @@ impl App {
         args: &[&str],
         current_dir: Option<&Path>,
     ) -> Result<Output, ZvError> {
-        // No need for canonicalization here, just a quick check
-        let is_our_shim = zig_path.parent() == Some(self.paths.bin_dir.as_path());
+        // Resolve symlinks so a linked bin/ (e.g. ~/.local/bin/zig -> ~/.zv/bin/zig) still counts
+        let is_our_shim = utils::is_in_dir(zig_path, &self.paths.bin_dir);
 
         let new_count = if is_our_shim {
             let count = std::env::var("ZV_RECURSION_COUNT")
@@ -467,6 +701,15 @@ impl App {
         }
     }
 
+    /// Fail before anything is downloaded if the release picked during resolution has no build
+    /// for this host, listing the targets it does have
+    pub fn ensure_host_artifact(&self) -> Result<(), ZvError> {
+        match &self.to_install {
+            Some(Either::Release(zig_release)) => host_artifact(zig_release).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
     /// Fetch latest master and returns a [ZigRelease]
     pub async fn fetch_master_version(&mut self) -> Result<ZigRelease, ZvError> {
         self.ensure_network().await?;
@@ -481,22 +724,62 @@ impl App {
         let version_str = zig_release.resolved_version().version().to_string();
         crate::app::migrations::update_master_file(&self.paths.master_file, &version_str).await;
 
+        // Remember this master so it stays resolvable after upstream moves on
+        let mut seen = network::MastersSeen::load(&self.paths.masters_seen_file).await;
+        if seen.record(&zig_release)
+            && let Err(e) = seen.save().await
+        {
+            tracing::debug!("Failed to record master in masters_seen.toml: {e}");
+        }
+
         Ok(zig_release)
     }
-    /// Fetch latest stable and returns a [ZigRelease]
+
+    /// Look up a master release observed by an earlier `fetch_master_version`
+    pub async fn seen_master_release(&self, version: &semver::Version) -> Option<ZigRelease> {
+        network::MastersSeen::load(&self.paths.masters_seen_file)
+            .await
+            .get(version)
+            .cloned()
+    }
+
+    /// Drop masters_seen.toml entries older than [MASTERS_SEEN_DAYS]; returns how many were removed
+    pub async fn prune_masters_seen(&self) -> Result<usize, ZvError> {
+        let mut seen = network::MastersSeen::load(&self.paths.masters_seen_file).await;
+        let pruned = seen.prune_older_than(*MASTERS_SEEN_DAYS);
+        if pruned > 0 {
+            seen.save().await.map_err(ZvError::ZvConfigError)?;
+        }
+        Ok(pruned)
+    }
+    /// Fetch latest stable (or latest pre-release, if `include_prerelease`) and returns a [ZigRelease]
     pub async fn fetch_latest_version(
         &mut self,
         cache_strategy: CacheStrategy,
+        include_prerelease: bool,
     ) -> Result<ZigRelease, ZvError> {
         self.ensure_network().await?;
         let zig_release = self
             .network
             .as_mut()
             .unwrap()
-            .fetch_latest_stable_version(cache_strategy)
+            .fetch_latest_stable_version(cache_strategy, include_prerelease)
             .await?;
         Ok(zig_release)
     }
+
+    /// Newest stable release in `series`, for `stable@0.14`
+    pub async fn fetch_latest_in_series(
+        &mut self,
+        series: &crate::VersionSeries,
+    ) -> Result<ZigRelease, ZvError> {
+        self.ensure_network().await?;
+        self.network
+            .as_mut()
+            .unwrap()
+            .fetch_latest_in_series(series)
+            .await
+    }
     /// Validate if a semver version exists in the index and returns a [ZigRelease] or [ResolvedZigVersion]
     pub async fn validate_semver(&mut self, version: &semver::Version) -> Result<Either, ZvError> {
         // todo!("Implement semver validation against installed versions and return early or else");
@@ -513,11 +796,121 @@ impl App {
         }
     }
 
+    /// Estimate the disk space in bytes needed to download and extract `release` for the host.
+    /// This is the compressed artifact size plus ~3x that for the extracted toolchain.
+    /// Returns 0 if the release has no artifact for the host target.
+    pub fn estimate_installation_size(release: &ZigRelease) -> u64 {
+        utils::host_target()
+            .and_then(|host| release.host_artifact(&host).map(|a| a.size))
+            .map(|size| size.saturating_add(size.saturating_mul(3)))
+            .unwrap_or(0)
+    }
+
+    /// Warn if the estimated installation size exceeds 80% of the space available under `versions_dir`
+    fn warn_if_low_disk_space(&self, release: &ZigRelease) {
+        let estimate = Self::estimate_installation_size(release);
+        if estimate == 0 {
+            return;
+        }
+        match fs2::available_space(&self.paths.versions_dir) {
+            Ok(available) if estimate > available / 10 * 8 => {
+                crate::tools::warn(format!(
+                    "Installing Zig {} needs about {:.1} MB but only {:.1} MB is available in {}",
+                    release.version_string(),
+                    estimate as f64 / 1_048_576.0,
+                    available as f64 / 1_048_576.0,
+                    self.paths.versions_dir.display()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("Could not determine available disk space: {e}");
+            }
+        }
+    }
+
     /// Check if version is installed returning Some(path) to zig binary if so
     #[inline]
     pub fn check_installed(&self, rzv: &ResolvedZigVersion) -> Option<PathBuf> {
         self.toolchain_manager.is_version_installed(rzv)
     }
+    /// Directory `rzv` is installed in, if it is installed
+    pub fn get_version_install_path(&self, rzv: &ResolvedZigVersion) -> Option<PathBuf> {
+        self.check_installed(rzv)
+            .and_then(|zig| zig.parent().map(Path::to_path_buf))
+    }
+    /// Package an installed version into a portable archive at `output`. With a minisign
+    /// secret key, the archive is also signed and the `.minisig` path returned.
+    pub async fn export_toolchain(
+        &self,
+        rzv: &ResolvedZigVersion,
+        output: &Path,
+        signing_key: Option<&Path>,
+    ) -> crate::Result<Option<PathBuf>> {
+        self.toolchain_manager.export_toolchain(rzv, output).await?;
+        match signing_key {
+            Some(key) => Ok(Some(minisign::sign_with_minisign(output, key)?)),
+            None => Ok(None),
+        }
+    }
+    /// Compare `install` with the checksums recorded when it was installed and the index. The
+    /// active version's zig binary is also checked against the hash recorded in zv.toml.
+    pub async fn validate_installed_checksum(
+        &mut self,
+        install: &toolchain::ZigInstall,
+    ) -> Result<bool, ZvError> {
+        let is_active = self
+            .toolchain_manager
+            .get_active_install()
+            .is_some_and(|active| active.version_matches(install));
+        if is_active
+            && let toolchain::ActiveZigIntegrity::Mismatch { zig_path, .. } =
+                self.verify_active_zig()
+        {
+            tracing::warn!(version = %install.version, "{} differs from the one activated", zig_path.display());
+            return Ok(false);
+        }
+        self.ensure_network().await?;
+        self.network
+            .as_mut()
+            .unwrap()
+            .validate_installed_checksum(install)
+            .await
+    }
+    /// Install a portable archive as `version`, e.g. one made by `zv export` on another machine
+    pub async fn import_toolchain(
+        &mut self,
+        archive: &Path,
+        version: &semver::Version,
+        is_master: bool,
+    ) -> crate::Result<PathBuf> {
+        self.toolchain_manager
+            .import_toolchain(archive, version, is_master)
+            .await
+    }
+    /// Install the loaded `to_install` version while holding the install lock. If another zv
+    /// process installed `rzv` while this one waited for the lock, that install is reused
+    /// instead (unless `reinstall`). Returns the zig binary and whether it was reused.
+    pub async fn install_serialized(
+        &mut self,
+        rzv: &ResolvedZigVersion,
+        force_ziglang: bool,
+        reinstall: bool,
+    ) -> Result<(PathBuf, bool), ZvError> {
+        let _lock = install_lock::InstallLock::acquire(&self.paths.install_lock_file()).await?;
+        if !reinstall && let Some(zig) = self.check_installed(rzv) {
+            tracing::debug!("{} was installed while waiting for the install lock", rzv);
+            return Ok((zig, true));
+        }
+        // The index may list this version without a build for this host; say so up front
+        self.ensure_host_artifact()?;
+        let zig = if let Some(Either::Version(_)) = self.to_install {
+            self.install_direct(force_ziglang).await?
+        } else {
+            self.install_release(force_ziglang).await?
+        };
+        Ok((zig, false))
+    }
     /// Install the current loaded `to_install` ZigVersion directly without index resolution
     pub async fn install_direct(&mut self, force_ziglang: bool) -> Result<PathBuf, ZvError> {
         const TARGET: &str = "zv::app::install_direct";
@@ -549,14 +942,13 @@ impl App {
         })?;
         tracing::debug!(target: TARGET, tarball = %zig_tarball, "Determined tarball name");
 
-        let ext = if zig_tarball.ends_with(".zip") {
-            ArchiveExt::Zip
-        } else if zig_tarball.ends_with(".tar.xz") {
-            ArchiveExt::TarXz
-        } else {
-            unreachable!("Unknown archive extension for tarball: {}", zig_tarball)
-        };
-        tracing::debug!(target: TARGET, ?ext, "Detected archive format");
+        let ext = ArchiveExt::from_file_name(&zig_tarball).ok_or_else(|| {
+            ZvError::UnsupportedArchive {
+                file: zig_tarball.clone(),
+                context: None,
+            }
+        })?;
+        tracing::debug!(target: TARGET, ?ext, "Archive format from tarball name");
 
         // Initialize network based on force_ziglang flag
         if !force_ziglang {
@@ -577,12 +969,14 @@ impl App {
             tarball_path,
             minisig_path,
             mirror_used,
+            archive_ext,
         } = if !force_ziglang {
             // Use mirrors with optional artifact info (None since we don't have index data)
             self.network
                 .as_mut()
                 .unwrap()
                 .download_version(semver_version, &zig_tarball, None)
+                .instrument(tracing::info_span!("download_version", version = %semver_version))
                 .await?
         } else {
             // Generate ziglang.org URLs directly
@@ -608,6 +1002,7 @@ impl App {
                     None, // No expected shasum
                     None, // No expected size
                 )
+                .instrument(tracing::info_span!("direct_download", version = %semver_version))
                 .await?
         };
         tracing::debug!(
@@ -617,10 +1012,19 @@ impl App {
             ?mirror_used,
             "Download completed"
         );
+        // The server's Content-Type is the better word on the format than the file name
+        let ext = archive_ext.unwrap_or(ext);
 
         let zig_exe = self
             .toolchain_manager
-            .install_version(&tarball_path, semver_version, ext, is_master)
+            .install_version(
+                &tarball_path,
+                semver_version,
+                ext,
+                is_master,
+                toolchain::InstallOrigin::Index,
+            )
+            .instrument(tracing::info_span!("extract", version = %semver_version))
             .await?;
         tracing::info!(
             target: TARGET,
@@ -631,8 +1035,34 @@ impl App {
         remove_files(&[tarball_path.as_path(), minisig_path.as_path()]).await;
         tracing::debug!(target: TARGET, "Cleaned up temporary download files");
 
+        if is_master {
+            self.prune_old_masters().await;
+        }
+
         Ok(zig_exe)
     }
+
+    /// Keep only the newest `master_keep` master builds after installing a new one
+    async fn prune_old_masters(&mut self) {
+        let keep = crate::app::config::load_zv_config(&self.paths.config_file)
+            .ok()
+            .and_then(|c| c.master_keep)
+            .unwrap_or(crate::app::config::DEFAULT_MASTER_KEEP);
+        if keep == 0 {
+            return;
+        }
+
+        let summary = self.toolchain_manager.prune_master_installs(keep).await;
+        if !summary.removed.is_empty() && !utils::is_quiet() {
+            println!(
+                "{} Removed {} old master build{}, freed {}",
+                yansi::Paint::green("✓"),
+                summary.removed.len(),
+                if summary.removed.len() == 1 { "" } else { "s" },
+                crate::tools::human_size(summary.freed_bytes)
+            );
+        }
+    }
     /// Install the current loaded `to_install` ZigRelease
     pub async fn install_release(&mut self, force_ziglang: bool) -> Result<PathBuf, ZvError> {
         const TARGET: &str = "zv::app::install_release";
@@ -664,52 +1094,37 @@ impl App {
         })?;
         tracing::debug!(target: TARGET, tarball = %zig_tarball, "Determined tarball name");
 
-        let ext = if zig_tarball.ends_with(".zip") {
-            ArchiveExt::Zip
-        } else if zig_tarball.ends_with(".tar.xz") {
-            ArchiveExt::TarXz
-        } else {
-            unreachable!("Unknown archive extension for tarball: {}", zig_tarball)
-        };
-        tracing::debug!(target: TARGET, ?ext, "Detected archive format");
+        let ext = ArchiveExt::from_file_name(&zig_tarball).ok_or_else(|| {
+            ZvError::UnsupportedArchive {
+                file: zig_tarball.clone(),
+                context: None,
+            }
+        })?;
+        tracing::debug!(target: TARGET, ?ext, "Archive format from tarball name");
         if !force_ziglang {
             self.ensure_network_with_mirrors().await?;
         } else {
             self.ensure_network().await?;
         }
-        let host_target = utils::host_target().ok_or_else(|| {
-            eyre!(
-                "Could not determine host target for Zig version {}",
-                zig_release.version_string()
-            )
-        })?;
-        tracing::debug!(target: TARGET, %host_target, "Resolved host target");
-
-        let download_artifact = zig_release
-            .target_artifact(&host_target)
-            .ok_or_else(|| {
-                eyre!(
-                    "No download artifact found for target <{}> in release {}",
-                    host_target,
-                    zig_release.version_string()
-                )
-            })
-            .map_err(ZvError::ZigNotFound)?;
+        let download_artifact = host_artifact(&zig_release)?;
         tracing::debug!(
             target: TARGET,
             artifact_url = %download_artifact.ziglang_org_tarball,
             "Selected download artifact"
         );
+        self.warn_if_low_disk_space(&zig_release);
 
         let ZigDownload {
             tarball_path,
             minisig_path,
             mirror_used,
+            archive_ext,
         } = if !force_ziglang {
             self.network
                 .as_mut()
                 .unwrap()
                 .download_version(semver_version, &zig_tarball, Some(download_artifact))
+                .instrument(tracing::info_span!("download_version", version = %semver_version))
                 .await?
         } else {
             tracing::trace!(target: "zv", "Using ziglang.org as download source");
@@ -724,6 +1139,7 @@ impl App {
                     Some(&download_artifact.shasum),
                     Some(download_artifact.size),
                 )
+                .instrument(tracing::info_span!("direct_download", version = %semver_version))
                 .await?
         };
         tracing::debug!(
@@ -733,10 +1149,19 @@ impl App {
             ?mirror_used,
             "Download completed"
         );
+        // The server's Content-Type is the better word on the format than the file name
+        let ext = archive_ext.unwrap_or(ext);
 
         let zig_exe = self
             .toolchain_manager
-            .install_version(&tarball_path, semver_version, ext, is_master)
+            .install_version(
+                &tarball_path,
+                semver_version,
+                ext,
+                is_master,
+                toolchain::InstallOrigin::Index,
+            )
+            .instrument(tracing::info_span!("extract", version = %semver_version))
             .await?;
         tracing::info!(
             target: TARGET,
@@ -750,3 +1175,32 @@ impl App {
         Ok(zig_exe)
     }
 }
+
+/// Artifact of `zig_release` for this host. Errors with the targets that do have a build when
+/// there is none.
+fn host_artifact(zig_release: &ZigRelease) -> Result<&network::ArtifactInfo, ZvError> {
+    let host_target = utils::host_target().ok_or_else(|| {
+        eyre!(
+            "Could not determine host target for Zig version {}",
+            zig_release.version_string()
+        )
+    })?;
+    tracing::debug!(target: "zv::app", %host_target, "Resolved host target");
+
+    zig_release.host_artifact(&host_target).ok_or_else(|| {
+        crate::tools::error(format!(
+            "Zig {} has no build for {}",
+            zig_release.version_string(),
+            host_target
+        ));
+        crate::suggest!(
+            "Builds are available for: {}",
+            zig_release.get_available_targets().join(", ")
+        );
+        ZvError::ZigNotFound(eyre!(
+            "No download artifact found for target <{}> in release {}",
+            host_target,
+            zig_release.version_string()
+        ))
+    })
+}
@@ -7,55 +7,103 @@ use color_eyre::eyre::Context;
 use futures::StreamExt;
 use tokio::io::AsyncWriteExt;
 
-use crate::{NetErr, ZvError, app::utils::ProgressHandle};
+use crate::{ArchiveExt, NetErr, ZvError, app::utils::ProgressHandle};
 
 const TARGET: &str = "zv::network::download";
 
+/// Longest `Retry-After` we are willing to sit out on the same mirror before giving up on it
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// How far a response may be off the size recorded in the index before the download is aborted
+const SIZE_TOLERANCE_BYTES: u64 = 4096;
+
 /// Download a single file with HTTP status code handling
 ///
 /// This function handles the complete download process for a single file with comprehensive
-/// error handling and logging for different failure scenarios.
+/// error handling and logging for different failure scenarios. Returns the archive format the
+/// server's `Content-Type` announced, if any.
+///
+/// A partial file an interrupted attempt left at `dest_path` is resumed with a `Range` request,
+/// starting over if the server sends the whole file instead.
 pub(in crate::app::network) async fn download_file(
     client: &reqwest::Client,
     url: &str,
     dest_path: &Path,
     expected_size: u64,
     progress_handle: &ProgressHandle,
-) -> Result<(), NetErr> {
+) -> Result<Option<ArchiveExt>, NetErr> {
     tracing::debug!(target: TARGET, "Starting download request for URL: {}", url);
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                tracing::warn!(target: TARGET, "Request timeout for URL: {} - This may indicate network connectivity issues or server overload", url);
-                NetErr::Timeout(format!("Request timeout for {}", url))
-            } else if e.is_connect() {
-                tracing::warn!(target: TARGET, "Connection error for URL: {} - Unable to establish connection to server", url);
-                NetErr::Reqwest(e)
-            } else {
-                tracing::error!(target: TARGET, "Network error during request to {}: {} - This may indicate DNS issues or network problems", url, e);
-                NetErr::Reqwest(e)
+    let resume_from = resumable_len(dest_path, expected_size).await;
+    let mut waited_for_rate_limit = false;
+    let response = loop {
+        let mut request = client.get(url);
+        if resume_from > 0 {
+            tracing::debug!(target: TARGET, "Resuming download of {} from byte {}", url, resume_from);
+            request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    tracing::warn!(target: TARGET, "Request timeout for URL: {} - This may indicate network connectivity issues or server overload", url);
+                    NetErr::Timeout(format!("Request timeout for {}", url))
+                } else if e.is_connect() {
+                    tracing::warn!(target: TARGET, "Connection error for URL: {} - Unable to establish connection to server", url);
+                    NetErr::Reqwest(e)
+                } else {
+                    tracing::error!(target: TARGET, "Network error during request to {}: {} - This may indicate DNS issues or network problems", url, e);
+                    NetErr::Reqwest(e)
+                }
+            })?;
+
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            break response;
+        }
+
+        // Honor short Retry-After windows on the same mirror once; anything longer
+        // is surfaced to the caller so it can demote this mirror and move on.
+        let retry_after = parse_retry_after(response.headers());
+        match retry_after {
+            Some(delay) if !waited_for_rate_limit && delay <= MAX_RETRY_AFTER => {
+                tracing::warn!(target: TARGET, "HTTP 429 Too Many Requests for URL: {} - Mirror asked us to retry after {}s, waiting before retrying", url, delay.as_secs());
+                let _ = progress_handle
+                    .update(format!(
+                        "Rate limited by mirror, retrying in {}s...",
+                        delay.as_secs()
+                    ))
+                    .await;
+                tokio::time::sleep(delay).await;
+                waited_for_rate_limit = true;
             }
-        })?;
+            _ => {
+                tracing::warn!(target: TARGET, "HTTP 429 Too Many Requests for URL: {} (Retry-After: {:?}) - Mirror is rate limiting requests. Will retry with different mirror.", url, retry_after);
+                return Err(NetErr::RateLimited { retry_after });
+            }
+        }
+    };
 
     let status = response.status();
     tracing::debug!(target: TARGET, "Received HTTP response with status: {} for URL: {}", status, url);
 
     // Handle specific HTTP status codes as retriable failures with detailed explanations
-    match status.as_u16() {
+    let offset = match status.as_u16() {
         200 => {
             // Success, proceed with download
             tracing::trace!(target: TARGET, "HTTP 200 OK received, proceeding with file download from {}", url);
+            0
         }
-        503 => {
-            tracing::warn!(target: TARGET, "HTTP 503 Service Unavailable for URL: {} - Mirror is experiencing scheduled downtime or maintenance. Will retry with different mirror.", url);
+        206 if resume_from > 0 && content_range_start(response.headers()) == Some(resume_from) => {
+            tracing::trace!(target: TARGET, "HTTP 206 Partial Content received, appending to the {} bytes already downloaded from {}", resume_from, url);
+            resume_from
+        }
+        206 => {
+            tracing::warn!(target: TARGET, "HTTP 206 Partial Content for URL: {} doesn't continue at byte {} - discarding the partial download. Will retry with different mirror.", url, resume_from);
             return Err(NetErr::HTTP(status));
         }
-        429 => {
-            tracing::warn!(target: TARGET, "HTTP 429 Too Many Requests for URL: {} - Mirror is rate limiting requests. Will retry with different mirror after delay.", url);
+        503 => {
+            tracing::warn!(target: TARGET, "HTTP 503 Service Unavailable for URL: {} - Mirror is experiencing scheduled downtime or maintenance. Will retry with different mirror.", url);
             return Err(NetErr::HTTP(status));
         }
         404 => {
@@ -78,12 +126,21 @@ pub(in crate::app::network) async fn download_file(
             tracing::warn!(target: TARGET, "Unexpected HTTP status {} for URL: {} - Unknown response code. Will retry with different mirror.", status, url);
             return Err(NetErr::HTTP(status));
         }
-    }
+    };
     tracing::trace!(target: TARGET, "Initiating streaming download for {} bytes from {}", expected_size, url);
-    match stream_download_file(client, url, dest_path, expected_size, progress_handle).await {
-        Ok(()) => {
+    match write_response(
+        response,
+        url,
+        dest_path,
+        expected_size,
+        offset,
+        progress_handle,
+    )
+    .await
+    {
+        Ok(archive_ext) => {
             tracing::debug!(target: TARGET, "Successfully completed download from {}", url);
-            Ok(())
+            Ok(archive_ext)
         }
         Err(e) => {
             tracing::error!(target: TARGET, "Download failed from {}: {}", url, e);
@@ -102,19 +159,21 @@ pub(in crate::app::network) async fn download_file(
 /// * `client` - HTTP client to use for the request
 /// * `url` - URL to download from
 /// * `dest_path` - Destination file path to write to
-/// * `expected_size` - Expected file size in bytes for progress calculation
+/// * `expected_size` - Expected file size in bytes for progress calculation; when nonzero, a
+///   `Content-Length` or body that doesn't match it aborts the download early
 /// * `progress_handle` - Handle for progress reporting
 ///
 /// # Returns
-/// * `Ok(())` on successful download
-/// * `Err(NetErr)` on network errors, timeouts, or file I/O errors
+/// * `Ok(Some(ext))` on successful download, when `Content-Type` names an archive format
+/// * `Ok(None)` on successful download with no such header (or a generic one)
+/// * `Err(NetErr)` on network errors, timeouts, size mismatches, or file I/O errors
 pub(in crate::app::network) async fn stream_download_file(
     client: &reqwest::Client,
     url: &str,
     dest_path: &Path,
     expected_size: u64,
     progress_handle: &ProgressHandle,
-) -> Result<(), NetErr> {
+) -> Result<Option<ArchiveExt>, NetErr> {
     // Start the download request
     let response = client.get(url).send().await.map_err(|e| {
         if e.is_timeout() {
@@ -130,14 +189,54 @@ pub(in crate::app::network) async fn stream_download_file(
     })?;
 
     // Check response status
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = parse_retry_after(response.headers());
+        tracing::warn!(target: TARGET, "HTTP 429 Too Many Requests for URL: {} (Retry-After: {:?})", url, retry_after);
+        return Err(NetErr::RateLimited { retry_after });
+    }
     if !response.status().is_success() {
         let status = response.status();
         tracing::error!(target: TARGET, "HTTP error {} for URL: {}", status, url);
         return Err(NetErr::HTTP(status));
     }
 
+    write_response(response, url, dest_path, expected_size, 0, progress_handle).await
+}
+
+/// Write the body of `response` to `dest_path`, the part of a [stream_download_file] after the
+/// request. With a nonzero `offset` the body continues the partial file already there.
+async fn write_response(
+    response: reqwest::Response,
+    url: &str,
+    dest_path: &Path,
+    expected_size: u64,
+    offset: u64,
+    progress_handle: &ProgressHandle,
+) -> Result<Option<ArchiveExt>, NetErr> {
+    // A mirror answering with an error page under 200 OK would otherwise only be caught by
+    // the checksum, after downloading the whole thing
+    if expected_size > 0
+        && let Some(got) = response.content_length().map(|len| offset + len)
+        && exceeds_size_tolerance(expected_size, got)
+    {
+        tracing::warn!(target: TARGET, "Content-Length {} for URL: {} doesn't match the expected {} bytes", got, url, expected_size);
+        return Err(NetErr::SizeMismatch {
+            expected: expected_size,
+            got,
+        });
+    }
+
+    let archive_ext = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(ArchiveExt::from_content_type);
+    tracing::trace!(target: TARGET, ?archive_ext, "Archive format from Content-Type for {}", url);
+
     // Get content length for progress calculation
-    let content_length = response.content_length().unwrap_or(expected_size);
+    let content_length = response
+        .content_length()
+        .map_or(expected_size, |len| offset + len);
     let actual_size = if expected_size == 0 {
         content_length
     } else {
@@ -145,15 +244,22 @@ pub(in crate::app::network) async fn stream_download_file(
     };
     tracing::debug!(target: TARGET, "Starting download: {} bytes from {} (content-length: {})", actual_size, url, content_length);
 
-    // Create the destination file
-    let mut file = tokio::fs::File::create(dest_path)
-        .await
+    // Create the destination file, or reopen the partial one being resumed
+    let file = if offset > 0 {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(dest_path)
+            .await
+    } else {
+        tokio::fs::File::create(dest_path).await
+    };
+    let mut file = file
         .map_err(ZvError::Io)
         .wrap_err_with(|| format!("Failed to create destination file: {}", dest_path.display()))?;
 
     // Stream the response body
     let mut stream = response.bytes_stream();
-    let mut downloaded = 0u64;
+    let mut downloaded = offset;
     let mut last_progress_update = Instant::now();
     const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_millis(250); // Update progress every 250ms
 
@@ -175,6 +281,13 @@ pub(in crate::app::network) async fn stream_download_file(
             })?;
 
         downloaded += chunk.len() as u64;
+        if expected_size > 0 && downloaded > expected_size + SIZE_TOLERANCE_BYTES {
+            tracing::warn!(target: TARGET, "Download from {} exceeded the expected {} bytes", url, expected_size);
+            return Err(NetErr::SizeMismatch {
+                expected: expected_size,
+                got: downloaded,
+            });
+        }
 
         // Update progress periodically to avoid overwhelming the progress bar
         let now = Instant::now();
@@ -197,7 +310,11 @@ pub(in crate::app::network) async fn stream_download_file(
                 format!("Downloading {:.1} MB", downloaded_mb)
             };
 
-            if let Err(e) = progress_handle.update(progress_msg).await {
+            let total = (actual_size > 0).then_some(actual_size);
+            if let Err(e) = progress_handle
+                .download(downloaded, total, progress_msg)
+                .await
+            {
                 tracing::warn!(target: TARGET, "Failed to update progress: {}", e);
             }
 
@@ -214,12 +331,46 @@ pub(in crate::app::network) async fn stream_download_file(
     // Final progress update
     let downloaded_mb = downloaded as f64 / 1_048_576.0;
     let final_msg = format!("Download completed: {:.1} MB", downloaded_mb);
-    if let Err(e) = progress_handle.update(final_msg).await {
+    let total = (actual_size > 0).then_some(actual_size);
+    if let Err(e) = progress_handle.download(downloaded, total, final_msg).await {
         tracing::warn!(target: TARGET, "Failed to update final progress: {}", e);
     }
 
     tracing::trace!(target: TARGET, "Successfully downloaded {} bytes to {}", downloaded, dest_path.display());
-    Ok(())
+    Ok(archive_ext)
+}
+
+/// Whether a response of `got` bytes is too far off the `expected` size to be the right file
+fn exceeds_size_tolerance(expected: u64, got: u64) -> bool {
+    expected.abs_diff(got) > SIZE_TOLERANCE_BYTES
+}
+
+/// Length of an interrupted download left at `dest_path`, or 0 if there is none to resume.
+/// Without an expected size there's no telling a partial file from a complete one.
+async fn resumable_len(dest_path: &Path, expected_size: u64) -> u64 {
+    if expected_size == 0 {
+        return 0;
+    }
+    match tokio::fs::metadata(dest_path).await {
+        Ok(metadata) if metadata.len() < expected_size => metadata.len(),
+        _ => 0,
+    }
+}
+
+/// First byte position of a `Content-Range: bytes <start>-<end>/<total>` header
+fn content_range_start(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    let (start, _) = value.trim().strip_prefix("bytes ")?.split_once('-')?;
+    start.parse().ok()
+}
+
+/// Whether `err` cut a transfer short, leaving a partial file the next attempt can resume
+pub(in crate::app::network) fn is_interrupted(err: &NetErr) -> bool {
+    match err {
+        NetErr::Reqwest(e) => e.is_body() || e.is_decode() || e.is_timeout(),
+        NetErr::Timeout(_) | NetErr::Stalled { .. } => true,
+        _ => false,
+    }
 }
 
 /// Move file from temporary location to final destination atomically
@@ -358,3 +509,235 @@ pub(in crate::app::network) async fn move_to_final_location(
         }
     }
 }
+
+/// Parse a `Retry-After` header into a wait duration
+///
+/// The header may carry either delta-seconds (`Retry-After: 120`) or an HTTP-date
+/// (`Retry-After: Wed, 21 Oct 2015 07:28:00 GMT`). Dates in the past yield a zero duration.
+/// Returns `None` when the header is absent or unparseable.
+pub(in crate::app::network) fn parse_retry_after(
+    headers: &reqwest::header::HeaderMap,
+) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    fn headers_with(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after(&headers_with("7")),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            parse_retry_after(&headers_with(" 0 ")),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        // A date in the past means "retry now"
+        assert_eq!(
+            parse_retry_after(&headers_with("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+
+        let future = chrono::Utc::now() + chrono::Duration::seconds(120);
+        let delay = parse_retry_after(&headers_with(&future.to_rfc2822())).unwrap();
+        assert!(delay > Duration::from_secs(100) && delay <= Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_parse_retry_after_missing_or_invalid() {
+        assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+        assert_eq!(parse_retry_after(&headers_with("soon")), None);
+    }
+
+    #[test]
+    fn content_range_start_reads_the_first_byte() {
+        let range = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                reqwest::header::CONTENT_RANGE,
+                HeaderValue::from_str(value).unwrap(),
+            );
+            content_range_start(&headers)
+        };
+        assert_eq!(range("bytes 1024-4095/4096"), Some(1024));
+        assert_eq!(range("bytes 0-0/*"), Some(0));
+        assert_eq!(range("bytes */4096"), None);
+        assert_eq!(content_range_start(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn size_tolerance_allows_small_differences_only() {
+        assert!(!exceeds_size_tolerance(50_000_000, 50_000_000));
+        assert!(!exceeds_size_tolerance(
+            50_000_000,
+            50_000_000 - SIZE_TOLERANCE_BYTES
+        ));
+        assert!(exceeds_size_tolerance(50_000_000, 1_234));
+        assert!(exceeds_size_tolerance(1_234, 50_000_000));
+    }
+
+    #[tokio::test]
+    async fn error_page_with_wrong_content_length_is_rejected_early() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zig.tar.xz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>Not here</html>"))
+            .mount(&server)
+            .await;
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("zig.tar.xz");
+
+        let err = stream_download_file(
+            &reqwest::Client::new(),
+            &format!("{}/zig.tar.xz", server.uri()),
+            &dest,
+            50_000_000,
+            &ProgressHandle::spawn(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            NetErr::SizeMismatch {
+                expected: 50_000_000,
+                got: 21
+            }
+        ));
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn content_type_reports_the_archive_format() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (name, content_type) in [
+            ("zig.zip", "application/zip"),
+            ("zig.tar.xz", "application/x-xz; charset=binary"),
+            ("zig.bin", "application/octet-stream"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{name}")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-type", content_type)
+                        .set_body_bytes(vec![0u8; 16]),
+                )
+                .mount(&server)
+                .await;
+        }
+        let temp = tempfile::tempdir().unwrap();
+
+        let mut formats = Vec::new();
+        for name in ["zig.zip", "zig.tar.xz", "zig.bin"] {
+            let ext = stream_download_file(
+                &reqwest::Client::new(),
+                &format!("{}/{name}", server.uri()),
+                &temp.path().join(name),
+                16,
+                &ProgressHandle::spawn(),
+            )
+            .await
+            .unwrap();
+            formats.push(ext);
+        }
+        assert_eq!(
+            formats,
+            [Some(ArchiveExt::Zip), Some(ArchiveExt::TarXz), None]
+        );
+    }
+
+    /// Collects what the JSON progress actor writes
+    #[derive(Clone, Default)]
+    struct SharedBuf(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn json_progress_reports_download_events() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = vec![7u8; 64 * 1024];
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zig.tar.xz"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+            .mount(&server)
+            .await;
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("zig.tar.xz");
+
+        let out = SharedBuf::default();
+        let progress = ProgressHandle::spawn_json(out.clone());
+        progress.start("Downloading zig.tar.xz").await.unwrap();
+        stream_download_file(
+            &reqwest::Client::new(),
+            &format!("{}/zig.tar.xz", server.uri()),
+            &dest,
+            body.len() as u64,
+            &progress,
+        )
+        .await
+        .unwrap();
+        progress.finish("Download complete").await.unwrap();
+        progress.shutdown().await.unwrap();
+
+        let output = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.first().unwrap()["event"], "start");
+        let last_download = events
+            .iter()
+            .rev()
+            .find(|e| e["event"] == "download")
+            .unwrap();
+        assert_eq!(last_download["bytes"], body.len() as u64);
+        assert_eq!(last_download["total"], body.len() as u64);
+        assert_eq!(
+            events.last().unwrap(),
+            &serde_json::json!({"event": "done", "message": "Download complete"})
+        );
+    }
+}
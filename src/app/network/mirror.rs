@@ -21,22 +21,22 @@
 //! # Example Usage
 //!
 //! ```rust,no_run
-//! use std::sync::Arc;
-//! use reqwest::Client;
+//! use std::collections::HashMap;
+//! use crate::app::constants::ZIG_COMMUNITY_MIRRORS;
 //! use crate::app::network::mirror::MirrorManager;
 //! use crate::app::network::CacheStrategy;
 //!
 //! async fn example() -> Result<(), Box<dyn std::error::Error>> {
-//!     let client = Arc::new(Client::new());
 //!     let cache_path = "/tmp/mirrors.toml";
 //!     
 //!     let mut manager = MirrorManager::init_and_load(
 //!         cache_path,
 //!         CacheStrategy::RespectTtl,
-//!         client
+//!         HashMap::new(),
+//!         ZIG_COMMUNITY_MIRRORS,
 //!     ).await?;
 //!     
-//!     let random_mirror = manager.get_random_mirror().await?;
+//!     let random_mirror = manager.get_random_mirror(&[]).await?;
 //!     println!("Using mirror: {}", random_mirror.base_url);
 //!     
 //!     Ok(())
@@ -44,25 +44,28 @@
 //! ```
 
 use std::{
+    collections::HashMap,
     convert::TryFrom,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use super::download::download_file;
 use super::{CacheStrategy, TARGET};
 use crate::{
-    CfgErr, NetErr,
+    ArchiveExt, CfgErr, NetErr, ZvError,
     app::{
-        MIRRORS_TTL_DAYS,
+        ALLOW_HTTP_FALLBACK, MIRRORS_TTL_DAYS,
         constants::ZIG_COMMUNITY_MIRRORS,
-        utils::{ProgressHandle, verify_checksum, zv_agent},
+        utils::{ProgressHandle, is_future_stamp, remove_files, verify_checksum, zv_agent},
     },
 };
 use chrono::{DateTime, Utc};
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, eyre};
 use reqwest::Client;
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use url::Url;
 
 // ============================================================================
@@ -105,6 +108,56 @@ pub struct Mirror {
     pub base_url: Url,
     pub layout: Layout,
     pub rank: u8,
+    /// Added with `zv sync --add-mirror` rather than taken from the community list, so it
+    /// survives list refreshes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub user_added: bool,
+    /// Download history, kept across list refreshes
+    #[serde(default, skip_serializing_if = "MirrorStats::is_empty")]
+    pub stats: MirrorStats,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Where [`Mirror::download`] saves a tarball and its signature, and what they must match
+pub struct DownloadRequest<'a> {
+    /// Path where the tarball should be saved
+    pub tarball_path: &'a Path,
+    /// Path where the minisig file should be saved
+    pub minisig_path: &'a Path,
+    /// Key the tarball's signature must verify against
+    pub minisign_pubkey: &'a str,
+    /// Expected SHA256 checksum of the tarball, if known
+    pub expected_shasum: Option<&'a str>,
+    /// Expected size of the tarball in bytes, if known
+    pub expected_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/// Outcome counters for downloads from one mirror, shown by `zv mirrors stats`
+pub struct MirrorStats {
+    pub successes: u64,
+    pub failures: u64,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_failure: Option<DateTime<Utc>>,
+    /// Total size of the archives this mirror delivered successfully
+    pub bytes_served: u64,
+}
+
+impl MirrorStats {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn record_success(&mut self, bytes: u64) {
+        self.successes += 1;
+        self.bytes_served += bytes;
+        self.last_success = Some(Utc::now());
+    }
+
+    pub fn record_failure(&mut self) {
+        self.failures += 1;
+        self.last_failure = Some(Utc::now());
+    }
 }
 
 // ============================================================================
@@ -122,27 +175,22 @@ impl Mirror {
     /// * `client` - HTTP client for making requests
     /// * `semver_version` - Version to download
     /// * `zig_tarball` - Name of the tarball file
-    /// * `tarball_path` - Path where tarball should be saved
-    /// * `minisig_path` - Path where minisig file should be saved
-    /// * `expected_shasum` - Optional expected SHA256 checksum for verification
-    /// * `expected_size` - Optional expected size of the tarball in bytes
+    /// * `request` - Where to save the tarball and minisig, and what they are checked against
     /// * `progress_handle` - Handle for progress reporting
     ///
     /// # Returns
     ///
-    /// `Ok(Layout)` with the layout that was successfully used if download succeeds,
+    /// `Ok((Layout, Option<ArchiveExt>))` with the layout that was successfully used and the
+    /// archive format the mirror's `Content-Type` announced, if download succeeds,
     /// otherwise returns the appropriate `NetErr` with detailed context about the failure.
     pub async fn download(
         &self,
         client: &reqwest::Client,
         semver_version: &semver::Version,
         zig_tarball: &str,
-        tarball_path: &Path,
-        minisig_path: &Path,
-        expected_shasum: Option<&str>,
-        expected_size: Option<u64>,
+        request: DownloadRequest<'_>,
         progress_handle: &ProgressHandle,
-    ) -> Result<Layout, NetErr> {
+    ) -> Result<(Layout, Option<ArchiveExt>), NetErr> {
         const TARGET: &str = "zv::network::mirror::download";
         tracing::debug!(target: TARGET, "Starting download with mirror: {} (rank: {})", self.base_url, self.rank);
 
@@ -152,16 +200,13 @@ impl Mirror {
                 client,
                 semver_version,
                 zig_tarball,
-                tarball_path,
-                minisig_path,
-                expected_shasum,
-                expected_size,
+                request,
                 progress_handle,
                 false,
             )
             .await
         {
-            Ok(layout) => Ok(layout),
+            Ok(downloaded) => Ok(downloaded),
             Err(net_err) => {
                 // If the failure was an HTTP 404, try the alternate layout
                 if matches!(net_err, NetErr::HTTP(status) if status.as_u16() == 404) {
@@ -174,36 +219,75 @@ impl Mirror {
                             client,
                             semver_version,
                             zig_tarball,
-                            tarball_path,
-                            minisig_path,
-                            expected_shasum,
-                            expected_size,
+                            request,
                             progress_handle,
                             true,
                         )
                         .await;
                 }
 
+                // A TLS/connection failure may be recoverable over plain HTTP, but only when the
+                // tarball can be checked against both the index checksum and the minisign signature
+                if *ALLOW_HTTP_FALLBACK
+                    && matches!(&net_err, NetErr::Reqwest(e) if e.is_connect())
+                    && request.expected_shasum.is_some()
+                    && let Some(http_mirror) = self.http_fallback()
+                {
+                    tracing::warn!(target: TARGET,
+                                  "HTTPS connection to mirror {} failed: {}. Retrying over HTTP (ZV_ALLOW_HTTP_FALLBACK=1)",
+                                  self.base_url, net_err);
+                    return http_mirror
+                        .try_download_with_layout(
+                            client,
+                            semver_version,
+                            zig_tarball,
+                            request,
+                            progress_handle,
+                            false,
+                        )
+                        .await;
+                }
+
                 // Otherwise propagate the concrete network error
                 Err(net_err)
             }
         }
     }
 
+    /// Plain-HTTP twin of an HTTPS mirror, used for the opt-in transport fallback
+    fn http_fallback(&self) -> Option<Mirror> {
+        if self.base_url.scheme() != "https" {
+            return None;
+        }
+        let mut base_url = self.base_url.clone();
+        base_url.set_scheme("http").ok()?;
+        Some(Mirror {
+            base_url,
+            layout: self.layout,
+            rank: self.rank,
+            user_added: self.user_added,
+            stats: MirrorStats::default(),
+        })
+    }
+
     /// Internal helper to try download with a specific layout
     async fn try_download_with_layout(
         &self,
         client: &reqwest::Client,
         semver_version: &semver::Version,
         zig_tarball: &str,
-        tarball_path: &Path,
-        minisig_path: &Path,
-        expected_shasum: Option<&str>,
-        expected_size: Option<u64>,
+        request: DownloadRequest<'_>,
         progress_handle: &ProgressHandle,
         use_alternate_layout: bool,
-    ) -> Result<Layout, NetErr> {
+    ) -> Result<(Layout, Option<ArchiveExt>), NetErr> {
         const TARGET: &str = "zv::network::mirror::try_download_with_layout";
+        let DownloadRequest {
+            tarball_path,
+            minisig_path,
+            minisign_pubkey,
+            expected_shasum,
+            expected_size,
+        } = request;
 
         // Determine which layout to use
         let mirror_for_download = if use_alternate_layout {
@@ -246,17 +330,19 @@ impl Mirror {
         };
 
         // Phase 1: Download tarball
-        match download_file(
+        let archive_ext = match download_file(
             client,
             &tarball_url,
             tarball_path,
             expected_size.unwrap_or(0),
             progress_handle,
         )
+        .instrument(tracing::info_span!("transfer", url = %tarball_url))
         .await
         {
-            Ok(()) => {
+            Ok(archive_ext) => {
                 tracing::debug!(target: TARGET, "Proceeding to checksum verification...");
+                archive_ext
             }
             Err(net_err) => {
                 tracing::trace!(target: TARGET, "Tarball download failed from mirror {}: {}", mirror_for_download.base_url, net_err);
@@ -275,12 +361,15 @@ impl Mirror {
 
                 return Err(net_err);
             }
-        }
+        };
 
         // Phase 2: Verify checksum (if available)
         if let Some(shasum) = expected_shasum {
             tracing::debug!(target: TARGET, "Verifying tarball integrity");
-            match verify_checksum(tarball_path, shasum).await {
+            match verify_checksum(tarball_path, shasum, Some(progress_handle))
+                .instrument(tracing::info_span!("checksum"))
+                .await
+            {
                 Ok(()) => {
                     tracing::debug!(target: TARGET, "Checksum verification successful");
                 }
@@ -316,8 +405,11 @@ impl Mirror {
         }
 
         // For minisig, we don't have size info, so use 0
-        match download_file(client, &minisig_url, minisig_path, 0, progress_handle).await {
-            Ok(()) => {
+        match download_file(client, &minisig_url, minisig_path, 0, progress_handle)
+            .instrument(tracing::info_span!("transfer", url = %minisig_url))
+            .await
+        {
+            Ok(_) => {
                 tracing::debug!(target: TARGET, "Minisig download completed successfully");
             }
             Err(net_err) => {
@@ -348,6 +440,26 @@ impl Mirror {
             }
         }
 
+        // A mirror could pair the tarball with a signature for a different file
+        if let Err(e) = crate::app::minisign::check_signature_filename(minisig_path, zig_tarball) {
+            tracing::error!(target: TARGET, "Signature from mirror {} doesn't match {}: {}", mirror_for_download.base_url, zig_tarball, e);
+            remove_files(&[tarball_path, minisig_path]).await;
+            return Err(NetErr::Other(e.into()));
+        }
+
+        // The checksum comes from the index; the signature ties the tarball to the Zig release key
+        // even if a mirror and the index agree on tampered bytes
+        if let Err(e) = crate::app::minisign::verify_minisign_signature(
+            minisign_pubkey,
+            zig_tarball,
+            tarball_path,
+            minisig_path,
+        ) {
+            tracing::error!(target: TARGET, "Signature verification failed for tarball from mirror {}: {}", mirror_for_download.base_url, e);
+            remove_files(&[tarball_path, minisig_path]).await;
+            return Err(NetErr::Other(e.into()));
+        }
+
         // Verify both files exist and have reasonable sizes
         let tarball_size = match tokio::fs::metadata(tarball_path).await {
             Ok(metadata) => {
@@ -392,7 +504,7 @@ impl Mirror {
         tracing::debug!(target: TARGET, "Download attempt completed successfully with mirror {} - tarball: {:.1} MB, minisig: {} bytes",
                      self.base_url, tarball_size as f64 / 1_048_576.0, minisig_size);
 
-        Ok(mirror_for_download.layout)
+        Ok((mirror_for_download.layout, archive_ext))
     }
 
     /// Get the primary download URL based on layout
@@ -419,6 +531,8 @@ impl Mirror {
             base_url: self.base_url.clone(),
             layout: !self.layout,
             rank: self.rank,
+            user_added: self.user_added,
+            stats: MirrorStats::default(),
         };
         alternate.get_download_url(version, tarball)
     }
@@ -467,6 +581,8 @@ impl TryFrom<&str> for Mirror {
             layout,
             base_url,
             rank: 1,
+            user_added: false,
+            stats: MirrorStats::default(),
         })
     }
 }
@@ -493,9 +609,26 @@ impl MirrorsIndex {
         }
     }
 
-    /// Check if the cache has expired based on TTL
+    /// How long ago the list was synced, e.g. "5 minutes ago" or "2 weeks ago"
+    pub fn get_last_synced_display(&self) -> String {
+        relative_time(Utc::now() - self.last_synced)
+    }
+
+    /// Check if the cache has expired based on TTL. A `last_synced` in the future counts as
+    /// expired, see [`is_future_stamp`].
     pub fn is_expired(&self) -> bool {
-        self.last_synced + chrono::Duration::days(*MIRRORS_TTL_DAYS) < Utc::now()
+        is_future_stamp(self.last_synced, "mirrors")
+            || self.last_synced + chrono::Duration::days(*MIRRORS_TTL_DAYS) < Utc::now()
+    }
+
+    /// Time left until the list expires and is refreshed from the network. `None` once it has
+    /// expired, including a `last_synced` in the future.
+    pub fn get_ttl_remaining(&self) -> Option<std::time::Duration> {
+        if self.last_synced > Utc::now() {
+            return None;
+        }
+        let expires_at = self.last_synced + chrono::Duration::days(*MIRRORS_TTL_DAYS);
+        (expires_at - Utc::now()).to_std().ok()
     }
 
     /// Load mirrors index from disk (PreferCache strategy)
@@ -547,28 +680,39 @@ pub struct MirrorManager {
     mirrors_index: Option<MirrorsIndex>,
     /// Path to the mirrors cache file
     cache_path: PathBuf,
+    /// User-configured rank overrides keyed by mirror URL (`mirror.priority` in zv.toml)
+    priority_overrides: HashMap<String, u8>,
+    /// Where the plain-text mirrors list is fetched from
+    mirrors_url: String,
 }
 
 impl MirrorManager {
     // ============================================================================
     // MIRROR MANAGER - CONSTRUCTION AND INITIALIZATION
     // ============================================================================
-    /// Create a new mirror manager (doesn't load mirrors yet)
-    pub fn new(cache_path: impl AsRef<Path>) -> Result<Self> {
+    /// Create a new mirror manager (doesn't load mirrors yet) that fetches the list with `client`
+    pub fn new(cache_path: impl AsRef<Path>, client: Client) -> Result<Self> {
         Ok(Self {
-            client: super::create_client()?,
+            client,
             mirrors: Vec::with_capacity(7), // 7 mirrors listed as of September 2025
             mirrors_index: None,
             cache_path: cache_path.as_ref().to_path_buf(),
+            priority_overrides: HashMap::new(),
+            mirrors_url: ZIG_COMMUNITY_MIRRORS.to_string(),
         })
     }
 
-    /// Create manager and immediately load mirrors
+    /// Create manager and immediately load mirrors from `mirrors_url`, applying any configured rank overrides
     pub async fn init_and_load(
         cache_path: impl AsRef<Path>,
+        client: Client,
         cache_strategy: CacheStrategy,
+        priority_overrides: HashMap<String, u8>,
+        mirrors_url: &str,
     ) -> Result<Self, NetErr> {
-        let mut manager = Self::new(cache_path)?;
+        let mut manager = Self::new(cache_path, client)?;
+        manager.priority_overrides = priority_overrides;
+        manager.mirrors_url = mirrors_url.to_string();
         manager.load_mirrors(cache_strategy).await?;
         Ok(manager)
     }
@@ -631,22 +775,45 @@ impl MirrorManager {
         if let Some(ref index) = self.mirrors_index {
             self.mirrors = index.mirrors.clone();
         }
+        self.apply_priority_overrides();
     }
 
-    /// Refresh mirrors from network and cache them, preserving existing layouts and ranks
+    /// Refresh mirrors from network and cache them, preserving existing layouts, ranks and stats.
+    /// If the list can't be fetched, fall back to the cached list however old it is.
     async fn refresh_from_network(&mut self) -> Result<(), NetErr> {
-        let fresh_mirrors = self.fetch_network_mirrors().await?;
+        let fresh_mirrors = match self.fetch_network_mirrors().await {
+            Ok(mirrors) => mirrors,
+            Err(err) => {
+                let Ok(stale) = MirrorsIndex::load_from_disk(&self.cache_path).await else {
+                    return Err(err);
+                };
+                tracing::warn!(
+                    target: TARGET,
+                    "Could not refresh the community mirrors list ({err}); using the cached list synced {}",
+                    stale.get_last_synced_display()
+                );
+                self.mirrors_index = Some(stale);
+                self.apply_cached_mirrors_index();
+                return Ok(());
+            }
+        };
 
-        // Try to load existing cached mirrors to preserve layouts and ranks
+        // Try to load existing cached mirrors to preserve layouts, ranks and download stats
         let merged_mirrors = match MirrorsIndex::load_from_disk(&self.cache_path).await {
             Ok(cached_index) => {
+                let user_added: Vec<Mirror> = cached_index
+                    .mirrors
+                    .iter()
+                    .filter(|m| m.user_added)
+                    .cloned()
+                    .collect();
                 let cached_mirrors_map: std::collections::HashMap<String, Mirror> = cached_index
                     .mirrors
                     .into_iter()
                     .map(|m| (m.base_url.to_string(), m))
                     .collect();
 
-                let merged: Vec<Mirror> = fresh_mirrors
+                let mut merged: Vec<Mirror> = fresh_mirrors
                     .into_iter()
                     .map(|mut fresh_mirror| {
                         if let Some(cached_mirror) =
@@ -654,13 +821,21 @@ impl MirrorManager {
                         {
                             fresh_mirror.layout = cached_mirror.layout;
                             fresh_mirror.rank = cached_mirror.rank;
+                            fresh_mirror.stats = cached_mirror.stats.clone();
                         }
                         fresh_mirror
                     })
                     .collect();
 
-                tracing::debug!(target: TARGET, "Merged layouts and ranks from {} cached mirrors into {} fresh mirrors",
+                tracing::debug!(target: TARGET, "Merged layouts, ranks and stats from {} cached mirrors into {} fresh mirrors",
                              cached_mirrors_map.len(), merged.len());
+
+                // Mirrors the user added by hand aren't in the community list; keep them
+                for user_mirror in user_added {
+                    if !merged.iter().any(|m| m.base_url == user_mirror.base_url) {
+                        merged.push(user_mirror);
+                    }
+                }
                 merged
             }
             Err(_) => {
@@ -670,6 +845,7 @@ impl MirrorManager {
         };
 
         self.mirrors = merged_mirrors;
+        self.apply_priority_overrides();
         let index = MirrorsIndex::new(self.mirrors.clone());
 
         // Save to cache (log errors but don't fail)
@@ -681,19 +857,47 @@ impl MirrorManager {
         Ok(())
     }
 
-    /// Fetch mirrors from the network
+    /// Fetch mirrors from the network, retrying with backoff so that a single transient failure
+    /// doesn't disable mirrors for the whole session
     async fn fetch_network_mirrors(&self) -> Result<Vec<Mirror>, NetErr> {
-        tracing::debug!(target: TARGET, "Fetching mirrors from {}", ZIG_COMMUNITY_MIRRORS);
+        const ATTEMPTS: u32 = 3;
+        const BACKOFF: Duration = Duration::from_millis(250);
+
+        let mut attempt = 1;
+        loop {
+            match self.fetch_network_mirrors_once().await {
+                Ok(mirrors) => return Ok(mirrors),
+                Err(err) if attempt < ATTEMPTS => {
+                    let delay = BACKOFF * 2u32.pow(attempt - 1);
+                    tracing::debug!(target: TARGET, "Mirrors list fetch attempt {attempt}/{ATTEMPTS} failed: {err}; retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
 
-        let mirrors: Vec<Mirror> = self
-            .client
-            .get(ZIG_COMMUNITY_MIRRORS)
-            .send()
-            .await
-            .map_err(NetErr::Reqwest)?
-            .text()
-            .await
-            .map_err(NetErr::Reqwest)?
+    async fn fetch_network_mirrors_once(&self) -> Result<Vec<Mirror>, NetErr> {
+        tracing::debug!(target: TARGET, "Fetching mirrors from {}", self.mirrors_url);
+
+        let body = async {
+            self.client
+                .get(&self.mirrors_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await
+        }
+        .instrument(tracing::info_span!(
+            "fetch_mirrors",
+            url = %self.mirrors_url
+        ))
+        .await
+        .map_err(NetErr::Reqwest)?;
+
+        let mirrors: Vec<Mirror> = body
             .lines()
             .filter(|line| !line.trim().is_empty()) // Skip empty lines
             .filter_map(|line| {
@@ -737,6 +941,20 @@ impl MirrorManager {
 
         Ok(())
     }
+    /// Pin the rank of every loaded mirror that has a configured override
+    fn apply_priority_overrides(&mut self) {
+        for mirror in self.mirrors.iter_mut() {
+            if let Some(&rank) = self
+                .priority_overrides
+                .iter()
+                .find(|(url, _)| same_mirror_url(url, &mirror.base_url))
+                .map(|(_, rank)| rank)
+            {
+                tracing::debug!(target: TARGET, "Pinning mirror {} to rank {}", mirror.base_url, rank);
+                mirror.rank = rank;
+            }
+        }
+    }
     /// Check if the cached mirrors have expired
     #[inline]
     fn is_cache_expired(&self) -> bool {
@@ -755,24 +973,30 @@ impl MirrorManager {
         }
         Ok(&mut self.mirrors)
     }
-    /// Get a random mirror for load balancing, preferring lower rank
-    pub async fn get_random_mirror(&mut self) -> Result<&mut Mirror, NetErr> {
+    /// Get a random mirror for load balancing, preferring lower rank. Mirrors in `tried` are
+    /// skipped while any other is left, so a retry moves on from a mirror that just failed.
+    pub async fn get_random_mirror(&mut self, tried: &[Url]) -> Result<&mut Mirror, NetErr> {
         use rand::Rng;
         let mirrors = self.all_mirrors_mut().await?;
         if mirrors.is_empty() {
             return Err(NetErr::EmptyMirrors);
         }
 
+        let all_tried = mirrors.iter().all(|m| tried.contains(&m.base_url));
+        let candidates: Vec<usize> = (0..mirrors.len())
+            .filter(|&i| all_tried || !tried.contains(&mirrors[i].base_url))
+            .collect();
+
         // If only one mirror, return it
-        if mirrors.len() == 1 {
-            return Ok(&mut mirrors[0]);
+        if candidates.len() == 1 {
+            return Ok(&mut mirrors[candidates[0]]);
         }
 
         // Calculate weights inversely proportional to rank
         // Lower rank = higher weight
-        let weights: Vec<f64> = mirrors
+        let weights: Vec<f64> = candidates
             .iter()
-            .map(|m| 1.0f64 / m.rank as f64) // Rank 1 = weight 1.0, rank 2 = 0.5, rank 5 = 0.2
+            .map(|&i| 1.0f64 / mirrors[i].rank as f64) // Rank 1 = weight 1.0, rank 2 = 0.5, rank 5 = 0.2
             .collect();
 
         // Simple weighted random selection
@@ -780,15 +1004,85 @@ impl MirrorManager {
         let total_weight: f64 = weights.iter().sum();
         let mut random_weight = rng.random::<f64>() * total_weight;
 
-        for (i, &weight) in weights.iter().enumerate() {
+        for (&i, &weight) in candidates.iter().zip(&weights) {
             random_weight -= weight;
             if random_weight <= 0.0 {
                 return Ok(&mut mirrors[i]);
             }
         }
 
-        // Fallback to first mirror (should not happen with correct weights)
-        Ok(&mut mirrors[0])
+        // Fallback to first candidate (should not happen with correct weights)
+        Ok(&mut mirrors[candidates[0]])
+    }
+    /// Manually override the rank of the mirror at `url`, taking precedence over
+    /// performance-based ranking. Returns `true` if a loaded mirror matched.
+    pub fn set_mirror_priority(&mut self, url: &str, rank: u8) -> bool {
+        let rank = rank.max(1);
+        self.priority_overrides.insert(url.to_string(), rank);
+        let mut matched = false;
+        for mirror in self
+            .mirrors
+            .iter_mut()
+            .filter(|m| same_mirror_url(url, &m.base_url))
+        {
+            mirror.rank = rank;
+            matched = true;
+        }
+        matched
+    }
+    /// Add the mirror at `url` at the default rank of 1 and persist it to the mirrors cache.
+    /// The mirror must answer a HEAD request first. Adding a mirror that is already known is
+    /// a no-op.
+    pub async fn add_mirror(&mut self, url: &str) -> Result<(), ZvError> {
+        let mut mirror = Mirror::try_from(url).map_err(|e| {
+            ZvError::NetworkError(NetErr::InvalidMirror(eyre!(
+                "{url} is not a valid URL: {e}"
+            )))
+        })?;
+        mirror.user_added = true;
+
+        self.ensure_mirrors_loaded()
+            .await
+            .map_err(ZvError::NetworkError)?;
+        if self
+            .mirrors
+            .iter()
+            .any(|m| same_mirror_url(mirror.base_url.as_str(), &m.base_url))
+        {
+            tracing::debug!(target: TARGET, "Mirror {} is already known", mirror.base_url);
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .head(mirror.base_url.clone())
+            .send()
+            .await
+            .map_err(|e| ZvError::NetworkError(NetErr::Reqwest(e)))?;
+        if response.status().is_server_error() {
+            return Err(ZvError::NetworkError(NetErr::HTTP(response.status())));
+        }
+
+        self.mirrors.push(mirror);
+        self.apply_priority_overrides();
+        self.save_index_to_disk()
+            .await
+            .map_err(ZvError::NetworkError)
+    }
+    /// Relative age of the loaded mirrors list, if one is loaded
+    pub fn last_synced_display(&self) -> Option<String> {
+        self.mirrors_index
+            .as_ref()
+            .map(MirrorsIndex::get_last_synced_display)
+    }
+    /// Clear the download history of every loaded mirror. Returns how many had any.
+    pub fn reset_stats(&mut self) -> usize {
+        let mut cleared = 0;
+        for mirror in self.mirrors.iter_mut().filter(|m| !m.stats.is_empty()) {
+            mirror.stats = MirrorStats::default();
+            cleared += 1;
+        }
+        cleared
     }
     /// Sort mirrors by rank and return mutable reference to the sorted mirror list
     pub async fn sort_by_rank(&mut self) -> Result<&mut Vec<Mirror>, NetErr> {
@@ -821,3 +1115,244 @@ impl MirrorManager {
         Ok(())
     }
 }
+
+/// Render an elapsed time in the largest whole unit, e.g. "3 days ago"
+pub(crate) fn relative_time(elapsed: chrono::Duration) -> String {
+    let (n, unit) = match elapsed.num_seconds() {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86400 => (s / 3600, "hour"),
+        s if s < 7 * 86400 => (s / 86400, "day"),
+        s if s < 30 * 86400 => (s / (7 * 86400), "week"),
+        s if s < 365 * 86400 => (s / (30 * 86400), "month"),
+        s => (s / (365 * 86400), "year"),
+    };
+    format!("{n} {unit}{} ago", if n == 1 { "" } else { "s" })
+}
+
+/// Compare a user-supplied mirror URL with a mirror's base URL, ignoring trailing slashes
+fn same_mirror_url(url: &str, base_url: &Url) -> bool {
+    url.trim_end_matches('/') == base_url.as_str().trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_overrides_pin_rank_regardless_of_saved_rank() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut manager =
+            MirrorManager::new(temp.path().join("mirrors.toml"), Client::new()).unwrap();
+        let mut slow = Mirror::try_from("https://example.com/zig").unwrap();
+        slow.rank = 9;
+        manager.mirrors_index = Some(MirrorsIndex::new(vec![
+            slow,
+            Mirror::try_from("https://other.example.org/zig").unwrap(),
+        ]));
+        manager.priority_overrides = HashMap::from([("https://example.com/zig/".to_string(), 1)]);
+
+        manager.apply_cached_mirrors_index();
+        assert_eq!(manager.mirrors[0].rank, 1);
+
+        assert!(manager.set_mirror_priority("https://other.example.org/zig", 5));
+        assert_eq!(manager.mirrors[1].rank, 5);
+        assert!(!manager.set_mirror_priority("https://unknown.example.net", 2));
+    }
+
+    #[tokio::test]
+    async fn random_mirror_skips_tried_mirrors_until_none_are_left() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut manager =
+            MirrorManager::new(temp.path().join("mirrors.toml"), Client::new()).unwrap();
+        let first = Mirror::try_from("https://example.com/zig").unwrap();
+        let second = Mirror::try_from("https://other.example.org/zig").unwrap();
+        let tried = [first.base_url.clone(), second.base_url.clone()];
+        manager.mirrors = vec![first, second];
+
+        for _ in 0..20 {
+            let picked = manager.get_random_mirror(&tried[..1]).await.unwrap();
+            assert_eq!(picked.base_url, tried[1]);
+        }
+        // With every mirror tried, any of them may be retried
+        assert!(manager.get_random_mirror(&tried).await.is_ok());
+    }
+
+    #[test]
+    fn http_fallback_only_downgrades_https_mirrors() {
+        let mirror = Mirror::try_from("https://example.com/zig").unwrap();
+        let http = mirror.http_fallback().unwrap();
+        assert_eq!(http.base_url.as_str(), "http://example.com/zig");
+        assert_eq!(http.layout, mirror.layout);
+
+        assert!(http.http_fallback().is_none());
+    }
+
+    /// Server whose mirrors list fails once with each of `failures`, then serves one mirror
+    /// (or keeps failing with 500 if `recovers` is false)
+    async fn mirrors_server(failures: &[u16], recovers: bool) -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let fallback = match recovers {
+            true => ResponseTemplate::new(200).set_body_string("https://example.com/zig\n"),
+            false => ResponseTemplate::new(500),
+        };
+        // Earlier mounts take precedence; each failure is served once
+        for &status in failures {
+            Mock::given(method("GET"))
+                .and(path("/mirrors"))
+                .respond_with(ResponseTemplate::new(status))
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/mirrors"))
+            .respond_with(fallback)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn load_mirrors(
+        cache_path: &Path,
+        server: &wiremock::MockServer,
+    ) -> Result<MirrorManager, NetErr> {
+        MirrorManager::init_and_load(
+            cache_path,
+            Client::new(),
+            CacheStrategy::AlwaysRefresh,
+            HashMap::new(),
+            &format!("{}/mirrors", server.uri()),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn mirror_list_fetch_retries_transient_failures() {
+        let server = mirrors_server(&[502, 503], true).await;
+        let temp = tempfile::tempdir().unwrap();
+
+        let manager = load_mirrors(&temp.path().join("mirrors.toml"), &server)
+            .await
+            .unwrap();
+        assert_eq!(manager.mirrors.len(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn mirror_list_falls_back_to_stale_cache() {
+        let server = mirrors_server(&[], false).await;
+        let temp = tempfile::tempdir().unwrap();
+        let cache = temp.path().join("mirrors.toml");
+
+        assert!(load_mirrors(&cache, &server).await.is_err());
+
+        let mut stale = MirrorsIndex::new(vec![
+            Mirror::try_from("https://cached.example.org/zig").unwrap(),
+        ]);
+        stale.last_synced -= chrono::Duration::days(90);
+        stale.save(&cache).await.unwrap();
+
+        let manager = load_mirrors(&cache, &server).await.unwrap();
+        assert_eq!(
+            manager.mirrors[0].base_url.as_str(),
+            "https://cached.example.org/zig"
+        );
+    }
+
+    #[tokio::test]
+    async fn added_mirror_is_persisted_and_survives_refresh() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = mirrors_server(&[], true).await;
+        Mock::given(method("HEAD"))
+            .and(path("/custom"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/broken"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let temp = tempfile::tempdir().unwrap();
+        let cache = temp.path().join("mirrors.toml");
+
+        let mut manager = load_mirrors(&cache, &server).await.unwrap();
+        let custom = format!("{}/custom", server.uri());
+        manager.add_mirror(&custom).await.unwrap();
+        manager.add_mirror(&custom).await.unwrap();
+        assert!(matches!(
+            manager
+                .add_mirror(&format!("{}/broken", server.uri()))
+                .await,
+            Err(ZvError::NetworkError(NetErr::HTTP(_)))
+        ));
+        assert_eq!(manager.mirrors.len(), 2);
+
+        let manager = load_mirrors(&cache, &server).await.unwrap();
+        let added: Vec<_> = manager.mirrors.iter().filter(|m| m.user_added).collect();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].base_url.as_str(), custom);
+        assert_eq!(added[0].rank, 1);
+    }
+
+    #[tokio::test]
+    async fn download_stats_survive_refresh_until_reset() {
+        let server = mirrors_server(&[], true).await;
+        let temp = tempfile::tempdir().unwrap();
+        let cache = temp.path().join("mirrors.toml");
+
+        let mut mirror = Mirror::try_from("https://example.com/zig").unwrap();
+        mirror.stats.record_success(1024);
+        mirror.stats.record_failure();
+        MirrorsIndex::new(vec![mirror]).save(&cache).await.unwrap();
+
+        let mut manager = load_mirrors(&cache, &server).await.unwrap();
+        let stats = &manager.mirrors[0].stats;
+        assert_eq!(
+            (stats.successes, stats.failures, stats.bytes_served),
+            (1, 1, 1024)
+        );
+        assert!(stats.last_success.is_some() && stats.last_failure.is_some());
+
+        assert_eq!(manager.reset_stats(), 1);
+        manager.save_index_to_disk().await.unwrap();
+        let saved = MirrorsIndex::load_from_disk(&cache).await.unwrap();
+        assert!(saved.mirrors[0].stats.is_empty());
+    }
+
+    #[test]
+    fn relative_time_uses_the_largest_whole_unit() {
+        let ago = |secs: i64| relative_time(chrono::Duration::seconds(secs));
+        assert_eq!(ago(-5), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(5 * 60), "5 minutes ago");
+        assert_eq!(ago(3600), "1 hour ago");
+        assert_eq!(ago(3 * 86400 + 7200), "3 days ago");
+        assert_eq!(ago(15 * 86400), "2 weeks ago");
+        assert_eq!(ago(90 * 86400), "3 months ago");
+        assert_eq!(ago(800 * 86400), "2 years ago");
+
+        let mut index = MirrorsIndex::new(vec![]);
+        index.last_synced -= chrono::Duration::minutes(5);
+        assert_eq!(index.get_last_synced_display(), "5 minutes ago");
+    }
+
+    #[test]
+    fn mirrors_synced_in_the_future_are_expired() {
+        let mut index = MirrorsIndex::new(vec![]);
+        assert!(!index.is_expired());
+        assert!(index.get_ttl_remaining().is_some());
+        index.last_synced += chrono::Duration::hours(3);
+        assert!(index.is_expired());
+        assert_eq!(index.get_ttl_remaining(), None);
+        index.last_synced -= chrono::Duration::days(*MIRRORS_TTL_DAYS + 1);
+        assert!(index.is_expired());
+        assert_eq!(index.get_ttl_remaining(), None);
+    }
+}
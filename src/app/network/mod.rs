@@ -1,9 +1,11 @@
-use crate::app::MASTER_CACHE_TTL_HOURS;
-use crate::app::constants::ZIG_DOWNLOAD_INDEX_JSON;
+use crate::app::constants::{ZIG_COMMUNITY_MIRRORS, ZIG_MINSIGN_PUBKEY};
+use crate::app::toolchain::{InstallOrigin, ZigInstall};
 use crate::app::utils::{ProgressHandle, remove_files, verify_checksum, zv_agent};
-use crate::{NetErr, ZvError};
+use crate::app::{FETCH_TIMEOUT_SECS, MASTER_CACHE_TTL_HOURS};
+use crate::{ArchiveExt, NetErr, ZvError, ZvResultExt};
 use color_eyre::eyre::{Result, WrapErr, eyre};
 use std::{path::PathBuf, time::Duration};
+use tracing::Instrument;
 
 use crate::types::{ResolvedZigVersion, TargetTriple};
 use std::collections::HashMap;
@@ -13,7 +15,7 @@ mod zig_index;
 pub mod zls;
 pub use zig_index::*;
 mod download;
-use download::{move_to_final_location, stream_download_file};
+use download::{is_interrupted, move_to_final_location, stream_download_file};
 pub use {ArtifactInfo, NetworkZigRelease, ZigRelease};
 /// Cache strategy for index loading
 #[derive(Debug, Clone, Copy)]
@@ -30,6 +32,46 @@ pub enum CacheStrategy {
 
 const TARGET: &str = "zv::network";
 
+/// Timeouts for network operations. `--timeout <secs>` sets all of them to the same value.
+///
+/// None of these bound a whole download: a tarball transfer may take as long as it needs so
+/// long as data keeps arriving within `download_stall`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetTimeouts {
+    /// Per-request timeout for fetching the download index (`ZV_FETCH_TIMEOUT_SECS`)
+    pub fetch: Duration,
+    /// Whole-request timeout for other metadata: the mirrors list and ZLS version lookups
+    pub metadata: Duration,
+    /// Time allowed to establish a connection, for every request
+    pub connect: Duration,
+    /// How long a download may go without receiving data before it fails
+    pub download_stall: Duration,
+}
+
+impl Default for NetTimeouts {
+    fn default() -> Self {
+        Self {
+            fetch: Duration::from_secs(*FETCH_TIMEOUT_SECS),
+            metadata: Duration::from_secs(15),
+            connect: Duration::from_secs(10),
+            download_stall: Duration::from_secs(30),
+        }
+    }
+}
+
+impl NetTimeouts {
+    /// Use `secs` for every timeout
+    pub fn uniform(secs: u64) -> Self {
+        let timeout = Duration::from_secs(secs);
+        Self {
+            fetch: timeout,
+            metadata: timeout,
+            connect: timeout,
+            download_stall: timeout,
+        }
+    }
+}
+
 /// Result of a successful download operation containing paths to verified files and mirror information
 #[derive(Debug, Clone)]
 pub struct ZigDownload {
@@ -39,6 +81,8 @@ pub struct ZigDownload {
     pub minisig_path: PathBuf,
     /// Mirror that was successfully used for the download
     pub mirror_used: String,
+    /// Archive format the server announced through `Content-Type`, if it named one
+    pub archive_ext: Option<ArchiveExt>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,10 +93,18 @@ pub struct ZvNetwork {
     pub index_manager: IndexManager,
     /// Path to mirrors.toml cache file
     mirrors_file: PathBuf,
+    /// Mirror rank overrides from zv.toml, applied when the mirror manager loads
+    mirror_priorities: HashMap<String, u8>,
+    /// Where the community mirrors list is fetched from
+    mirrors_url: String,
+    /// Key that signatures of mirror downloads must verify against
+    minisign_pubkey: String,
     /// Download cache path
     download_cache: PathBuf,
-    /// Network Client
-    client: reqwest::Client,
+    /// Client for small metadata fetches (index, partial master probes)
+    index_client: reqwest::Client,
+    /// Client for long-lived tarball and minisig downloads
+    download_client: reqwest::Client,
 }
 
 // === Initialize ZvNetwork ===
@@ -75,17 +127,38 @@ impl ZvNetwork {
         index_file: PathBuf,
         mirrors_file: PathBuf,
         downloads_dir: PathBuf,
+        mirror_priorities: HashMap<String, u8>,
+        timeouts: NetTimeouts,
     ) -> Result<Self, ZvError> {
-        let client = create_client()?;
+        let index_client = create_index_client(&timeouts)?;
 
         Ok(Self {
             download_cache: downloads_dir,
-            index_manager: IndexManager::new(index_file, client.clone()),
-            client,
+            index_manager: IndexManager::new(index_file, index_client.clone(), timeouts.fetch),
+            index_client,
+            download_client: create_download_client(&timeouts)?,
             mirrors_file,
+            mirror_priorities,
+            mirrors_url: ZIG_COMMUNITY_MIRRORS.to_string(),
+            minisign_pubkey: ZIG_MINSIGN_PUBKEY.to_string(),
             mirror_manager: None,
         })
     }
+
+    /// Fetch the index and mirrors list from other endpoints, e.g. a local mock server
+    #[cfg(test)]
+    pub fn with_endpoints(mut self, index_url: &str, mirrors_url: &str) -> Self {
+        self.index_manager.set_index_url(index_url);
+        self.mirrors_url = mirrors_url.to_string();
+        self
+    }
+
+    /// Verify mirror downloads against another key, e.g. one a local mock server signs with
+    #[cfg(test)]
+    pub fn with_minisign_pubkey(mut self, pubkey: &str) -> Self {
+        self.minisign_pubkey = pubkey.to_string();
+        self
+    }
     /// Load the mirror manager if not already done
     pub async fn ensure_mirror_manager(&mut self) -> Result<&mut MirrorManager, ZvError> {
         if !self.download_cache.is_dir() {
@@ -97,7 +170,10 @@ impl ZvNetwork {
         if self.mirror_manager.is_none() {
             let mirror_manager = MirrorManager::init_and_load(
                 self.mirrors_file.clone(),
+                self.index_client.clone(),
                 CacheStrategy::RespectTtl,
+                self.mirror_priorities.clone(),
+                &self.mirrors_url,
             )
             .await
             .map_err(|net_err| {
@@ -139,6 +215,11 @@ impl ZvNetwork {
 
         Ok(0)
     }
+
+    /// Add a community mirror by URL and persist it to the mirrors cache
+    pub async fn add_mirror(&mut self, url: &str) -> Result<(), ZvError> {
+        self.ensure_mirror_manager().await?.add_mirror(url).await
+    }
 }
 
 // === Usage ===
@@ -195,6 +276,7 @@ impl ZvNetwork {
         let progress_handle = ProgressHandle::spawn();
         let max_retries = *MAX_RETRIES;
         let mut last_error = None;
+        let mut failed_mirrors = Vec::new();
 
         // Clean up any existing temporary files from previous failed attempts
         remove_files(&[temp_tarball_path.as_path(), temp_minisig_path.as_path()]).await;
@@ -212,7 +294,7 @@ impl ZvNetwork {
             // Select mirror based on attempt number
             let selected_mirror = {
                 // For subsequent attempts, get ranked mirrors and select the best available
-                match mirror_manager.get_random_mirror().await {
+                match mirror_manager.get_random_mirror(&failed_mirrors).await {
                     Ok(ranked_mirror) => ranked_mirror,
                     Err(net_err) => {
                         tracing::error!(target: TARGET, "Failed to get ranked mirror for attempt {attempt}: {net_err}");
@@ -229,19 +311,22 @@ impl ZvNetwork {
             let original_layout = selected_mirror.layout;
             let download_result = selected_mirror
                 .download(
-                    &self.client,
+                    &self.download_client,
                     semver_version,
                     zig_tarball,
-                    &temp_tarball_path,
-                    &temp_minisig_path,
-                    shasum.map(|s| s.as_str()),
-                    size,
+                    DownloadRequest {
+                        tarball_path: &temp_tarball_path,
+                        minisig_path: &temp_minisig_path,
+                        minisign_pubkey: &self.minisign_pubkey,
+                        expected_shasum: shasum.map(|s| s.as_str()),
+                        expected_size: size,
+                    },
                     &progress_handle,
                 )
                 .await;
 
             match download_result {
-                Ok(used_layout) => {
+                Ok((used_layout, archive_ext)) => {
                     // If layout changed, update mirror permanently
                     if used_layout != original_layout {
                         selected_mirror.layout = used_layout;
@@ -278,9 +363,14 @@ impl ZvNetwork {
                         }
                     }
 
-                    // Promote the successful mirror and save rankings
+                    // Promote the successful mirror, count the download and save rankings
                     let old_rank = selected_mirror.rank;
                     selected_mirror.promote();
+                    let bytes_served = match tokio::fs::metadata(&final_tarball_path).await {
+                        Ok(metadata) => metadata.len(),
+                        Err(_) => size.unwrap_or(0),
+                    };
+                    selected_mirror.stats.record_success(bytes_served);
                     tracing::trace!(target: TARGET, "Promoting successful mirror {} from rank {} to {}",
                                  selected_mirror.base_url, old_rank, selected_mirror.rank);
 
@@ -308,6 +398,7 @@ impl ZvNetwork {
                         tarball_path: final_tarball_path,
                         minisig_path: final_minisig_path,
                         mirror_used: selected_mirror.base_url.to_string(),
+                        archive_ext,
                     };
 
                     // Update mirror ranking on disk (this will also persist layout changes)
@@ -320,12 +411,19 @@ impl ZvNetwork {
                     return Ok(download_result);
                 }
                 Err(err) => {
-                    tracing::warn!(target: TARGET, "Download attempt {}/{} failed with mirror {} (rank: {}): {}",
-                                 attempt, max_retries, selected_mirror.base_url, selected_mirror.rank, err);
+                    if let NetErr::RateLimited { retry_after } = &err {
+                        tracing::warn!(target: TARGET, "Mirror {} is rate limiting downloads (Retry-After: {:?}) - demoting and switching mirror",
+                                     selected_mirror.base_url, retry_after);
+                    } else {
+                        tracing::warn!(target: TARGET, "Download attempt {}/{} failed with mirror {} (rank: {}): {}",
+                                     attempt, max_retries, selected_mirror.base_url, selected_mirror.rank, err);
+                    }
 
-                    // Demote the failed mirror and save rankings
+                    // Demote the failed mirror, count the failure and save rankings
                     let old_rank = selected_mirror.rank;
                     selected_mirror.demote();
+                    selected_mirror.stats.record_failure();
+                    failed_mirrors.push(selected_mirror.base_url.clone());
                     tracing::debug!(target: TARGET, "Demoting failed mirror {} from rank {} to rank {}",
                                  selected_mirror.base_url, old_rank, selected_mirror.rank);
 
@@ -336,8 +434,15 @@ impl ZvNetwork {
                         tracing::debug!(target: TARGET, "Successfully updated mirror rankings after failure");
                     }
 
-                    // Clean up temporary files after download
-                    remove_files(&[temp_tarball_path.as_path(), temp_minisig_path.as_path()]).await;
+                    // Clean up temporary files after download, keeping a tarball cut short midway
+                    // for the next attempt to resume; the checksum and signature still cover it
+                    if is_interrupted(&err) {
+                        tracing::debug!(target: TARGET, "Keeping partial {} to resume on the next attempt", temp_tarball_path.display());
+                        remove_files(&[temp_minisig_path.as_path()]).await;
+                    } else {
+                        remove_files(&[temp_tarball_path.as_path(), temp_minisig_path.as_path()])
+                            .await;
+                    }
 
                     last_error = Some(ZvError::NetworkError(err));
 
@@ -452,6 +557,44 @@ impl ZvNetwork {
             }
         }
     }
+
+    /// Check that the archive `install` was downloaded from is still the one the index lists
+    /// for this host. Imports have nothing to compare against and pass. When the index can't
+    /// be consulted, or doesn't list the version for this host, the install is left unverified
+    /// and an error says why.
+    pub async fn validate_installed_checksum(
+        &mut self,
+        install: &ZigInstall,
+    ) -> Result<bool, ZvError> {
+        let metadata = install.metadata().ok_or_else(|| {
+            ZvError::General(eyre!(
+                "No checksums recorded for Zig {}; it was installed before zv recorded them",
+                install.version
+            ))
+        })?;
+        if metadata.origin != Some(InstallOrigin::Index) {
+            tracing::debug!(target: TARGET, version = %install.version, origin = ?metadata.origin, "Not installed from the index, nothing to compare");
+            return Ok(true);
+        }
+
+        let host = crate::app::utils::host_target()
+            .ok_or_else(|| eyre!("Could not determine the host target"))?;
+        let release = self.validate_semver(&install.version).await.map_err(|e| {
+            tracing::debug!(target: TARGET, version = %install.version, "Index not available for verification: {e}");
+            ZvError::General(eyre!(
+                "Unverified: Zig {} could not be looked up in the index ({e})",
+                install.version
+            ))
+        })?;
+        match release.shasum(&host) {
+            Some(expected) => Ok(expected.eq_ignore_ascii_case(&metadata.archive_shasum)),
+            None => Err(ZvError::General(eyre!(
+                "Unverified: the index lists no {host} archive for Zig {}",
+                install.version
+            ))),
+        }
+    }
+
     pub async fn fetch_master_version(&mut self) -> Result<ZigRelease, ZvError> {
         // First try cache, skipping all network probes when master is still within TTL.
         // Use PreferCache so master_last_fetched is the sole freshness gate — RespectTtl
@@ -482,14 +625,17 @@ impl ZvNetwork {
             .map(|r| r.resolved_version().clone());
 
         // Try enhanced partial fetch first
-        match try_partial_fetch_master(&self.client).await {
+        match try_partial_fetch_master(&self.index_client, self.index_manager.index_url())
+            .instrument(tracing::info_span!("fetch_master"))
+            .await
+        {
             Ok(PartialFetchResult::Complete(complete_release)) => {
                 tracing::debug!(
                     target: "zv::network::fetch_master_version",
                     "Got complete master ZigRelease from partial fetch"
                 );
-                let is_new = prev_master_version.as_ref()
-                    != Some(complete_release.resolved_version());
+                let is_new =
+                    prev_master_version.as_ref() != Some(complete_release.resolved_version());
                 if is_new {
                     self.persist_master_fetched_metadata(Some(complete_release.clone()))
                         .await;
@@ -509,13 +655,12 @@ impl ZvNetwork {
                     .index_manager
                     .ensure_loaded(CacheStrategy::OnlyCache)
                     .await
-                    && let Some(cached_master) =
-                        index.get_master_version().and_then(|cached_master| {
-                            semver::Version::parse(&cached_master.version_string())
-                                .ok()
-                                .filter(|cached_version| *cached_version == partial_master_version)
-                                .map(|_| cached_master.clone())
+                    && let Some(cached_master) = index
+                        .get_master_version()
+                        .filter(|cached_master| {
+                            *cached_master.resolved_version().version() == partial_master_version
                         })
+                        .cloned()
                 {
                     tracing::debug!(
                         target: "zv::network::fetch_master_version",
@@ -580,20 +725,25 @@ impl ZvNetwork {
         }
     }
 
-    /// Returns the latest stable version from the Zig download index with consistent error handling
+    /// Returns the latest stable version from the Zig download index with consistent error handling.
+    /// With `include_prerelease`, release candidates count as well.
     pub async fn fetch_latest_stable_version(
         &mut self,
         cache_strategy: CacheStrategy,
+        include_prerelease: bool,
     ) -> Result<ZigRelease, ZvError> {
         match cache_strategy {
             CacheStrategy::AlwaysRefresh | CacheStrategy::RespectTtl => {
                 // Try the requested strategy first, fallback to cache on network failure
                 match self.index_manager.ensure_loaded(cache_strategy).await {
-                    Ok(index) => index.get_latest_stable_release().cloned().ok_or_else(|| {
-                        ZvError::ZigVersionResolveError(eyre!(
-                            "No stable version found in Zig download index"
-                        ))
-                    }),
+                    Ok(index) => index
+                        .get_latest_stable_release(include_prerelease)
+                        .cloned()
+                        .ok_or_else(|| {
+                            ZvError::ZigVersionResolveError(eyre!(
+                                "No stable version found in Zig download index"
+                            ))
+                        }),
                     Err(network_err) => {
                         tracing::error!(
                             target: "zv::network::fetch_latest_stable_version",
@@ -606,13 +756,14 @@ impl ZvNetwork {
                             .ensure_loaded(CacheStrategy::OnlyCache)
                             .await
                         {
-                            Ok(index) => {
-                                index.get_latest_stable_release().cloned().ok_or_else(|| {
+                            Ok(index) => index
+                                .get_latest_stable_release(include_prerelease)
+                                .cloned()
+                                .ok_or_else(|| {
                                     ZvError::ZigVersionResolveError(eyre!(
                                         "No stable version found in cached index"
                                     ))
-                                })
-                            }
+                                }),
                             Err(cache_err) => {
                                 tracing::error!(
                                     target: "zv::network::fetch_latest_stable_version",
@@ -627,13 +778,55 @@ impl ZvNetwork {
             CacheStrategy::PreferCache | CacheStrategy::OnlyCache => {
                 let index = self.index_manager.ensure_loaded(cache_strategy).await?;
 
-                index.get_latest_stable_release().cloned().ok_or_else(|| {
-                    ZvError::ZigVersionResolveError(eyre!(
-                        "No stable version found in Zig download index"
-                    ))
-                })
+                index
+                    .get_latest_stable_release(include_prerelease)
+                    .cloned()
+                    .ok_or_else(|| {
+                        ZvError::ZigVersionResolveError(eyre!(
+                            "No stable version found in Zig download index"
+                        ))
+                    })
+            }
+        }
+    }
+
+    /// Newest stable patch release in `series`. The cached index is refreshed once when it
+    /// has no match, since a new series may have shipped since it was synced
+    pub async fn fetch_latest_in_series(
+        &mut self,
+        series: &crate::VersionSeries,
+    ) -> Result<ZigRelease, ZvError> {
+        let found = match self
+            .index_manager
+            .ensure_loaded(CacheStrategy::RespectTtl)
+            .await
+        {
+            Ok(index) => index.get_latest_stable_in(series).cloned(),
+            Err(network_err) => {
+                tracing::error!(
+                    target: "zv::network::fetch_latest_in_series",
+                    "Failed to load the Zig index: {network_err}. Falling back to cached index"
+                );
+                self.index_manager
+                    .ensure_loaded(CacheStrategy::OnlyCache)
+                    .await?
+                    .get_latest_stable_in(series)
+                    .cloned()
             }
+        };
+        if let Some(release) = found {
+            return Ok(release);
         }
+        self.index_manager
+            .ensure_loaded(CacheStrategy::AlwaysRefresh)
+            .await?
+            .get_latest_stable_in(series)
+            .cloned()
+            .ok_or_else(|| {
+                ZvError::ZigNotFound(eyre!(
+                    "No stable {series}.x release found in Zig download index"
+                ))
+            })
     }
 
     /// Direct download function for --force-ziglang mode
@@ -683,20 +876,24 @@ impl ZvNetwork {
             tracing::debug!(target: TARGET, "Failed to start progress reporting: {} - continuing without progress updates", e);
         }
 
-        stream_download_file(
-            &self.client,
+        let archive_ext = stream_download_file(
+            &self.download_client,
             tarball_url,
             &final_tarball_path,
             expected_size.unwrap_or(0),
             &progress_handle,
         )
+        .instrument(tracing::info_span!("transfer", url = %tarball_url))
         .await
         .map_err(ZvError::NetworkError)?;
 
         // Phase 2: Verify checksum (if available)
         if let Some(shasum) = expected_shasum {
             tracing::debug!(target: TARGET, "Verifying tarball checksum");
-            verify_checksum(&final_tarball_path, shasum).await?;
+            verify_checksum(&final_tarball_path, shasum, Some(&progress_handle))
+                .instrument(tracing::info_span!("checksum"))
+                .await
+                .zv_context(|| format!("verifying {}", final_tarball_path.display()))?;
         } else {
             tracing::debug!(target: TARGET, "Skipping checksum verification - no expected checksum provided");
         }
@@ -711,12 +908,13 @@ impl ZvNetwork {
         }
 
         stream_download_file(
-            &self.client,
+            &self.download_client,
             minisig_url,
             &final_minisig_path,
             0, // minisig files are small, size unknown
             &progress_handle,
         )
+        .instrument(tracing::info_span!("transfer", url = %minisig_url))
         .await
         .map_err(ZvError::NetworkError)?;
 
@@ -726,12 +924,14 @@ impl ZvNetwork {
             tracing::warn!(target: TARGET, "Failed to update progress for signature verification: {} - continuing", e);
         }
 
-        crate::app::minisign::verify_minisign_signature(
-            minisign_pubkey,
-            &zig_tarball,
-            &final_tarball_path,
-            &final_minisig_path,
-        )?;
+        tracing::info_span!("verify_signature").in_scope(|| {
+            crate::app::minisign::verify_minisign_signature(
+                minisign_pubkey,
+                &zig_tarball,
+                &final_tarball_path,
+                &final_minisig_path,
+            )
+        })?;
 
         // Finish progress reporting
         if let Err(e) = progress_handle
@@ -752,18 +952,35 @@ impl ZvNetwork {
             tarball_path: final_tarball_path,
             minisig_path: final_minisig_path,
             mirror_used: tarball_url.to_string(),
+            archive_ext,
         })
     }
 }
 
-pub(crate) fn create_client() -> Result<reqwest::Client> {
+fn client_builder(timeouts: &NetTimeouts) -> reqwest::ClientBuilder {
     reqwest::Client::builder()
         .user_agent(zv_agent())
         .pool_max_idle_per_host(0) // Don't keep idle connections
-        .connect_timeout(Duration::from_secs(10))
+        .connect_timeout(timeouts.connect)
+}
+
+/// Client for index, mirror list and other metadata requests, which should fail fast
+pub(crate) fn create_index_client(timeouts: &NetTimeouts) -> Result<reqwest::Client> {
+    client_builder(timeouts)
+        .timeout(timeouts.metadata)
         .build()
         .map_err(|e| ZvError::NetworkError(NetErr::Reqwest(e)))
-        .wrap_err("Failed to build HTTP client")
+        .wrap_err("Failed to build HTTP index client")
+}
+
+/// Client for tarball downloads. Transfers may legitimately take minutes, so there is no
+/// overall response timeout; instead a read that stalls for `download_stall` fails the download.
+pub(crate) fn create_download_client(timeouts: &NetTimeouts) -> Result<reqwest::Client> {
+    client_builder(timeouts)
+        .read_timeout(timeouts.download_stall)
+        .build()
+        .map_err(|e| ZvError::NetworkError(NetErr::Reqwest(e)))
+        .wrap_err("Failed to build HTTP download client")
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -786,51 +1003,82 @@ pub(crate) enum PartialFetchResult {
     VersionOnly(semver::Version),
 }
 
+/// Index prefixes requested in turn by [try_partial_fetch_master]. 8KB holds most master
+/// objects; 32KB catches one that spills just past it before resorting to the full index.
+const PARTIAL_FETCH_RANGES: [usize; 2] = [8 * 1024, 32 * 1024];
+
 pub(crate) async fn try_partial_fetch_master(
     client: &reqwest::Client,
+    index_url: &str,
 ) -> Result<PartialFetchResult, PartialFetchError> {
-    // (8KB) to increase chances of getting complete master object
-    let response = client
-        .get(ZIG_DOWNLOAD_INDEX_JSON)
-        .header("Range", "bytes=0-8191") // 8KB should be enough for most master objects
-        .timeout(Duration::from_secs(2))
-        .send()
-        .await
-        .map_err(|err| {
-            if err.is_timeout() {
-                PartialFetchError::Timeout(err)
-            } else {
-                PartialFetchError::Network(err)
+    let mut partial_text = String::new();
+    for range in PARTIAL_FETCH_RANGES {
+        partial_text = match fetch_index_prefix(client, index_url, range).await {
+            Ok(text) => text,
+            // The smaller prefix already read may still name the master version
+            Err(err) if parse_master_version_fast(&partial_text).is_ok() => {
+                tracing::debug!(
+                    target: "zv::network::partial_fetch",
+                    "{range} byte partial fetch failed, falling back to the earlier prefix: {err}"
+                );
+                break;
             }
-        })?;
-
-    if response.status() == 206 {
-        let partial_text = response.text().await.map_err(PartialFetchError::Network)?;
+            Err(err) => return Err(err),
+        };
 
         // First try to extract complete master ZigRelease
         match try_extract_complete_master(&partial_text) {
             Ok(complete_release) => {
                 tracing::debug!(
                     target: "zv::network::partial_fetch",
-                    "Successfully extracted complete master ZigRelease from partial fetch"
+                    "Successfully extracted complete master ZigRelease from {range} byte partial fetch"
                 );
                 return Ok(PartialFetchResult::Complete(complete_release));
             }
             Err(e) => {
                 tracing::debug!(
                     target: "zv::network::partial_fetch",
-                    "Could not extract complete master object: {e}, falling back to version-only parsing"
+                    "Could not extract complete master object from {range} byte partial fetch: {e}"
                 );
             }
         }
 
-        // Fallback to version-only extraction
-        let version_str =
-            parse_master_version_fast(&partial_text).map_err(PartialFetchError::Parse)?;
-        let version =
-            semver::Version::parse(&version_str).map_err(|e| PartialFetchError::Parse(e.into()))?;
+        // A short body is the whole index; a larger range won't return anything more
+        if partial_text.len() < range {
+            break;
+        }
+    }
+
+    // Fallback to version-only extraction
+    let version_str = parse_master_version_fast(&partial_text).map_err(PartialFetchError::Parse)?;
+    let version =
+        semver::Version::parse(&version_str).map_err(|e| PartialFetchError::Parse(e.into()))?;
 
-        Ok(PartialFetchResult::VersionOnly(version))
+    Ok(PartialFetchResult::VersionOnly(version))
+}
+
+/// Request the first `range` bytes of the index, expecting a 206 Partial Content response
+async fn fetch_index_prefix(
+    client: &reqwest::Client,
+    index_url: &str,
+    range: usize,
+) -> Result<String, PartialFetchError> {
+    let response = client
+        .get(index_url)
+        .header("Range", format!("bytes=0-{}", range - 1))
+        .timeout(Duration::from_secs(2))
+        .send()
+        .await
+        .map_err(|err| {
+            if err.is_timeout() {
+                PartialFetchError::Timeout(err)
+            } else {
+                PartialFetchError::Network(err)
+            }
+        })?;
+
+    if response.status() == 206 {
+        response.text().await.map_err(PartialFetchError::Network)
     } else {
         Err(PartialFetchError::Not206(response.status()))
     }
@@ -940,7 +1188,9 @@ fn try_extract_complete_master(json_text: &str) -> Result<ZigRelease> {
         }
     }
 
-    let master_release = ZigRelease::new(resolved_version, network_release.date, runtime_artifacts);
+    let master_release = ZigRelease::new(resolved_version, network_release.date, runtime_artifacts)
+        .with_src(network_release.src.map(ArtifactInfo::from))
+        .with_notes(network_release.notes);
 
     Ok(master_release)
 }
@@ -973,3 +1223,80 @@ pub(crate) fn parse_master_version_fast(json_text: &str) -> Result<String> {
 
     Err(eyre!("Could not extract master version from partial JSON"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Serve `body` as 206 slices for every range in [PARTIAL_FETCH_RANGES]
+    async fn serve_ranges(body: &str) -> MockServer {
+        let server = MockServer::start().await;
+        for range in PARTIAL_FETCH_RANGES {
+            Mock::given(method("GET"))
+                .and(header("Range", format!("bytes=0-{}", range - 1)))
+                .respond_with(
+                    ResponseTemplate::new(206).set_body_string(&body[..range.min(body.len())]),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn partial_fetch_widens_range_when_master_spills_past_8kb() {
+        // Written out by hand: upstream lists master first, which `json!` would not preserve
+        let index = format!(
+            r#"{{"master": {{"version": "0.16.0-dev.1+abc", "date": "2025-10-01", "notes": "{}",
+                "x86_64-linux": {{"tarball": "https://ziglang.org/builds/zig-x86_64-linux-0.16.0-dev.1+abc.tar.xz",
+                "shasum": "{}", "size": "1"}}}},
+              "0.15.1": {{"date": "2025-08-19", "notes": "{}"}}}}"#,
+            "x".repeat(10 * 1024),
+            "0".repeat(64),
+            "x".repeat(32 * 1024),
+        );
+        let server = serve_ranges(&index).await;
+
+        let result = try_partial_fetch_master(&reqwest::Client::new(), &server.uri())
+            .await
+            .unwrap();
+        let PartialFetchResult::Complete(release) = result else {
+            panic!("master should be extracted from the 32KB prefix");
+        };
+        assert_eq!(
+            release.resolved_version().version().to_string(),
+            "0.16.0-dev.1+abc"
+        );
+        assert_eq!(release.version_string(), "master");
+    }
+
+    #[tokio::test]
+    async fn partial_fetch_falls_back_to_the_8kb_version_when_the_wider_range_fails() {
+        let index = format!(
+            r#"{{"master": {{"version": "0.16.0-dev.1+abc", "date": "2025-10-01", "notes": "{}"}}}}"#,
+            "x".repeat(40 * 1024),
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("Range", "bytes=0-8191"))
+            .respond_with(ResponseTemplate::new(206).set_body_string(&index[..8 * 1024]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("Range", "bytes=0-32767"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let result = try_partial_fetch_master(&reqwest::Client::new(), &server.uri())
+            .await
+            .unwrap();
+        let PartialFetchResult::VersionOnly(version) = result else {
+            panic!("only the version fits in the 8KB prefix");
+        };
+        assert_eq!(version.to_string(), "0.16.0-dev.1+abc");
+    }
+}
@@ -4,26 +4,19 @@ use super::models::{ArtifactInfo, CacheZigIndex, NetworkZigIndex, ZigIndex, ZigR
 use crate::{
     CfgErr, NetErr, ZvError,
     app::{
-        FETCH_TIMEOUT_SECS,
-        constants::ZIG_DOWNLOAD_INDEX_JSON,
+        INDEX_TTL_DAYS,
+        constants::{ZIG_DOWNLOAD_INDEX_JSON, ZV_RAW_INDEX_FILE},
         network::{CacheStrategy, TARGET},
     },
-    types::ResolvedZigVersion,
 };
 use reqwest::Client;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
+use tracing::Instrument;
 
 // Backward compatibility wrapper for ZigRelease
 impl ZigRelease {
-    /// Get version as string
-    pub fn version_string(&self) -> String {
-        // Extract the actual semver version from ResolvedZigVersion
-        match self.resolved_version() {
-            ResolvedZigVersion::Semver(v) => v.to_string(),
-            ResolvedZigVersion::Master(v) => v.to_string(),
-        }
-    }
-
     /// Fast target-support check (backward compatibility)
     pub fn has_target(&self, triple: &str) -> bool {
         use crate::types::TargetTriple;
@@ -34,14 +27,33 @@ impl ZigRelease {
         }
     }
 
-    /// Borrow the artifact for a target
+    /// Borrow the artifact for a target key in "arch-os" format.
+    /// Returns `None` for malformed keys and targets without an artifact.
     pub fn target_artifact(&self, triple: &str) -> Option<&ArtifactInfo> {
         use crate::types::TargetTriple;
-        if let Some(target_triple) = TargetTriple::from_key(triple) {
-            self.artifacts().get(&target_triple)
-        } else {
-            None
-        }
+        TargetTriple::from_key(triple)
+            .and_then(|target_triple| self.artifacts().get(&target_triple))
+    }
+
+    /// Index key this release uses for `host`. Releases up to 0.14.0 spell some architectures
+    /// differently (32-bit ARM was `armv7a`), so that spelling is tried as well.
+    pub fn resolve_target_key(&self, host: &str) -> Option<String> {
+        std::iter::once(host.to_string())
+            .chain(crate::app::utils::legacy_target_key(host))
+            .find(|key| self.has_target(key))
+    }
+
+    /// Borrow the artifact for `host`, see [`ZigRelease::resolve_target_key`]
+    pub fn host_artifact(&self, host: &str) -> Option<&ArtifactInfo> {
+        self.resolve_target_key(host)
+            .and_then(|key| self.target_artifact(&key))
+    }
+
+    /// Sorted target keys this release has artifacts for
+    pub fn get_available_targets(&self) -> Vec<String> {
+        let mut targets: Vec<String> = self.targets().collect();
+        targets.sort();
+        targets
     }
 
     /// ziglang tarball URL for a target (backward compatibility)
@@ -70,7 +82,11 @@ impl ZigRelease {
 /// In memory index manager for zig download index
 pub struct IndexManager {
     client: Client,
+    /// Timeout for each full index request
+    fetch_timeout: Duration,
     index_path: PathBuf,
+    /// Where the JSON index is fetched from (`ZIG_DOWNLOAD_INDEX_JSON` outside of tests)
+    index_url: String,
     index: Option<ZigIndex>,
 }
 
@@ -81,14 +97,28 @@ impl IndexManager {
     ///
     /// * `index_path` - The file path where the index will be cached on disk.
     /// * `client` - A reqwest client for making network requests.
-    pub fn new(index_path: PathBuf, client: Client) -> Self {
+    /// * `fetch_timeout` - Timeout for each full index request.
+    pub fn new(index_path: PathBuf, client: Client, fetch_timeout: Duration) -> Self {
         Self {
             index_path,
             index: None,
             client,
+            fetch_timeout,
+            index_url: ZIG_DOWNLOAD_INDEX_JSON.to_string(),
         }
     }
 
+    /// URL the JSON index is fetched from
+    pub fn index_url(&self) -> &str {
+        &self.index_url
+    }
+
+    /// Point the manager at a different index, e.g. a local mock server
+    #[cfg(test)]
+    pub fn set_index_url(&mut self, url: impl Into<String>) {
+        self.index_url = url.into();
+    }
+
     /// Ensures the index is loaded based on the provided cache strategy.
     ///
     /// This method handles loading the index from disk or fetching it from the network
@@ -107,19 +137,14 @@ impl IndexManager {
     ) -> Result<&ZigIndex, ZvError> {
         match cache_strategy {
             CacheStrategy::AlwaysRefresh => {
-                // Always fetch fresh data from network; For timeout we prefer the env var FETCH_TIMEOUT_SECS
+                // Always fetch fresh data from network, bounded by `fetch_timeout`
                 tracing::debug!(target: TARGET, "Refreshing index - fetching from network");
                 self.refresh_from_network().await?;
             }
             CacheStrategy::PreferCache => {
                 // Use cached data if available, only fetch if no cache exists
-                if self.index_path.is_file() {
-                    let data =
-                        tokio::fs::read_to_string(&self.index_path)
-                            .await
-                            .map_err(|io_err| {
-                                ZvError::ZvConfigError(CfgErr::NotFound(io_err.into()))
-                            })?;
+                if self.has_cache() {
+                    let data = self.read_cache().await?;
 
                     let cache_index: CacheZigIndex = toml::from_str(&data)
                         .map_err(|e| ZvError::ZvConfigError(CfgErr::ParseFail(e.into())))?;
@@ -134,13 +159,8 @@ impl IndexManager {
             }
             CacheStrategy::RespectTtl => {
                 // Respect TTL - use cache if not expired, otherwise refresh
-                if self.index_path.is_file() {
-                    let data =
-                        tokio::fs::read_to_string(&self.index_path)
-                            .await
-                            .map_err(|io_err| {
-                                ZvError::ZvConfigError(CfgErr::NotFound(io_err.into()))
-                            })?;
+                if self.has_cache() {
+                    let data = self.read_cache().await?;
 
                     let cache_index = toml::from_str::<CacheZigIndex>(&data).map_err(|e| {
                         tracing::error!(target: TARGET, "Parse error on cached zig index: {e}");
@@ -171,13 +191,8 @@ impl IndexManager {
             }
             CacheStrategy::OnlyCache => {
                 // Use cached data if available, returns error if no cache exists
-                if self.index_path.is_file() {
-                    let data =
-                        tokio::fs::read_to_string(&self.index_path)
-                            .await
-                            .map_err(|io_err| {
-                                ZvError::ZvConfigError(CfgErr::NotFound(io_err.into()))
-                            })?;
+                if self.has_cache() {
+                    let data = self.read_cache().await?;
 
                     let cache_index: CacheZigIndex = toml::from_str(&data)
                         .map_err(|e| ZvError::ZvConfigError(CfgErr::ParseFail(e.into())))?;
@@ -187,9 +202,10 @@ impl IndexManager {
                     tracing::debug!(target: TARGET, "Using cached index");
                 } else {
                     tracing::debug!(target: TARGET, "No cache found - OnlyCache strategy... returning");
-                    return Err(ZvError::CacheNotFound(
-                        self.index_path.to_string_lossy().to_string(),
-                    ));
+                    return Err(ZvError::CacheNotFound {
+                        path: self.index_path.to_string_lossy().to_string(),
+                        context: None,
+                    });
                 }
             }
         }
@@ -200,7 +216,7 @@ impl IndexManager {
             .expect("Index should be loaded after ensure_loaded"))
     }
 
-    /// Saves the current in-memory index to disk as a TOML file.
+    /// Saves the current in-memory index to disk as a gzip-compressed TOML file.
     ///
     /// If no index is loaded, this method does nothing.
     ///
@@ -213,11 +229,117 @@ impl IndexManager {
             let cache_index = CacheZigIndex::from(runtime_index);
             let toml_str =
                 toml::to_string_pretty(&cache_index).map_err(|e| CfgErr::ParseFail(e.into()))?;
-            tokio::fs::write(&self.index_path, toml_str)
+            self.write_compressed(&toml_str).await?;
+        }
+        Ok(())
+    }
+
+    /// Plain `index.toml` written by older zv versions, next to the compressed cache
+    fn legacy_index_path(&self) -> PathBuf {
+        self.index_path.with_extension("")
+    }
+
+    /// The upstream index.json as last fetched, gzip-compressed next to the TOML cache
+    pub fn raw_index_path(&self) -> PathBuf {
+        self.index_path.with_file_name(ZV_RAW_INDEX_FILE)
+    }
+
+    /// The upstream index.json as last fetched, with every field the TOML cache drops (docs
+    /// URLs, source tarballs, bootstrap artifacts). `None` if no index has been fetched since
+    /// zv started keeping it.
+    pub async fn read_raw_index(&self) -> Result<Option<String>, ZvError> {
+        use std::io::Read;
+
+        let compressed = match tokio::fs::read(self.raw_index_path()).await {
+            Ok(compressed) => compressed,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ZvError::Io(e)),
+        };
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut json)
+            .map_err(|e| ZvError::ZvConfigError(CfgErr::ParseFail(e.into())))?;
+        Ok(Some(json))
+    }
+
+    /// [`Self::read_raw_index`] parsed, for reading fields [`ZigRelease`] does not model
+    #[cfg(test)]
+    pub async fn raw_index(&self) -> Result<Option<serde_json::Value>, ZvError> {
+        self.read_raw_index()
+            .await?
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| ZvError::ZvConfigError(CfgErr::ParseFail(e.into())))
+    }
+
+    /// Keep `json` gzip-compressed at [`Self::raw_index_path`]
+    async fn write_raw_index(&self, json: &str) -> Result<(), CfgErr> {
+        use std::io::Write;
+
+        let raw_path = self.raw_index_path();
+        let write_fail = |io_err: std::io::Error| {
+            CfgErr::WriteFail(io_err.into(), raw_path.to_string_lossy().to_string())
+        };
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).map_err(write_fail)?;
+        let compressed = encoder.finish().map_err(write_fail)?;
+        tokio::fs::write(&raw_path, compressed)
+            .await
+            .map_err(write_fail)
+    }
+
+    fn has_cache(&self) -> bool {
+        self.index_path.is_file() || self.legacy_index_path().is_file()
+    }
+
+    /// Read the cached index as TOML, decompressing it. A plain `index.toml` left by an older
+    /// zv is read instead when there is no compressed cache, and migrated to one.
+    async fn read_cache(&self) -> Result<String, ZvError> {
+        use std::io::Read;
+
+        let not_found =
+            |io_err: std::io::Error| ZvError::ZvConfigError(CfgErr::NotFound(io_err.into()));
+        if !self.index_path.is_file() {
+            let legacy_path = self.legacy_index_path();
+            let data = tokio::fs::read_to_string(&legacy_path)
                 .await
-                .map_err(|io_err| {
-                    CfgErr::WriteFail(io_err.into(), self.index_path.to_string_lossy().to_string())
-                })?;
+                .map_err(not_found)?;
+            match self.write_compressed(&data).await {
+                Ok(()) => {
+                    tracing::debug!(target: TARGET, "Migrated {} to {}", legacy_path.display(), self.index_path.display());
+                }
+                Err(e) => tracing::warn!(target: TARGET, "Failed to compress cached index: {e}"),
+            }
+            return Ok(data);
+        }
+
+        let compressed = tokio::fs::read(&self.index_path).await.map_err(not_found)?;
+        let mut data = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut data)
+            .map_err(|e| ZvError::ZvConfigError(CfgErr::ParseFail(e.into())))?;
+        Ok(data)
+    }
+
+    /// Write `toml_str` gzip-compressed to the index path, replacing any plain `index.toml`
+    async fn write_compressed(&self, toml_str: &str) -> Result<(), CfgErr> {
+        use std::io::Write;
+
+        let write_fail = |io_err: std::io::Error| {
+            CfgErr::WriteFail(io_err.into(), self.index_path.to_string_lossy().to_string())
+        };
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(toml_str.as_bytes()).map_err(write_fail)?;
+        let compressed = encoder.finish().map_err(write_fail)?;
+        tokio::fs::write(&self.index_path, compressed)
+            .await
+            .map_err(write_fail)?;
+
+        let legacy_path = self.legacy_index_path();
+        if legacy_path.is_file()
+            && let Err(e) = tokio::fs::remove_file(&legacy_path).await
+        {
+            tracing::debug!(target: TARGET, "Failed to remove {}: {e}", legacy_path.display());
         }
         Ok(())
     }
@@ -228,24 +350,51 @@ impl IndexManager {
         self.index.as_ref()
     }
 
+    /// Time left until the loaded index expires and is refreshed from the network, going by
+    /// its `last_synced` stamp. `None` if no index is loaded or it has already expired, which
+    /// includes a stamp in the future from a skewed clock.
+    pub fn get_ttl_remaining(&self) -> Option<Duration> {
+        let last_synced = self.index.as_ref()?.last_synced()?;
+        if last_synced > chrono::Utc::now() {
+            return None;
+        }
+        let expires_at = last_synced + chrono::Duration::days(*INDEX_TTL_DAYS);
+        (expires_at - chrono::Utc::now()).to_std().ok()
+    }
+
     /// Mark master as freshly fetched from network and persist cache metadata.
     pub async fn stamp_master_fetched(
         &mut self,
         master_release: Option<ZigRelease>,
     ) -> Result<(), CfgErr> {
+        if self.index.is_none() {
+            return Ok(());
+        }
+        if let Some(release) = master_release {
+            let releases = BTreeMap::from([(release.resolved_version().clone(), release)]);
+            self.merge_update(ZigIndex::with_releases(releases, None, None));
+        }
         if let Some(index) = self.index.as_mut() {
-            if let Some(release) = master_release {
-                index.upsert_master_release(release);
-            }
             index.mark_master_fetched_now();
-            self.save_to_disk().await?;
         }
-        Ok(())
+        self.save_to_disk().await
+    }
+
+    /// Fold `update` into the loaded index with [`CacheZigIndex::merge`], so releases it lacks
+    /// stay cached. Without a loaded index, `update` becomes the index.
+    fn merge_update(&mut self, update: ZigIndex) {
+        self.index = Some(match self.index.take() {
+            Some(current) => CacheZigIndex::from(&current)
+                .merge(&CacheZigIndex::from(&update))
+                .into(),
+            None => update,
+        });
     }
 
     /// Fetches the latest index from the network, updates the internal state, and attempts to save it to disk.
     ///
-    /// The index is fetched from `ZIG_DOWNLOAD_INDEX_JSON`, parsed as JSON, and the `last_synced` timestamp is updated.
+    /// The index is fetched from `index_url` (`ZIG_DOWNLOAD_INDEX_JSON`), parsed as JSON, and the `last_synced` timestamp is updated.
+    /// The response is merged into the loaded index, so releases it leaves out stay cached.
     /// If saving to disk fails, it is logged as a warning but does not fail the operation.
     ///
     /// # Returns
@@ -261,23 +410,25 @@ impl IndexManager {
             .map(|r| r.resolved_version().clone());
         let prev_master_stamp = self.index.as_ref().and_then(|i| i.master_last_fetched());
 
-        let response = self
-            .client
-            .get(ZIG_DOWNLOAD_INDEX_JSON)
-            .timeout(std::time::Duration::from_secs(*FETCH_TIMEOUT_SECS))
-            .send()
-            .await
-            .map_err(NetErr::Reqwest)
-            .map_err(ZvError::NetworkError)?;
-        if !response.status().is_success() {
-            return Err(ZvError::NetworkError(NetErr::HTTP(response.status())));
+        let text = async {
+            let response = self
+                .client
+                .get(&self.index_url)
+                .timeout(self.fetch_timeout)
+                .send()
+                .await
+                .map_err(NetErr::Reqwest)?;
+            if !response.status().is_success() {
+                return Err(NetErr::HTTP(response.status()));
+            }
+            response.text().await.map_err(NetErr::Reqwest)
         }
-
-        let text = response
-            .text()
-            .await
-            .map_err(NetErr::Reqwest)
-            .map_err(ZvError::NetworkError)?;
+        .instrument(tracing::info_span!(
+            "fetch_index",
+            url = %self.index_url
+        ))
+        .await
+        .map_err(ZvError::NetworkError)?;
 
         // Deserialize as NetworkZigIndex and convert to ZigIndex
         let network_index = serde_json::from_str::<NetworkZigIndex>(&text)
@@ -298,11 +449,218 @@ impl IndexManager {
             runtime_index.set_master_last_fetched(prev_master_stamp);
         }
 
-        self.index = Some(runtime_index);
+        self.merge_update(runtime_index);
         let _ = self.save_to_disk().await.map_err(|e| {
             // Non-fatal error, log and continue
             tracing::warn!(target: TARGET, "Failed to save refreshed index to disk: {}", e);
         });
+        if let Err(e) = self.write_raw_index(&text).await {
+            tracing::warn!(target: TARGET, "Failed to save raw index to disk: {}", e);
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ResolvedZigVersion, TargetTriple};
+    use std::collections::HashMap;
+
+    fn release_with(artifacts: HashMap<TargetTriple, ArtifactInfo>) -> ZigRelease {
+        ZigRelease::new(
+            ResolvedZigVersion::Semver(semver::Version::new(0, 15, 1)),
+            "2025-08-19".to_string(),
+            artifacts,
+        )
+    }
+
+    #[test]
+    fn target_artifact_finds_known_target() {
+        let release = release_with(HashMap::from([(
+            TargetTriple::new("x86_64".to_string(), "linux".to_string()),
+            ArtifactInfo {
+                ziglang_org_tarball:
+                    "https://ziglang.org/download/0.15.1/zig-x86_64-linux-0.15.1.tar.xz".to_string(),
+                shasum: "abc123".to_string(),
+                size: 42,
+            },
+        )]));
+
+        let artifact = release.target_artifact("x86_64-linux").unwrap();
+        assert_eq!(artifact.shasum, "abc123");
+        assert_eq!(release.size("x86_64-linux"), Some(42));
+    }
+
+    #[test]
+    fn target_artifact_rejects_unknown_or_malformed_target() {
+        let release = release_with(HashMap::from([(
+            TargetTriple::new("x86_64".to_string(), "linux".to_string()),
+            ArtifactInfo {
+                ziglang_org_tarball: String::new(),
+                shasum: String::new(),
+                size: 0,
+            },
+        )]));
+
+        assert!(release.target_artifact("aarch64-macos").is_none());
+        assert!(release.target_artifact("x86_64").is_none());
+        assert!(release.target_artifact("").is_none());
+    }
+
+    #[test]
+    fn target_artifact_on_empty_artifacts() {
+        let release = release_with(HashMap::new());
+        assert!(release.target_artifact("x86_64-linux").is_none());
+        assert!(!release.has_target("x86_64-linux"));
+    }
+
+    #[test]
+    fn host_artifact_falls_back_to_legacy_arch_names() {
+        let artifact = |shasum: &str| ArtifactInfo {
+            ziglang_org_tarball: String::new(),
+            shasum: shasum.to_string(),
+            size: 0,
+        };
+        let release = release_with(HashMap::from([
+            (
+                TargetTriple::from_key("armv7a-linux").unwrap(),
+                artifact("arm"),
+            ),
+            (
+                TargetTriple::from_key("x86_64-linux").unwrap(),
+                artifact("x86_64"),
+            ),
+            (
+                TargetTriple::from_key("aarch64-windows").unwrap(),
+                artifact("arm64-windows"),
+            ),
+        ]));
+
+        assert_eq!(
+            release.resolve_target_key("arm-linux").as_deref(),
+            Some("armv7a-linux")
+        );
+        assert_eq!(release.host_artifact("arm-linux").unwrap().shasum, "arm");
+        assert_eq!(
+            release.host_artifact("aarch64-windows").unwrap().shasum,
+            "arm64-windows"
+        );
+        assert!(release.host_artifact("aarch64-macos").is_none());
+        assert_eq!(
+            release.get_available_targets(),
+            ["aarch64-windows", "armv7a-linux", "x86_64-linux"]
+        );
+    }
+
+    #[tokio::test]
+    async fn plain_index_cache_is_migrated_to_gzip() {
+        let temp = tempfile::tempdir().unwrap();
+        let index_path = temp.path().join("index.toml.gz");
+        let legacy_path = temp.path().join("index.toml");
+        let release = release_with(HashMap::new());
+        let index = ZigIndex::with_releases(
+            [(release.resolved_version().clone(), release)].into(),
+            Some(chrono::Utc::now()),
+            None,
+        );
+        tokio::fs::write(
+            &legacy_path,
+            toml::to_string_pretty(&CacheZigIndex::from(&index)).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let mut manager = IndexManager::new(index_path.clone(), Client::new(), Duration::ZERO);
+        let loaded = manager
+            .ensure_loaded(CacheStrategy::OnlyCache)
+            .await
+            .unwrap();
+        assert_eq!(loaded.releases().len(), 1);
+        assert!(!legacy_path.exists());
+        let compressed = std::fs::read(&index_path).unwrap();
+        assert_eq!(compressed[..2], [0x1f, 0x8b]);
+
+        let mut manager = IndexManager::new(index_path, Client::new(), Duration::ZERO);
+        let loaded = manager
+            .ensure_loaded(CacheStrategy::OnlyCache)
+            .await
+            .unwrap();
+        assert_eq!(loaded.releases().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn master_probe_is_merged_into_the_cached_index() {
+        let temp = tempfile::tempdir().unwrap();
+        let master = |version: &str| {
+            ZigRelease::new(
+                ResolvedZigVersion::Master(semver::Version::parse(version).unwrap()),
+                "2025-08-20".to_string(),
+                HashMap::new(),
+            )
+        };
+        let stable = release_with(HashMap::new());
+        let old_master = master("0.16.0-dev.1+abc");
+        let mut manager = IndexManager::new(
+            temp.path().join("index.toml.gz"),
+            Client::new(),
+            Duration::ZERO,
+        );
+        manager.index = Some(ZigIndex::with_releases(
+            [
+                (stable.resolved_version().clone(), stable),
+                (old_master.resolved_version().clone(), old_master),
+            ]
+            .into(),
+            Some(chrono::Utc::now()),
+            None,
+        ));
+
+        manager
+            .stamp_master_fetched(Some(master("0.16.0-dev.9+def")))
+            .await
+            .unwrap();
+        let index = manager.loaded_index().unwrap();
+        let versions: Vec<_> = index.releases().keys().map(|v| v.to_string()).collect();
+        assert_eq!(versions, ["0.15.1", "master <0.16.0-dev.9+def>"]);
+        assert!(index.master_last_fetched().is_some());
+        assert!(temp.path().join("index.toml.gz").is_file());
+    }
+
+    #[test]
+    fn ttl_remaining_counts_down_from_last_synced() {
+        let mut manager = IndexManager::new(PathBuf::new(), Client::new(), Duration::ZERO);
+        assert_eq!(manager.get_ttl_remaining(), None);
+
+        let ttl = chrono::Duration::days(*INDEX_TTL_DAYS);
+        let synced_at = |age: chrono::Duration| {
+            Some(ZigIndex::with_releases(
+                Default::default(),
+                Some(chrono::Utc::now() - age),
+                None,
+            ))
+        };
+        manager.index = synced_at(chrono::Duration::hours(1));
+        let remaining = manager.get_ttl_remaining().unwrap();
+        assert!(remaining <= (ttl - chrono::Duration::hours(1)).to_std().unwrap());
+        assert!(remaining > (ttl - chrono::Duration::hours(2)).to_std().unwrap());
+
+        manager.index = synced_at(ttl + chrono::Duration::hours(1));
+        assert_eq!(manager.get_ttl_remaining(), None);
+    }
+
+    #[test]
+    fn index_synced_in_the_future_is_expired() {
+        let mut manager = IndexManager::new(PathBuf::new(), Client::new(), Duration::ZERO);
+        let synced_at = |offset: chrono::Duration| {
+            ZigIndex::with_releases(Default::default(), Some(chrono::Utc::now() + offset), None)
+        };
+
+        manager.index = Some(synced_at(chrono::Duration::days(2)));
+        assert!(manager.index.as_ref().unwrap().is_expired());
+        assert_eq!(manager.get_ttl_remaining(), None);
+
+        manager.index = Some(synced_at(-chrono::Duration::days(2)));
+        assert!(!manager.index.as_ref().unwrap().is_expired());
+    }
+}
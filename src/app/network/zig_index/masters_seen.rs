@@ -0,0 +1,122 @@
+//! Append-only record of every master release zv has observed (`masters_seen.toml`)
+//!
+//! The upstream index only carries the current master, so once it moves on, an older
+//! `master@<dev-version>` can no longer be validated even though its tarball is still
+//! hosted on ziglang.org/builds. Releases recorded here keep those versions resolvable.
+
+use super::models::{CacheZigIndex, ZigIndex, ZigRelease};
+use crate::{CfgErr, app::network::TARGET, types::ResolvedZigVersion};
+use chrono::{NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct MastersSeen {
+    path: PathBuf,
+    releases: BTreeMap<ResolvedZigVersion, ZigRelease>,
+}
+
+impl MastersSeen {
+    /// Load the record from `path`. A missing or unreadable file yields an empty record.
+    pub async fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let releases = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => match toml::from_str::<CacheZigIndex>(&contents) {
+                Ok(cache) => ZigIndex::from(cache).releases().clone(),
+                Err(e) => {
+                    tracing::warn!(target: TARGET, "Ignoring unreadable {}: {}", path.display(), e);
+                    BTreeMap::new()
+                }
+            },
+            Err(_) => BTreeMap::new(),
+        };
+        Self { path, releases }
+    }
+
+    /// Look up a previously observed master release
+    pub fn get(&self, version: &semver::Version) -> Option<&ZigRelease> {
+        self.releases
+            .get(&ResolvedZigVersion::Master(version.clone()))
+    }
+
+    /// Record a master release. Existing entries are never overwritten.
+    /// Returns `true` if the release was not seen before.
+    pub fn record(&mut self, release: &ZigRelease) -> bool {
+        let key = ResolvedZigVersion::Master(release.resolved_version().version().clone());
+        if self.releases.contains_key(&key) {
+            return false;
+        }
+        self.releases.insert(key, release.clone());
+        true
+    }
+
+    /// Drop entries whose release date is more than `days` days ago.
+    /// Entries with an unparseable date are kept. Returns the number of entries removed.
+    pub fn prune_older_than(&mut self, days: i64) -> usize {
+        let today = Utc::now().date_naive();
+        let before = self.releases.len();
+        self.releases.retain(|_, release| {
+            NaiveDate::parse_from_str(release.date(), "%Y-%m-%d")
+                .map(|date| (today - date).num_days() <= days)
+                .unwrap_or(true)
+        });
+        before - self.releases.len()
+    }
+
+    /// Persist the record to disk
+    pub async fn save(&self) -> Result<(), CfgErr> {
+        let index = ZigIndex::with_releases(self.releases.clone(), None, None);
+        let toml_str = toml::to_string_pretty(&CacheZigIndex::from(&index))
+            .map_err(|e| CfgErr::ParseFail(e.into()))?;
+        tokio::fs::write(&self.path, toml_str)
+            .await
+            .map_err(|io_err| {
+                CfgErr::WriteFail(io_err.into(), self.path.to_string_lossy().to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn master(version: &str, date: &str) -> ZigRelease {
+        ZigRelease::new(
+            ResolvedZigVersion::Master(semver::Version::parse(version).unwrap()),
+            date.to_string(),
+            HashMap::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn records_are_append_only_and_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("masters_seen.toml");
+
+        let mut seen = MastersSeen::load(&path).await;
+        assert!(seen.record(&master("0.16.0-dev.120+abc", "2025-10-01")));
+        assert!(seen.record(&master("0.16.0-dev.200+def", "2025-10-05")));
+        assert!(!seen.record(&master("0.16.0-dev.120+abc", "2025-10-09")));
+        seen.save().await.unwrap();
+
+        let seen = MastersSeen::load(&path).await;
+        assert_eq!(seen.releases.len(), 2);
+        let old = semver::Version::parse("0.16.0-dev.120+abc").unwrap();
+        assert_eq!(seen.get(&old).unwrap().date(), "2025-10-01");
+    }
+
+    #[tokio::test]
+    async fn prunes_entries_past_the_horizon() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut seen = MastersSeen::load(temp.path().join("masters_seen.toml")).await;
+        let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+        seen.record(&master("0.14.0-dev.1+aaa", "2020-01-01"));
+        seen.record(&master("0.16.0-dev.1+bbb", &today));
+        seen.record(&master("0.16.0-dev.2+ccc", "not-a-date"));
+
+        assert_eq!(seen.prune_older_than(30), 1);
+        assert_eq!(seen.releases.len(), 2);
+    }
+}
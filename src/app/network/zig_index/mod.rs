@@ -1,8 +1,10 @@
 //! Zig download index representation and management
 
 pub mod index;
+pub mod masters_seen;
 pub mod models;
 
 // Re-export types
 pub use index::*;
+pub use masters_seen::*;
 pub use models::*;
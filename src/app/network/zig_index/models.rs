@@ -4,7 +4,7 @@
 //! 3. Cache Layer (CacheZigIndex, CacheZigRelease, CacheArtifact) - for TOML serialization
 
 use crate::app::INDEX_TTL_DAYS;
-use crate::app::utils::host_target;
+use crate::app::utils::{host_target, is_future_stamp};
 use crate::types::{ResolvedZigVersion, TargetTriple};
 use chrono::{DateTime, Utc};
 use serde::{
@@ -26,6 +26,10 @@ pub struct NetworkZigIndex {
 pub struct NetworkZigRelease {
     pub date: String,
     pub version: Option<String>, // Only present for master
+    /// Source tarball, for bootstrapping Zig from source
+    pub src: Option<NetworkArtifact>,
+    /// Release notes page, listed for tagged releases only
+    pub notes: Option<String>,
     pub targets: HashMap<String, NetworkArtifact>,
 }
 
@@ -68,6 +72,8 @@ impl<'de> Deserialize<'de> for NetworkZigRelease {
             {
                 let mut date = None;
                 let mut version = None;
+                let mut src = None;
+                let mut notes = None;
                 let mut targets = HashMap::new();
 
                 while let Some(key) = map.next_key::<String>()? {
@@ -79,8 +85,19 @@ impl<'de> Deserialize<'de> for NetworkZigRelease {
                             // Capture version field if present (for master)
                             version = Some(map.next_value()?);
                         }
-                        // Skip documentation, bootstrap, source, and other non-platform fields
-                        "docs" | "stdDocs" | "langRef" | "notes" | "bootstrap" | "src" => {
+                        "src" => {
+                            // Older releases list a source tarball without a size; skip those
+                            // Read the whole value first, so a malformed one can't leave the
+                            // parser midway through it
+                            let value: serde_json::Value = map.next_value()?;
+                            src = serde_json::from_value::<NetworkArtifact>(value).ok();
+                        }
+                        "notes" => {
+                            let value: serde_json::Value = map.next_value()?;
+                            notes = serde_json::from_value::<String>(value).ok();
+                        }
+                        // Skip documentation, bootstrap, and other non-platform fields
+                        "docs" | "stdDocs" | "langRef" | "bootstrap" => {
                             let _: serde_json::Value = map.next_value()?;
                         }
                         // Everything else should be a platform target
@@ -104,6 +121,8 @@ impl<'de> Deserialize<'de> for NetworkZigRelease {
                 Ok(NetworkZigRelease {
                     date,
                     version,
+                    src,
+                    notes,
                     targets,
                 })
             }
@@ -129,7 +148,7 @@ pub struct CacheZigIndex {
 }
 
 /// Simplified TOML representation of a Zig release
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheZigRelease {
     /// Version string (e.g., "0.11.0", "master")
     pub version: String,
@@ -137,10 +156,23 @@ pub struct CacheZigRelease {
     pub date: String,
     /// List of artifacts using array structure for clean TOML output
     pub artifacts: Vec<CacheArtifact>,
+    /// Source tarball, with `target` set to `src`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src: Option<CacheArtifact>,
+    /// Release notes URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl CacheZigRelease {
+    /// Whether this is a master build, stored as `master@<version>`
+    fn is_master(&self) -> bool {
+        self.version.starts_with("master@")
+    }
 }
 
 /// Simplified TOML representation of a download artifact
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheArtifact {
     /// Target triple in "arch-os" format
     pub target: String,
@@ -163,15 +195,31 @@ pub struct ArtifactInfo {
     pub size: u64,
 }
 
+impl From<NetworkArtifact> for ArtifactInfo {
+    fn from(artifact: NetworkArtifact) -> Self {
+        Self {
+            ziglang_org_tarball: artifact.ziglang_org_tarball,
+            shasum: artifact.shasum,
+            size: artifact.size,
+        }
+    }
+}
+
 /// Runtime representation of a Zig release optimized for fast lookups
 #[derive(Debug, Clone)]
 pub struct ZigRelease {
     /// Version information
     version: ResolvedZigVersion,
+    /// Canonical version string, see [`ZigRelease::version_string`]
+    version_string: String,
     /// Release date
     date: String,
     /// Map of target triples to artifact information
     artifacts: HashMap<TargetTriple, ArtifactInfo>,
+    /// Source tarball, if the index lists one
+    src: Option<ArtifactInfo>,
+    /// Release notes URL, if the index lists one
+    notes: Option<String>,
 }
 
 impl ZigRelease {
@@ -185,13 +233,48 @@ impl ZigRelease {
         date: String,
         artifacts: HashMap<TargetTriple, ArtifactInfo>,
     ) -> Self {
+        let version_string = match &version {
+            ResolvedZigVersion::Semver(v) => v.to_string(),
+            ResolvedZigVersion::Master(_) => "master".to_string(),
+        };
         Self {
             version,
+            version_string,
             date,
             artifacts,
+            src: None,
+            notes: None,
         }
     }
 
+    /// Attach the source tarball listed for this release
+    pub fn with_src(mut self, src: Option<ArtifactInfo>) -> Self {
+        self.src = src;
+        self
+    }
+
+    /// Attach the release notes URL listed for this release
+    pub fn with_notes(mut self, notes: Option<String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Release notes URL, listed by the index for tagged releases but not for master
+    pub fn notes_url(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+
+    /// Source tarball for bootstrapping this release, if the index lists one
+    pub fn src_artifact(&self) -> Option<&ArtifactInfo> {
+        self.src.as_ref()
+    }
+
+    /// Version as shown to users: the semver for tagged releases (`0.13.0`) and `master` for
+    /// master builds, whatever dev version they resolve to
+    pub fn version_string(&self) -> &str {
+        &self.version_string
+    }
+
     /// Get the version of this release
     pub fn resolved_version(&self) -> &ResolvedZigVersion {
         &self.version
@@ -210,8 +293,8 @@ impl ZigRelease {
     /// Generate tarball URL for the current host system
     /// Returns None if the target is not supported or no artifact is available
     pub fn zig_tarball_for_current_host(&self) -> Option<String> {
-        let host_target_str = host_target()?;
-        let target_triple = TargetTriple::from_key(&host_target_str)?;
+        let host_target_key = self.resolve_target_key(&host_target()?)?;
+        let target_triple = TargetTriple::from_key(&host_target_key)?;
         self.zig_tarball_for_target(&target_triple)
     }
 
@@ -324,15 +407,10 @@ impl ZigIndex {
         self.master_last_fetched = ts;
     }
 
-    /// Upsert the master release payload in the index
-    pub fn upsert_master_release(&mut self, release: ZigRelease) {
-        self.releases
-            .insert(release.resolved_version().clone(), release);
-    }
-
     /// Get the latest stable version
-    /// Returns the highest semantic version that is not a pre-release
-    pub fn get_latest_stable(&self) -> Option<&ResolvedZigVersion> {
+    /// Returns the highest semantic version that is not a pre-release,
+    /// or the highest non-master version at all when `include_prerelease` is set
+    pub fn get_latest_stable(&self, include_prerelease: bool) -> Option<&ResolvedZigVersion> {
         self.releases
             .keys()
             .rev() // Start from highest versions
@@ -340,7 +418,8 @@ impl ZigIndex {
                 match version {
                     ResolvedZigVersion::Semver(v) => {
                         // Only consider stable releases (no pre-release or build metadata)
-                        v.pre.is_empty() && v.build.is_empty()
+                        // unless release candidates were explicitly asked for
+                        (include_prerelease || v.pre.is_empty()) && v.build.is_empty()
                     }
                     _ => false, // Master variants are not considered stable
                 }
@@ -350,21 +429,47 @@ impl ZigIndex {
 
 // Backward compatibility wrapper for ZigIndex
 impl ZigIndex {
-    /// Check if a semver is in index (backward compatibility)
+    /// Check if a semver is in index (backward compatibility). A stable release wins over a
+    /// master build carrying the same version.
     pub fn contains_version(&self, version: &semver::Version) -> Option<&ZigRelease> {
-        let resolved_version = ResolvedZigVersion::Semver(version.clone());
-        self.releases().get(&resolved_version)
+        self.find_by_version(version)
+            .next()
+            .map(|(_, release)| release)
+    }
+
+    /// Every release whose numeric version is `version`, whatever the variant, stable first
+    pub fn find_by_version(
+        &self,
+        version: &semver::Version,
+    ) -> impl Iterator<Item = (&ResolvedZigVersion, &ZigRelease)> {
+        // Both variants of one version are adjacent under `ResolvedZigVersion`'s ordering
+        self.releases().range(
+            ResolvedZigVersion::Semver(version.clone())
+                ..=ResolvedZigVersion::Master(version.clone()),
+        )
     }
 
     /// Get the latest stable release version (backward compatibility)
-    pub fn get_latest_stable_release(&self) -> Option<&ZigRelease> {
-        if let Some(latest_version) = self.get_latest_stable() {
+    pub fn get_latest_stable_release(&self, include_prerelease: bool) -> Option<&ZigRelease> {
+        if let Some(latest_version) = self.get_latest_stable(include_prerelease) {
             self.releases().get(latest_version)
         } else {
             None
         }
     }
 
+    /// Newest stable release in `series`, e.g. 0.14.1 for `0.14`
+    pub fn get_latest_stable_in(&self, series: &crate::VersionSeries) -> Option<&ZigRelease> {
+        self.releases()
+            .iter()
+            .rev()
+            .find(|(version, _)| match version {
+                ResolvedZigVersion::Semver(v) => series.matches(v),
+                _ => false,
+            })
+            .map(|(_, release)| release)
+    }
+
     /// Get master version info (backward compatibility)
     pub fn get_master_version(&self) -> Option<&ZigRelease> {
         // Look for any master version in the index
@@ -380,6 +485,10 @@ impl ZigIndex {
     /// Cache expired? (backward compatibility)
     pub fn is_expired(&self) -> bool {
         if let Some(last_synced) = self.last_synced() {
+            // A stamp in the future would otherwise keep the index fresh until the clock catches up
+            if is_future_stamp(last_synced, "index") {
+                return true;
+            }
             let age = Utc::now() - last_synced;
             age.num_days() >= *INDEX_TTL_DAYS
         } else {
@@ -469,11 +578,14 @@ impl From<NetworkZigIndex> for ZigIndex {
                 }
             }
 
+            let src = network_release.src.map(ArtifactInfo::from);
             let runtime_release = ZigRelease::new(
                 resolved_version.clone(),
                 network_release.date,
                 runtime_artifacts,
-            );
+            )
+            .with_src(src)
+            .with_notes(network_release.notes);
 
             releases.insert(resolved_version, runtime_release);
         }
@@ -484,6 +596,36 @@ impl From<NetworkZigIndex> for ZigIndex {
     }
 }
 
+impl CacheZigIndex {
+    /// Merge a partial index update into this index.
+    ///
+    /// Releases only present in `other` are added, and releases whose date changed are
+    /// replaced by the newer entry. Releases absent from `other` are kept, so a response
+    /// limited to recent releases never shrinks the cached index. The index names a single
+    /// master build, so a master in `other` takes the place of the cached one.
+    pub fn merge(&self, other: &CacheZigIndex) -> CacheZigIndex {
+        let mut releases = self.releases.clone();
+        if other.releases.iter().any(|r| r.is_master()) {
+            releases.retain(|r| {
+                !r.is_master() || other.releases.iter().any(|u| u.version == r.version)
+            });
+        }
+        for update in &other.releases {
+            match releases.iter_mut().find(|r| r.version == update.version) {
+                Some(existing) if existing.date != update.date => *existing = update.clone(),
+                Some(_) => {}
+                None => releases.push(update.clone()),
+            }
+        }
+
+        CacheZigIndex {
+            releases,
+            last_synced: self.last_synced.max(other.last_synced),
+            master_last_fetched: self.master_last_fetched.max(other.master_last_fetched),
+        }
+    }
+}
+
 impl From<&ZigIndex> for CacheZigIndex {
     fn from(runtime_index: &ZigIndex) -> Self {
         let mut cache_releases = Vec::new();
@@ -514,6 +656,13 @@ impl From<&ZigIndex> for CacheZigIndex {
                 version: version_string,
                 date: runtime_release.date.clone(),
                 artifacts: cache_artifacts,
+                src: runtime_release.src.as_ref().map(|src| CacheArtifact {
+                    target: "src".to_string(),
+                    tarball_url: src.ziglang_org_tarball.clone(),
+                    shasum: src.shasum.clone(),
+                    size: src.size,
+                }),
+                notes: runtime_release.notes.clone(),
             };
 
             cache_releases.push(cache_release);
@@ -582,11 +731,18 @@ impl From<CacheZigIndex> for ZigIndex {
                 }
             }
 
+            let src = cache_release.src.map(|src| ArtifactInfo {
+                ziglang_org_tarball: src.tarball_url,
+                shasum: src.shasum,
+                size: src.size,
+            });
             let runtime_release = ZigRelease::new(
                 resolved_version.clone(),
                 cache_release.date,
                 runtime_artifacts,
-            );
+            )
+            .with_src(src)
+            .with_notes(cache_release.notes);
 
             releases.insert(resolved_version, runtime_release);
         }
@@ -598,3 +754,278 @@ impl From<CacheZigIndex> for ZigIndex {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(version: &str, date: &str, shasum: &str) -> CacheZigRelease {
+        CacheZigRelease {
+            version: version.to_string(),
+            date: date.to_string(),
+            artifacts: vec![CacheArtifact {
+                target: "x86_64-linux".to_string(),
+                tarball_url: format!("https://ziglang.org/download/{version}/zig.tar.xz"),
+                shasum: shasum.to_string(),
+                size: 1,
+            }],
+            src: None,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn merge_adds_and_updates_without_removing() {
+        let cached = CacheZigIndex {
+            releases: vec![
+                release("0.13.0", "2024-06-07", "aaa"),
+                release("master@0.15.0-dev.1+abc", "2025-01-01", "bbb"),
+            ],
+            last_synced: None,
+            master_last_fetched: Some(Utc::now()),
+        };
+        let partial = CacheZigIndex {
+            releases: vec![
+                release("master@0.15.0-dev.1+abc", "2025-01-02", "ccc"),
+                release("0.14.0", "2025-03-05", "ddd"),
+            ],
+            last_synced: Some(Utc::now()),
+            master_last_fetched: None,
+        };
+
+        let merged = cached.merge(&partial);
+        let versions: Vec<_> = merged.releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, ["0.13.0", "master@0.15.0-dev.1+abc", "0.14.0"]);
+        assert_eq!(merged.releases[1].artifacts[0].shasum, "ccc");
+        assert_eq!(merged.last_synced, partial.last_synced);
+        assert_eq!(merged.master_last_fetched, cached.master_last_fetched);
+    }
+
+    #[test]
+    fn merge_replaces_the_cached_master_with_a_newer_one() {
+        let cached = CacheZigIndex {
+            releases: vec![
+                release("0.14.1", "2025-05-21", "aaa"),
+                release("master@0.15.0-dev.1+abc", "2025-01-01", "bbb"),
+            ],
+            last_synced: None,
+            master_last_fetched: None,
+        };
+        let probe = CacheZigIndex {
+            releases: vec![release("master@0.15.0-dev.9+def", "2025-01-09", "ccc")],
+            last_synced: None,
+            master_last_fetched: Some(Utc::now()),
+        };
+
+        let merged = cached.merge(&probe);
+        let versions: Vec<_> = merged.releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, ["0.14.1", "master@0.15.0-dev.9+def"]);
+        assert_eq!(merged.master_last_fetched, probe.master_last_fetched);
+    }
+
+    #[test]
+    fn merge_keeps_existing_entry_when_date_is_unchanged() {
+        let cached = CacheZigIndex {
+            releases: vec![release("0.13.0", "2024-06-07", "aaa")],
+            last_synced: None,
+            master_last_fetched: None,
+        };
+        let partial = CacheZigIndex {
+            releases: vec![release("0.13.0", "2024-06-07", "zzz")],
+            last_synced: None,
+            master_last_fetched: None,
+        };
+
+        let merged = cached.merge(&partial);
+        assert_eq!(merged.releases.len(), 1);
+        assert_eq!(merged.releases[0].artifacts[0].shasum, "aaa");
+    }
+
+    #[test]
+    fn latest_stable_only_includes_prereleases_on_request() {
+        let index: ZigIndex = CacheZigIndex {
+            releases: vec![
+                release("0.14.1", "2025-05-21", "aaa"),
+                release("0.15.0-rc.1", "2025-08-01", "bbb"),
+                release("master@0.16.0-dev.1+abc", "2025-08-02", "ccc"),
+            ],
+            last_synced: None,
+            master_last_fetched: None,
+        }
+        .into();
+
+        let stable = index.get_latest_stable(false).unwrap();
+        assert_eq!(stable.version().to_string(), "0.14.1");
+        let prerelease = index.get_latest_stable(true).unwrap();
+        assert_eq!(prerelease.version().to_string(), "0.15.0-rc.1");
+        assert!(!prerelease.is_master());
+    }
+
+    #[test]
+    fn latest_stable_in_series_picks_the_newest_patch() {
+        let index: ZigIndex = CacheZigIndex {
+            releases: vec![
+                release("0.14.0", "2025-03-05", "aaa"),
+                release("0.14.1", "2025-05-21", "bbb"),
+                release("0.15.0-rc.1", "2025-08-01", "ccc"),
+                release("0.15.1", "2025-08-19", "ddd"),
+            ],
+            last_synced: None,
+            master_last_fetched: None,
+        }
+        .into();
+
+        let latest_in = |major, minor| {
+            let series = crate::VersionSeries { major, minor };
+            index
+                .get_latest_stable_in(&series)
+                .map(|release| release.resolved_version().version().to_string())
+        };
+        assert_eq!(latest_in(0, Some(14)).as_deref(), Some("0.14.1"));
+        assert_eq!(latest_in(0, Some(15)).as_deref(), Some("0.15.1"));
+        assert_eq!(latest_in(0, None).as_deref(), Some("0.15.1"));
+        assert_eq!(latest_in(0, Some(16)), None);
+    }
+
+    #[test]
+    fn stable_lookups_ignore_a_master_with_the_same_version() {
+        let index: ZigIndex = CacheZigIndex {
+            releases: vec![
+                release("master@0.15.0", "2025-08-20", "ccc"),
+                release("0.15.0", "2025-08-19", "bbb"),
+                release("0.14.1", "2025-05-21", "aaa"),
+            ],
+            last_synced: None,
+            master_last_fetched: None,
+        }
+        .into();
+        let version = semver::Version::new(0, 15, 0);
+
+        let latest = index.get_latest_stable(false).unwrap();
+        assert_eq!(latest, &ResolvedZigVersion::Semver(version.clone()));
+        assert_eq!(
+            index.contains_version(&version).unwrap().date(),
+            "2025-08-19"
+        );
+        let variants: Vec<bool> = index
+            .find_by_version(&version)
+            .map(|(resolved, _)| resolved.is_master())
+            .collect();
+        assert_eq!(variants, [false, true]);
+
+        let dev = semver::Version::parse("0.16.0-dev.1+abc").unwrap();
+        let index: ZigIndex = CacheZigIndex {
+            releases: vec![release("master@0.16.0-dev.1+abc", "2025-08-21", "ddd")],
+            last_synced: None,
+            master_last_fetched: None,
+        }
+        .into();
+        assert!(index.contains_version(&dev).is_some());
+        assert!(index.get_latest_stable(true).is_none());
+    }
+
+    #[test]
+    fn version_string_is_master_for_master_releases() {
+        let index: ZigIndex = CacheZigIndex {
+            releases: vec![
+                release("0.13.0", "2024-06-07", "aaa"),
+                release("master@0.16.0-dev.1+abc", "2025-08-02", "bbb"),
+            ],
+            last_synced: None,
+            master_last_fetched: None,
+        }
+        .into();
+
+        let version_strings: Vec<&str> = index
+            .releases()
+            .values()
+            .map(ZigRelease::version_string)
+            .collect();
+        assert!(version_strings.contains(&"0.13.0"));
+        assert_eq!(
+            index.get_master_version().unwrap().version_string(),
+            "master"
+        );
+    }
+
+    #[test]
+    fn source_tarball_and_notes_survive_the_cache_round_trip() {
+        let json = r#"{
+            "0.14.1": {
+                "date": "2025-05-21",
+                "docs": "https://ziglang.org/documentation/0.14.1/",
+                "notes": "https://ziglang.org/download/0.14.1/release-notes.html",
+                "src": {
+                    "tarball": "https://ziglang.org/download/0.14.1/zig-0.14.1.tar.xz",
+                    "shasum": "abc",
+                    "size": "17772188"
+                },
+                "x86_64-linux": {
+                    "tarball": "https://ziglang.org/download/0.14.1/zig-x86_64-linux-0.14.1.tar.xz",
+                    "shasum": "def",
+                    "size": "49086504"
+                }
+            }
+        }"#;
+        let index: ZigIndex = serde_json::from_str::<NetworkZigIndex>(json)
+            .unwrap()
+            .into();
+        let cached: ZigIndex = CacheZigIndex::from(&index).into();
+
+        let version = ResolvedZigVersion::Semver(semver::Version::new(0, 14, 1));
+        let release = &cached.releases()[&version];
+        let src = release.src_artifact().unwrap();
+        assert!(src.ziglang_org_tarball.ends_with("/zig-0.14.1.tar.xz"));
+        assert_eq!((src.shasum.as_str(), src.size), ("abc", 17772188));
+        assert_eq!(release.artifacts().len(), 1);
+        assert_eq!(
+            release.notes_url(),
+            Some("https://ziglang.org/download/0.14.1/release-notes.html")
+        );
+    }
+
+    #[test]
+    fn malformed_source_tarball_is_skipped_without_failing_the_index() {
+        let json = r#"{
+            "0.9.0": {
+                "date": "2021-12-20",
+                "src": {"size": "abc", "tarball": "x"},
+                "x86_64-linux": {
+                    "tarball": "https://ziglang.org/download/0.9.0/zig-linux-x86_64-0.9.0.tar.xz",
+                    "shasum": "def",
+                    "size": "42"
+                }
+            }
+        }"#;
+        let index: ZigIndex = serde_json::from_str::<NetworkZigIndex>(json)
+            .unwrap()
+            .into();
+
+        let release = &index.releases()[&ResolvedZigVersion::Semver(semver::Version::new(0, 9, 0))];
+        assert!(release.src_artifact().is_none());
+        assert_eq!(release.artifacts().len(), 1);
+    }
+
+    #[test]
+    fn non_string_notes_are_skipped_without_failing_the_index() {
+        let json = r#"{
+            "0.14.1": {
+                "date": "2025-05-21",
+                "notes": {"url": "https://ziglang.org/download/0.14.1/release-notes.html"},
+                "x86_64-linux": {
+                    "tarball": "https://ziglang.org/download/0.14.1/zig-x86_64-linux-0.14.1.tar.xz",
+                    "shasum": "def",
+                    "size": "49086504"
+                }
+            }
+        }"#;
+        let index: ZigIndex = serde_json::from_str::<NetworkZigIndex>(json)
+            .unwrap()
+            .into();
+
+        let release =
+            &index.releases()[&ResolvedZigVersion::Semver(semver::Version::new(0, 14, 1))];
+        assert!(release.notes_url().is_none());
+        assert_eq!(release.artifacts().len(), 1);
+    }
+}
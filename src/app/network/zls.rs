@@ -1,5 +1,5 @@
 use crate::app::constants::ZLS_SELECT_VERSION_ENDPOINT;
-use crate::app::network::create_client;
+use crate::app::network::{NetTimeouts, create_index_client};
 use crate::{NetErr, ZvError};
 use color_eyre::eyre::eyre;
 use serde::Deserialize;
@@ -59,8 +59,11 @@ where
     }
 }
 
-pub async fn select_version(zig_version: &str) -> Result<ZlsRelease, ZvError> {
-    let client = create_client()?;
+pub async fn select_version(
+    zig_version: &str,
+    timeouts: &NetTimeouts,
+) -> Result<ZlsRelease, ZvError> {
+    let client = create_index_client(timeouts)?;
     let response = client
         .get(ZLS_SELECT_VERSION_ENDPOINT)
         .query(&[("zig_version", zig_version), ("compatibility", "full")])
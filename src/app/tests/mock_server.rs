@@ -0,0 +1,406 @@
+//! Local stand-in for ziglang.org and a community mirror, backed by `wiremock`
+//!
+//! Serves a download index listing one release and a master build, a mirrors list pointing
+//! back at itself, and fake Zig archives and a source tarball (each with a `.minisig` from a
+//! throwaway test key) whose SHA-256 matches the index. Like the real servers, the index and
+//! mirrors answer `Range` requests with 206 Partial Content.
+
+use crate::app::utils::{host_target, zig_tarball};
+use crate::types::{ArchiveExt, Shim};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use blake2::Blake2b512;
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// The only stable release listed in the mock index
+pub const MOCK_VERSION: &str = "0.15.1";
+
+/// Version of the master build listed in the mock index
+pub const MOCK_MASTER_VERSION: &str = "0.16.0-dev.1+d0ba6642b";
+
+/// File name of the source tarball listed for [`MOCK_VERSION`]
+pub const MOCK_SOURCE_TARBALL: &str = "zig-0.15.1.tar.xz";
+
+/// Key id embedded in the mock public key and every mock signature
+const MOCK_KEY_ID: [u8; 8] = *b"zv-mock!";
+
+/// How the mirror deviates from the release it advertises
+enum Fault {
+    None,
+    /// Bytes that don't match the advertised checksum
+    Corrupt,
+    /// A signature issued for another file name
+    SwappedSignature,
+    /// Tampered bytes the index checksum agrees with, under the original signature
+    Tampered,
+    /// The connection drops halfway through the first archive download
+    Interrupted,
+    /// Two mirrors, each answering its first archive request with 503
+    Unavailable,
+}
+
+pub struct MockZigServer {
+    server: MockServer,
+    tarball: String,
+    shasum: String,
+}
+
+impl MockZigServer {
+    /// Start a server whose mirror hosts a valid archive for [`MOCK_VERSION`]
+    pub async fn start() -> Self {
+        Self::start_with(Fault::None).await
+    }
+
+    /// Start a server whose mirror serves bytes that don't match the advertised checksum
+    pub async fn start_corrupt() -> Self {
+        Self::start_with(Fault::Corrupt).await
+    }
+
+    /// Start a server whose mirror pairs the archive with a signature issued for another file
+    pub async fn start_swapped_signature() -> Self {
+        Self::start_with(Fault::SwappedSignature).await
+    }
+
+    /// Start a server whose index and mirror agree on a tampered archive the signature
+    /// doesn't cover
+    pub async fn start_tampered() -> Self {
+        Self::start_with(Fault::Tampered).await
+    }
+
+    /// Start a server whose mirror drops the connection halfway through the first archive
+    /// download, then serves the rest to a `Range` request
+    pub async fn start_interrupted() -> Self {
+        Self::start_with(Fault::Interrupted).await
+    }
+
+    /// Start a server listing two mirrors that each fail their first archive request with 503
+    pub async fn start_unavailable() -> Self {
+        Self::start_with(Fault::Unavailable).await
+    }
+
+    async fn start_with(fault: Fault) -> Self {
+        let server = MockServer::start().await;
+        let version = semver::Version::parse(MOCK_VERSION).unwrap();
+        let tarball = zig_tarball(&version, None).expect("host has a zig tarball name");
+        let archive = fake_zig_archive(&tarball, MOCK_VERSION);
+        let source = fake_source_archive();
+        let master = semver::Version::parse(MOCK_MASTER_VERSION).unwrap();
+        let master_tarball = zig_tarball(&master, None).expect("host has a zig tarball name");
+        let master_archive = fake_zig_archive(&master_tarball, MOCK_MASTER_VERSION);
+
+        let signed_file = match fault {
+            Fault::SwappedSignature => format!("swapped-{tarball}"),
+            _ => tarball.clone(),
+        };
+        let signature = sign(&signed_file, &archive);
+        let served = match fault {
+            Fault::Corrupt => [&archive[..], b"corrupted"].concat(),
+            Fault::Tampered => fake_zig_archive(&tarball, "tampered"),
+            _ => archive.clone(),
+        };
+        let advertised = match fault {
+            Fault::Tampered => &served,
+            _ => &archive,
+        };
+        let shasum = format!("{:x}", Sha256::digest(advertised));
+        let size = advertised.len();
+
+        let index = serde_json::json!({
+            "master": {
+                "version": MOCK_MASTER_VERSION,
+                "date": "2025-10-01",
+                host_target().unwrap(): {
+                    "tarball": format!("{}/builds/{master_tarball}", server.uri()),
+                    "shasum": format!("{:x}", Sha256::digest(&master_archive)),
+                    "size": master_archive.len().to_string(),
+                }
+            },
+            MOCK_VERSION: {
+                "date": "2025-08-19",
+                "src": {
+                    "tarball": format!("{}/download/{MOCK_VERSION}/{MOCK_SOURCE_TARBALL}", server.uri()),
+                    "shasum": format!("{:x}", Sha256::digest(&source)),
+                    "size": source.len().to_string(),
+                },
+                host_target().unwrap(): {
+                    "tarball": format!("{}/download/{MOCK_VERSION}/{tarball}", server.uri()),
+                    "shasum": shasum,
+                    "size": size.to_string(),
+                }
+            }
+        });
+        Mock::given(method("GET"))
+            .and(path("/download/index.json"))
+            .respond_with(Ranged(serde_json::to_vec(&index).unwrap()))
+            .mount(&server)
+            .await;
+
+        let mirrors: &[&str] = match fault {
+            Fault::Unavailable => &["mirror", "backup"],
+            _ => &["mirror"],
+        };
+        // The first archive request reaches the mirror through a connection that drops midway
+        let mirror_host = match fault {
+            Fault::Interrupted => {
+                let proxy = interrupting_proxy(*server.address(), served.clone()).await;
+                format!("http://{proxy}")
+            }
+            _ => server.uri(),
+        };
+        let list: String = mirrors
+            .iter()
+            .map(|mirror| format!("{mirror_host}/{mirror}\n"))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/mirrors"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list))
+            .mount(&server)
+            .await;
+
+        let archive_path = |mirror: &str| format!("/{mirror}/{MOCK_VERSION}/{tarball}");
+        for mirror in mirrors {
+            if let Fault::Unavailable = fault {
+                Mock::given(method("GET"))
+                    .and(path(archive_path(mirror)))
+                    .respond_with(ResponseTemplate::new(503))
+                    .up_to_n_times(1)
+                    .with_priority(1)
+                    .mount(&server)
+                    .await;
+            }
+            let files = [
+                (archive_path(mirror), Ranged(served.clone())),
+                (
+                    format!("/{mirror}/{MOCK_VERSION}/{MOCK_SOURCE_TARBALL}"),
+                    Ranged(source.clone()),
+                ),
+                (
+                    format!("/{mirror}/{master}/{master_tarball}"),
+                    Ranged(master_archive.clone()),
+                ),
+            ];
+            for (file_path, body) in files {
+                Mock::given(method("GET"))
+                    .and(path(file_path))
+                    .respond_with(body)
+                    .mount(&server)
+                    .await;
+            }
+
+            let signatures = [
+                (archive_path(mirror), signature.clone()),
+                (
+                    format!("/{mirror}/{MOCK_VERSION}/{MOCK_SOURCE_TARBALL}"),
+                    sign(MOCK_SOURCE_TARBALL, &source),
+                ),
+                (
+                    format!("/{mirror}/{master}/{master_tarball}"),
+                    sign(&master_tarball, &master_archive),
+                ),
+            ];
+            for (file_path, signature) in signatures {
+                Mock::given(method("GET"))
+                    .and(path(format!("{file_path}.minisig")))
+                    .respond_with(ResponseTemplate::new(200).set_body_string(signature))
+                    .mount(&server)
+                    .await;
+            }
+        }
+
+        Self {
+            server,
+            tarball,
+            shasum,
+        }
+    }
+
+    pub fn index_url(&self) -> String {
+        format!("{}/download/index.json", self.server.uri())
+    }
+
+    pub fn mirrors_url(&self) -> String {
+        format!("{}/mirrors", self.server.uri())
+    }
+
+    /// File name of the hosted archive
+    pub fn tarball(&self) -> &str {
+        &self.tarball
+    }
+
+    /// SHA-256 advertised in the index
+    pub fn shasum(&self) -> &str {
+        &self.shasum
+    }
+
+    /// Public key the mock signatures verify against, in minisign's base64 form
+    pub fn pubkey(&self) -> String {
+        let mut key = b"Ed".to_vec();
+        key.extend_from_slice(&MOCK_KEY_ID);
+        key.extend_from_slice(mock_key().verifying_key().as_bytes());
+        STANDARD.encode(key)
+    }
+
+    /// Number of requests the server received for `request_path`
+    pub async fn hits(&self, request_path: &str) -> usize {
+        self.ranges(request_path).await.len()
+    }
+
+    /// `Range` header of each request the server received for `request_path`, in order
+    pub async fn ranges(&self, request_path: &str) -> Vec<Option<String>> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == request_path)
+            .map(|request| {
+                let range = request.headers.get("range")?;
+                range.to_str().ok().map(str::to_string)
+            })
+            .collect()
+    }
+}
+
+/// A file served whole, or from the start of a `Range: bytes=<start>-<end>` request with 206
+/// like ziglang.org and the mirrors do
+struct Ranged(Vec<u8>);
+
+impl Respond for Ranged {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let len = self.0.len();
+        let range = request
+            .headers
+            .get("range")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("bytes="))
+            .and_then(|value| value.split_once('-'));
+        if let Some((start, end)) = range {
+            let start: usize = start.parse().unwrap();
+            let end = end.parse().map_or(len - 1, |end: usize| end.min(len - 1));
+            if start >= len {
+                return ResponseTemplate::new(416)
+                    .insert_header("content-range", format!("bytes */{len}"));
+            }
+            return ResponseTemplate::new(206)
+                .insert_header("content-range", format!("bytes {start}-{end}/{len}"))
+                .set_body_bytes(&self.0[start..=end]);
+        }
+        ResponseTemplate::new(200).set_body_bytes(self.0.clone())
+    }
+}
+
+/// TCP front for `upstream` that answers the first request itself, announcing all of `body`
+/// but hanging up halfway through it, and passes later connections through. `wiremock` can't
+/// send a body shorter than its `Content-Length`.
+async fn interrupting_proxy(upstream: SocketAddr, body: Vec<u8>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let Ok((mut first, _)) = listener.accept().await else {
+            return;
+        };
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            match first.read(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+        let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+        let _ = first.write_all(head.as_bytes()).await;
+        let _ = first.write_all(&body[..body.len() / 2]).await;
+        drop(first);
+
+        while let Ok((mut client, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                if let Ok(mut server) = TcpStream::connect(upstream).await {
+                    let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+                }
+            });
+        }
+    });
+    address
+}
+
+fn mock_key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+}
+
+/// Prehashed minisign signature of `contents`, with `file` in the trusted comment
+fn sign(file: &str, contents: &[u8]) -> String {
+    let key = mock_key();
+    let signature = key.sign(&Blake2b512::digest(contents)).to_bytes();
+    let trusted_comment = format!("timestamp:0\tfile:{file}\tmock");
+    let global = key
+        .sign(&[&signature[..], trusted_comment.as_bytes()].concat())
+        .to_bytes();
+
+    let mut line = b"ED".to_vec();
+    line.extend_from_slice(&MOCK_KEY_ID);
+    line.extend_from_slice(&signature);
+    format!(
+        "untrusted comment: mock signature\n{}\ntrusted comment: {trusted_comment}\n{}\n",
+        STANDARD.encode(line),
+        STANDARD.encode(global)
+    )
+}
+
+/// Archive named `tarball` wrapping a single placeholder `zig` executable that prints `output`
+fn fake_zig_archive(tarball: &str, output: &str) -> Vec<u8> {
+    let zip = tarball.ends_with(".zip");
+    let ext = if zip {
+        ArchiveExt::Zip
+    } else {
+        ArchiveExt::TarXz
+    };
+    let root = tarball.strip_suffix(&format!(".{ext}")).unwrap_or(tarball);
+    let exe = Shim::Zig.executable_name();
+    let contents = format!("#!/bin/sh\necho {output}\n");
+
+    if zip {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                format!("{root}/{exe}"),
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+        return writer.finish().unwrap().into_inner();
+    }
+
+    let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(Vec::new(), 6));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, format!("{root}/{exe}"), contents.as_bytes())
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+/// Source tarball holding a placeholder `build.zig` under the usual `zig-<version>/` root
+fn fake_source_archive() -> Vec<u8> {
+    let contents = "pub fn build(b: *std.Build) void {}\n";
+    let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(Vec::new(), 6));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(
+            &mut header,
+            format!("zig-{MOCK_VERSION}/build.zig"),
+            contents.as_bytes(),
+        )
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+}
@@ -0,0 +1,443 @@
+//! End-to-end tests of the resolve → download → install pipeline against a local mock server
+
+mod mock_server;
+
+use super::{App, Either, network::ZvNetwork};
+use crate::Shim;
+use crate::types::ResolvedZigVersion;
+use mock_server::{MOCK_MASTER_VERSION, MOCK_SOURCE_TARBALL, MOCK_VERSION, MockZigServer};
+
+/// `App` rooted in `root` whose network talks to `server` instead of ziglang.org
+async fn app_against(server: &MockZigServer, root: &std::path::Path) -> App {
+    let mut app = App::with_zv_dir(root).await.unwrap();
+    let network = ZvNetwork::new(
+        app.paths.index_file.clone(),
+        app.paths.mirrors_file.clone(),
+        app.paths.downloads_dir.clone(),
+        app.mirror_priorities(),
+        app.net_timeouts(),
+    )
+    .await
+    .unwrap()
+    .with_endpoints(&server.index_url(), &server.mirrors_url())
+    .with_minisign_pubkey(&server.pubkey());
+    app.network = Some(network);
+    app.ensure_network().await.unwrap();
+    app
+}
+
+#[tokio::test]
+async fn with_zv_dir_builds_an_isolated_offline_app() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path().join("zv");
+    let app = App::with_zv_dir(&root).await.unwrap();
+
+    for dir in ["bin", "versions", "downloads"] {
+        assert!(root.join(dir).is_dir());
+    }
+    assert_eq!(app.paths.index_file, root.join("index.toml.gz"));
+    assert!(app.network.is_none());
+    assert!(app.shell.is_none());
+    // A fresh temp dir is never on PATH
+    assert!(!app.is_setup());
+    assert!(app.toolchain_manager.installations().is_empty());
+}
+
+#[test]
+fn bin_dir_override_must_stay_out_of_the_versions_dir() {
+    let temp = tempfile::tempdir().unwrap();
+    let versions = temp.path().join("versions");
+
+    assert!(App::check_bin_dir_override(&versions.join("0.14.1/bin"), &versions).is_err());
+    let bin = temp.path().join("shared-bin");
+    App::check_bin_dir_override(&bin, &versions).unwrap();
+    assert!(bin.is_dir());
+}
+
+#[tokio::test]
+async fn validate_semver_resolves_from_mock_index() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    let Either::Release(release) = app.validate_semver(&version).await.unwrap() else {
+        panic!("{MOCK_VERSION} should resolve to a release from the mock index");
+    };
+    let host = crate::app::utils::host_target().unwrap();
+    assert_eq!(release.shasum(&host), Some(server.shasum()));
+    assert!(temp.path().join("index.toml.gz").is_file());
+
+    let unknown = semver::Version::parse("9.9.9").unwrap();
+    assert!(matches!(
+        app.validate_semver(&unknown).await.unwrap(),
+        Either::Version(ResolvedZigVersion::Semver(v)) if v == unknown
+    ));
+}
+
+#[tokio::test]
+async fn install_release_downloads_from_mock_mirror() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    let zig = app.install_release(false).await.unwrap();
+
+    assert!(zig.is_file());
+    assert!(zig.starts_with(temp.path().join("versions").join(MOCK_VERSION)));
+    assert!(temp.path().join("mirrors.toml").is_file());
+    let tarball_path = format!("/mirror/{MOCK_VERSION}/{}", server.tarball());
+    assert!(server.hits(&tarball_path).await > 0);
+    // The archive is removed from the download cache once extracted
+    assert!(
+        !temp
+            .path()
+            .join("downloads")
+            .join(server.tarball())
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn install_release_rejects_checksum_mismatch() {
+    let server = MockZigServer::start_corrupt().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+
+    assert!(app.install_release(false).await.is_err());
+    assert!(!temp.path().join("versions").join(MOCK_VERSION).exists());
+    // Every attempt downloaded the corrupt archive, none of them left it behind
+    let tarball_path = format!("/mirror/{MOCK_VERSION}/{}", server.tarball());
+    assert_eq!(
+        server.hits(&tarball_path).await,
+        *crate::app::MAX_RETRIES as usize
+    );
+    let temp_dir = temp.path().join("downloads").join("tmp");
+    assert_eq!(std::fs::read_dir(temp_dir).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn interrupted_download_resumes_where_it_stopped() {
+    let server = MockZigServer::start_interrupted().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    let zig = app.install_release(false).await.unwrap();
+    assert!(zig.is_file());
+
+    // The retry asks for the bytes after the half that arrived, not the whole archive again
+    let tarball_path = format!("/mirror/{MOCK_VERSION}/{}", server.tarball());
+    let ranges = server.ranges(&tarball_path).await;
+    let [Some(resumed)] = ranges.as_slice() else {
+        panic!("expected one range request after the dropped download: {ranges:?}");
+    };
+    assert_ne!(resumed, "bytes=0-");
+    assert!(resumed.starts_with("bytes=") && resumed.ends_with('-'));
+
+    let mirrors = app.network.as_mut().unwrap().ensure_mirror_manager().await;
+    let stats = &mirrors.unwrap().all_mirrors_mut().await.unwrap()[0].stats;
+    assert_eq!((stats.failures, stats.successes), (1, 1));
+}
+
+#[tokio::test]
+async fn unavailable_mirror_fails_over_to_the_other() {
+    let server = MockZigServer::start_unavailable().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    app.install_release(false).await.unwrap();
+
+    // Each mirror's first answer is a 503; the second attempt must not retry the first mirror
+    for mirror in ["mirror", "backup"] {
+        let tarball_path = format!("/{mirror}/{MOCK_VERSION}/{}", server.tarball());
+        assert!(
+            server.hits(&tarball_path).await >= 1,
+            "{mirror} was never tried"
+        );
+    }
+    let mirrors = app.network.as_mut().unwrap().ensure_mirror_manager().await;
+    let mirrors = mirrors.unwrap().all_mirrors_mut().await.unwrap();
+    assert!(mirrors.iter().all(|mirror| mirror.stats.failures == 1));
+    let successes: u64 = mirrors.iter().map(|mirror| mirror.stats.successes).sum();
+    assert_eq!(successes, 1);
+}
+
+#[tokio::test]
+async fn master_found_by_partial_fetch_installs_from_mirror() {
+    use crate::app::network::{PartialFetchResult, try_partial_fetch_master};
+
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let result = try_partial_fetch_master(&reqwest::Client::new(), &server.index_url())
+        .await
+        .unwrap();
+    let PartialFetchResult::Complete(master) = result else {
+        panic!("the whole master entry fits in the first range");
+    };
+    let version = semver::Version::parse(MOCK_MASTER_VERSION).unwrap();
+    assert_eq!(
+        master.resolved_version(),
+        &ResolvedZigVersion::Master(version.clone())
+    );
+    assert_eq!(
+        server.ranges("/download/index.json").await,
+        [Some("bytes=0-8191".to_string())]
+    );
+
+    app.to_install = Some(master.into());
+    let zig = app.install_release(false).await.unwrap();
+    assert!(zig.is_file());
+    let tarball = crate::app::utils::zig_tarball(&version, None).unwrap();
+    let tarball_path = format!("/mirror/{version}/{tarball}");
+    assert_eq!(server.hits(&tarball_path).await, 1);
+}
+
+#[tokio::test]
+async fn install_release_rejects_signature_for_another_file() {
+    let server = MockZigServer::start_swapped_signature().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+
+    assert!(app.install_release(false).await.is_err());
+    assert!(!temp.path().join("versions").join(MOCK_VERSION).exists());
+    assert!(
+        !temp
+            .path()
+            .join("downloads")
+            .join(server.tarball())
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn install_release_rejects_tampered_archive_with_matching_checksum() {
+    let server = MockZigServer::start_tampered().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+
+    assert!(app.install_release(false).await.is_err());
+    let tarball_path = format!("/mirror/{MOCK_VERSION}/{}", server.tarball());
+    assert!(server.hits(&tarball_path).await > 0);
+    assert!(!temp.path().join("versions").join(MOCK_VERSION).exists());
+    assert!(
+        !temp
+            .path()
+            .join("downloads")
+            .join(server.tarball())
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn release_without_host_build_is_rejected_before_download() {
+    use crate::app::network::{ArtifactInfo, ZigRelease};
+
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    let foreign_only = ZigRelease::new(
+        ResolvedZigVersion::Semver(version.clone()),
+        "2025-08-19".to_string(),
+        [(
+            crate::types::TargetTriple::new("sparc64".to_string(), "plan9".to_string()),
+            ArtifactInfo {
+                ziglang_org_tarball: "https://ziglang.org/zig-sparc64-plan9.tar.xz".to_string(),
+                shasum: "0".repeat(64),
+                size: 1,
+            },
+        )]
+        .into(),
+    );
+    app.to_install = Some(foreign_only.into());
+
+    let err = app.ensure_host_artifact().unwrap_err();
+    assert!(matches!(err, crate::ZvError::ZigNotFound(_)));
+
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    assert!(app.ensure_host_artifact().is_ok());
+}
+
+#[tokio::test]
+async fn clear_all_caches_keeps_in_progress_downloads() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.validate_semver(&version).await.unwrap();
+    let downloads = temp.path().join("downloads");
+    std::fs::create_dir_all(downloads.join("tmp")).unwrap();
+    std::fs::write(downloads.join("zig-old.tar.xz"), [0u8; 512]).unwrap();
+    std::fs::write(downloads.join("tmp").join("zig-partial.tar.xz"), b"").unwrap();
+    assert!(
+        app.cached_files()
+            .contains(&temp.path().join("index.toml.gz"))
+    );
+
+    let (removed, freed_bytes) = app.clear_all_caches().await.unwrap();
+    assert!(removed >= 2);
+    assert!(freed_bytes >= 512);
+    assert!(app.cached_files().is_empty());
+    assert!(downloads.join("tmp").join("zig-partial.tar.xz").is_file());
+}
+
+#[tokio::test]
+async fn second_use_of_same_version_reuses_first_install() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    // Both invocations start before either has installed anything
+    let mut first = app_against(&server, temp.path()).await;
+    let mut second = app_against(&server, temp.path()).await;
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    let rzv = ResolvedZigVersion::Semver(version.clone());
+    first.to_install = Some(first.validate_semver(&version).await.unwrap());
+    second.to_install = Some(second.validate_semver(&version).await.unwrap());
+
+    let tarball_path = format!("/mirror/{MOCK_VERSION}/{}", server.tarball());
+
+    let (zig, reused) = first.install_serialized(&rzv, false, false).await.unwrap();
+    assert!(!reused);
+    let downloads = server.hits(&tarball_path).await;
+    assert!(downloads > 0);
+
+    // The second waited on the lock, then finds the first's install instead of downloading
+    let (again, reused) = second.install_serialized(&rzv, false, false).await.unwrap();
+    assert!(reused);
+    assert_eq!(again, zig);
+    assert_eq!(server.hits(&tarball_path).await, downloads);
+}
+
+#[tokio::test]
+async fn installed_checksum_is_recorded_and_validated() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    let zig = app.install_release(false).await.unwrap();
+    let install = super::toolchain::ZigInstall {
+        version,
+        path: zig.parent().unwrap().to_path_buf(),
+        is_master: false,
+    };
+    let metadata = install.metadata().unwrap();
+    assert_eq!(metadata.archive_shasum, server.shasum());
+    assert_eq!(
+        metadata.origin,
+        Some(super::toolchain::InstallOrigin::Index)
+    );
+    assert!(app.validate_installed_checksum(&install).await.unwrap());
+
+    // An imported archive was repacked, so only index downloads are held to the index shasum
+    let write_metadata = |origin| install.write_metadata(&"0".repeat(64), origin).unwrap();
+    write_metadata(super::toolchain::InstallOrigin::Import);
+    assert!(app.validate_installed_checksum(&install).await.unwrap());
+    write_metadata(super::toolchain::InstallOrigin::Index);
+    assert!(!app.validate_installed_checksum(&install).await.unwrap());
+
+    // A version the index doesn't list is left unverified rather than passed
+    let unlisted = super::toolchain::ZigInstall {
+        version: semver::Version::new(0, 1, 0),
+        ..install.clone()
+    };
+    assert!(app.validate_installed_checksum(&unlisted).await.is_err());
+
+    // Installs from before checksums were recorded can't be checked
+    std::fs::remove_file(install.path.join("metadata.json")).unwrap();
+    assert!(app.validate_installed_checksum(&install).await.is_err());
+    install
+        .write_metadata(server.shasum(), super::toolchain::InstallOrigin::Index)
+        .unwrap();
+
+    // The active zig binary changed after activation no longer matches
+    std::fs::create_dir_all(app.bin_path()).unwrap();
+    std::fs::write(app.bin_path().join(Shim::Zv.executable_name()), b"zv").unwrap();
+    let rzv = ResolvedZigVersion::Semver(install.version.clone());
+    app.toolchain_manager
+        .set_active_version_with_path(&rzv, zig.clone())
+        .await
+        .unwrap();
+    assert!(app.validate_installed_checksum(&install).await.unwrap());
+    std::fs::write(&zig, b"tampered").unwrap();
+    assert!(!app.validate_installed_checksum(&install).await.unwrap());
+}
+
+#[tokio::test]
+async fn raw_index_is_kept_beside_the_cache_and_dumped() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let dest = temp.path().join("dumped.json");
+    assert!(app.dump_raw_index(&dest).await.is_err());
+
+    app.sync_zig_index().await.unwrap();
+    assert!(
+        app.cached_files()
+            .contains(&temp.path().join("index.json.gz"))
+    );
+    app.dump_raw_index(&dest).await.unwrap();
+    let dumped: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&dest).unwrap()).unwrap();
+    let host = crate::app::utils::host_target().unwrap();
+    assert_eq!(dumped[MOCK_VERSION][&host]["shasum"], server.shasum());
+
+    let network = app.network.as_ref().unwrap();
+    let raw = network.index_manager.raw_index().await.unwrap();
+    assert_eq!(raw, Some(dumped));
+}
+
+#[tokio::test]
+async fn fetch_zig_source_verifies_without_extracting() {
+    let server = MockZigServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut app = app_against(&server, temp.path()).await;
+
+    let version = semver::Version::parse(MOCK_VERSION).unwrap();
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    let download = super::zig_source::fetch_zig_source(&mut app, None, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        download.tarball_path,
+        temp.path().join("downloads").join(MOCK_SOURCE_TARBALL)
+    );
+    assert!(download.minisig_path.is_file());
+    assert!(!temp.path().join("sources").exists());
+
+    let out = temp.path().join("vendor");
+    app.to_install = Some(app.validate_semver(&version).await.unwrap());
+    let download = super::zig_source::fetch_zig_source(&mut app, Some(&out), false)
+        .await
+        .unwrap();
+    assert_eq!(download.tarball_path, out.join(MOCK_SOURCE_TARBALL));
+    assert!(download.tarball_path.is_file());
+    assert!(out.join(format!("{MOCK_SOURCE_TARBALL}.minisig")).is_file());
+    assert!(
+        !temp
+            .path()
+            .join("downloads")
+            .join(MOCK_SOURCE_TARBALL)
+            .exists()
+    );
+}
@@ -1,11 +1,17 @@
 use crate::app::config::ZvConfig;
 use crate::app::constants::ZV_MASTER_FILE;
+use crate::tools::canonicalize;
 use crate::{ArchiveExt, ResolvedZigVersion, Result, Shim, ZvError, app::utils::ProgressHandle};
 use color_eyre::eyre::{Context, eyre};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 const TARGET: &str = "zv::app::toolchain";
+/// Checksums of an installation, written into its directory at install time
+const INSTALL_METADATA_FILE: &str = "metadata.json";
+/// Cached result of the last scan of versions/, kept beside it so startup can skip the walk
+/// Suffixes of backups left next to a shim by manual tinkering or an interrupted update
+const SHIM_LEFTOVER_SUFFIXES: [&str; 2] = [".old", ".bak"];
 
 /// An entry representing an installed Zig version
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -18,30 +24,240 @@ pub struct ZigInstall {
     pub is_master: bool,
 }
 
+impl ZigInstall {
+    /// Whether `other` is the same Zig version, regardless of where either is installed
+    pub fn version_matches(&self, other: &ZigInstall) -> bool {
+        self.version == other.version && self.is_master == other.is_master
+    }
+
+    /// SHA-256 of this installation's zig binary, or `None` if it can't be read
+    pub fn zig_sha256(&self) -> Option<String> {
+        let zig_path = self.path.join(Shim::Zig.executable_name());
+        crate::tools::calculate_file_sha256(&zig_path)
+            .inspect_err(
+                |e| tracing::debug!(target: TARGET, "Failed to hash {}: {}", zig_path.display(), e),
+            )
+            .ok()
+    }
+
+    /// When this installation's directory was created (last modified if creation time isn't
+    /// available), to the second
+    pub fn installed_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::SubsecRound;
+
+        let metadata = std::fs::metadata(&self.path).ok()?;
+        let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+        Some(chrono::DateTime::<chrono::Utc>::from(time).trunc_subsecs(0))
+    }
+}
+
+/// Where an installed version's archive came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallOrigin {
+    /// Downloaded from a mirror or ziglang.org, as listed in the index
+    Index,
+    /// `zv import` of a local archive, which doesn't hash like the index tarball
+    Import,
+}
+
+/// Recorded in `<install>/metadata.json` when a version is installed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct InstallMetadata {
+    /// SHA-256 of the archive the version was extracted from
+    pub archive_shasum: String,
+    /// Where that archive came from; `None` in metadata written before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<InstallOrigin>,
+}
+
+impl ZigInstall {
+    /// Checksums recorded at install time; `None` for versions installed before zv recorded them
+    pub fn metadata(&self) -> Option<InstallMetadata> {
+        let path = self.path.join(INSTALL_METADATA_FILE);
+        let contents = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&contents)
+            .inspect_err(
+                |e| tracing::warn!(target: TARGET, "Ignoring unreadable {}: {}", path.display(), e),
+            )
+            .ok()
+    }
+
+    /// Record `archive_shasum` and where the archive came from in `metadata.json`
+    pub fn write_metadata(
+        &self,
+        archive_shasum: &str,
+        origin: InstallOrigin,
+    ) -> std::io::Result<()> {
+        let metadata = InstallMetadata {
+            archive_shasum: archive_shasum.to_string(),
+            origin: Some(origin),
+        };
+        let contents = serde_json::to_string_pretty(&metadata).map_err(std::io::Error::other)?;
+        std::fs::write(self.path.join(INSTALL_METADATA_FILE), contents)
+    }
+}
+
+/// Outcome of comparing the active zig binary against the hash recorded in zv.toml
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActiveZigIntegrity {
+    /// No active install, or no hash recorded for it (configs written before hashes were tracked)
+    Unrecorded,
+    /// Binary matches the recorded hash
+    Verified,
+    /// Binary differs from the recorded hash
+    Mismatch {
+        zig_path: PathBuf,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+/// Contents of `installations.toml`: the installs a scan found, and enough about the versions
+/// directory to tell whether it has changed outside zv since
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+struct InstallationsManifest {
+    /// Modification time of versions/ (nanoseconds since the epoch) when it was scanned
+    versions_mtime: Option<u64>,
+    /// Modification time of versions/master/, which new master builds land in
+    master_mtime: Option<u64>,
+    /// `local_master_zig` at scan time, which decides `is_master` for flat installs
+    local_master: Option<String>,
+    #[serde(default, rename = "install")]
+    installations: Vec<ZigInstall>,
+}
+
+impl InstallationsManifest {
+    /// The manifest `installations` would have if `versions_path` were scanned right now
+    fn current(versions_path: &Path, installations: Vec<ZigInstall>) -> Self {
+        let mtime = |dir: &Path| {
+            let modified = std::fs::metadata(dir).and_then(|m| m.modified()).ok()?;
+            let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+            u64::try_from(since_epoch.as_nanos()).ok()
+        };
+        Self {
+            versions_mtime: mtime(versions_path),
+            master_mtime: mtime(&versions_path.join("master")),
+            local_master: ToolchainManager::local_master_version(versions_path),
+            installations,
+        }
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        toml::from_str(&contents)
+            .inspect_err(
+                |e| tracing::debug!(target: TARGET, "Ignoring unreadable {}: {}", path.display(), e),
+            )
+            .ok()
+    }
+
+    /// Write the manifest. Failing to is harmless, the next startup just scans again.
+    fn save(&self, path: &Path) {
+        let written = toml::to_string_pretty(self)
+            .map_err(std::io::Error::other)
+            .and_then(|contents| std::fs::write(path, contents));
+        if let Err(e) = written {
+            tracing::debug!(target: TARGET, "Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Versions removed by a prune and the disk space they occupied
+#[derive(Debug, Clone, Default)]
+pub struct PruneSummary {
+    pub removed: Vec<semver::Version>,
+    pub freed_bytes: u64,
+}
+
+/// How shims in bin/ point at the zv binary, going by what its filesystem supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShimLink {
+    Symlink,
+    HardLink,
+    /// Neither kind of link works (exFAT, some network mounts): shims are copies of zv
+    Copy,
+}
+
+impl ShimLink {
+    /// Probe `dir` by linking a throwaway file there, best option first. A dir that can't be
+    /// written to is reported as [ShimLink::Symlink], leaving the real error to shim creation.
+    pub fn probe(dir: &Path) -> Self {
+        let target = dir.join(".zv-link-probe");
+        let link = dir.join(".zv-link-probe.link");
+        let _ = std::fs::remove_file(&link);
+        if std::fs::write(&target, b"").is_err() {
+            return Self::Symlink;
+        }
+
+        #[cfg(unix)]
+        let symlinked = std::os::unix::fs::symlink(&target, &link);
+        #[cfg(windows)]
+        let symlinked = std::os::windows::fs::symlink_file(&target, &link);
+        let supported = if symlinked.is_ok() {
+            Self::Symlink
+        } else if std::fs::hard_link(&target, &link).is_ok() {
+            Self::HardLink
+        } else {
+            Self::Copy
+        };
+
+        let _ = std::fs::remove_file(&link);
+        let _ = std::fs::remove_file(&target);
+        tracing::debug!(target: TARGET, dir = %dir.display(), ?supported, "Probed shim link support");
+        supported
+    }
+
+    /// How the `zig` shim already in `bin` was made, read without writing anything there.
+    /// `None` when there's no shim to look at.
+    pub fn detect(bin: &Path) -> Option<Self> {
+        let zig = bin.join(Shim::Zig.executable_name());
+        let metadata = std::fs::symlink_metadata(&zig).ok()?;
+        if metadata.file_type().is_symlink() {
+            Some(Self::Symlink)
+        } else if same_file::is_same_file(&zig, bin.join(Shim::Zv.executable_name()))
+            .unwrap_or(false)
+        {
+            Some(Self::HardLink)
+        } else {
+            Some(Self::Copy)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ToolchainManager {
     versions_path: PathBuf,
+    /// `installations.toml`, the manifest of what versions/ held when last scanned
+    installations_file: PathBuf,
     installations: Vec<ZigInstall>,
     active_install: Option<ZigInstall>,
     bin_path: PathBuf,
     zv_config_file: PathBuf,
     public_bin_dir: Option<PathBuf>,
+    /// Replace files in bin/ that aren't zv shims instead of refusing
+    force_shims: bool,
+    /// Probed on first use, so commands that never write shims don't touch the filesystem
+    shim_link: std::sync::OnceLock<ShimLink>,
 }
 
 impl ToolchainManager {
     pub async fn new(
-        zv_root: impl AsRef<Path>,
+        bin_path: impl AsRef<Path>,
+        versions_path: impl AsRef<Path>,
+        installations_file: impl AsRef<Path>,
         config_file: impl AsRef<Path>,
         public_bin_dir: Option<PathBuf>,
     ) -> Result<Self, ZvError> {
-        let zv_root = zv_root.as_ref().to_path_buf();
-        let versions_path = zv_root.join("versions");
-        let bin_path = zv_root.join("bin");
+        let bin_path = bin_path.as_ref().to_path_buf();
+        let versions_path = versions_path.as_ref().to_path_buf();
+        let installations_file = installations_file.as_ref().to_path_buf();
         let zv_config_file = config_file.as_ref().to_path_buf();
 
         // discover what is on disk
-        let installations =
-            Self::scan_installations(&versions_path).map_err(ZvError::ZvAppInitError)?;
+        let installations = Self::load_installations(&versions_path, &installations_file)
+            .map_err(ZvError::ZvAppInitError)?;
 
         // Helper function to find the best fallback version from installations
         let find_fallback_install = |installations: &[ZigInstall]| -> Option<ZigInstall> {
@@ -69,6 +285,8 @@ impl ToolchainManager {
                 let local_master = existing_config
                     .as_ref()
                     .and_then(|c| c.local_master_zig.clone());
+                let master_keep = existing_config.as_ref().and_then(|c| c.master_keep);
+                let mirror = existing_config.as_ref().and_then(|c| c.mirror.clone());
                 let zls = existing_config.and_then(|c| c.zls);
 
                 // Write fallback to zv.toml
@@ -78,9 +296,13 @@ impl ToolchainManager {
                         version: zi.version.to_string(),
                         path: zi.path.to_string_lossy().to_string(),
                         is_master: zi.is_master,
+                        zig_sha256: zi.zig_sha256(),
+                        external: false,
                     }),
                     local_master_zig: local_master,
                     zls,
+                    master_keep,
+                    mirror,
                 };
 
                 if let Err(e) = crate::app::config::save_zv_config(&zv_config_file, &config) {
@@ -95,7 +317,13 @@ impl ToolchainManager {
         let active_install = if zv_config_file.is_file() {
             match crate::app::config::load_zv_config(&zv_config_file) {
                 Ok(config) => {
-                    if let Some(ref active_zig) = config.active_zig {
+                    if let Some(ref active_zig) = config.active_zig
+                        && active_zig.external
+                    {
+                        // System zig lives outside versions/, so it never shows up in the scan
+                        Self::load_external_install(active_zig)
+                            .or_else(|| find_fallback_install(&installations))
+                    } else if let Some(ref active_zig) = config.active_zig {
                         // Parse version string
                         match semver::Version::parse(&active_zig.version) {
                             Ok(version) => {
@@ -164,25 +392,169 @@ impl ToolchainManager {
 
         let toolchain_manager = Self {
             versions_path,
+            installations_file,
             installations,
             active_install,
             bin_path,
             zv_config_file,
             public_bin_dir,
+            force_shims: false,
+            shim_link: std::sync::OnceLock::new(),
         };
 
         Ok(toolchain_manager)
     }
-    /// Scan installations in `versions_path` and return a sorted list of found [ZigInstall]s
-    pub(crate) fn scan_installations(versions_path: &Path) -> Result<Vec<ZigInstall>> {
-        use walkdir::WalkDir;
 
-        let mut out = Vec::new();
+    /// Let shim deployment overwrite a `zig`/`zls` in bin/ that zv didn't create
+    pub fn set_force_shims(&mut self, force: bool) {
+        self.force_shims = force;
+    }
+
+    /// How shims are created in bin/, probing its filesystem the first time this is asked
+    pub fn shim_link(&self) -> ShimLink {
+        *self
+            .shim_link
+            .get_or_init(|| ShimLink::probe(&self.bin_path))
+    }
+    /// Rebuild the [ZigInstall] for an external (system) zig recorded in zv.toml,
+    /// provided its binary is still present
+    fn load_external_install(active_zig: &crate::app::config::ActiveZig) -> Option<ZigInstall> {
+        let path = PathBuf::from(&active_zig.path);
+        if !path.join(Shim::Zig.executable_name()).is_file() {
+            tracing::debug!(target: TARGET,
+                "External zig at {} no longer exists, using fallback",
+                path.display()
+            );
+            return None;
+        }
+        match semver::Version::parse(&active_zig.version) {
+            Ok(version) => Some(ZigInstall {
+                version,
+                path,
+                is_master: false,
+            }),
+            Err(err) => {
+                tracing::debug!(target: TARGET,
+                    "Failed to parse external zig version from config: {}, using fallback",
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Locate a `zig` on PATH that isn't one of zv's shims or managed installations
+    pub fn find_system_zig(&self) -> Option<PathBuf> {
+        let zig_exe = Shim::Zig.executable_name();
+        let path_var = std::env::var_os("PATH")?;
+        let zv_bin = self.bin_path.join(Shim::Zv.executable_name());
+        // PATH entries may reach zv's directories through symlinks
+        let versions_path = canonicalize(&self.versions_path).ok();
+        let bin_path = canonicalize(&self.bin_path).ok();
+
+        std::env::split_paths(&path_var)
+            .filter(|dir| {
+                let resolved = canonicalize(dir).ok();
+                dir != &self.bin_path
+                    && self.public_bin_dir.as_ref() != Some(dir)
+                    && !dir.starts_with(&self.versions_path)
+                    && (resolved.is_none() || resolved != bin_path)
+                    && !resolved
+                        .zip(versions_path.as_ref())
+                        .is_some_and(|(dir, versions)| dir.starts_with(versions))
+            })
+            .map(|dir| dir.join(zig_exe))
+            .filter(|candidate| candidate.is_file())
+            // A symlink or hard link back to zv is just another shim
+            .find(|candidate| !same_file::is_same_file(candidate, &zv_bin).unwrap_or(false))
+    }
+
+    /// Sets an external (system) zig as active, updating the shims in bin/ and zv.toml
+    pub async fn set_active_external(
+        &mut self,
+        zig_path: &Path,
+        version: semver::Version,
+    ) -> Result<()> {
+        let install_dir = zig_path
+            .parent()
+            .ok_or_else(|| eyre!("Invalid zig path: {}", zig_path.display()))?
+            .to_path_buf();
+        let zig_install = ZigInstall {
+            version,
+            path: install_dir,
+            is_master: false,
+        };
+        tracing::debug!(target: TARGET, version = %zig_install.version, path = %zig_install.path.display(), "Setting external zig as active");
+        self.deploy_shims(&zig_install, false, false).await?;
+
+        let mut config =
+            crate::app::config::load_zv_config(&self.zv_config_file).unwrap_or(ZvConfig {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                active_zig: None,
+                local_master_zig: None,
+                zls: None,
+                master_keep: None,
+                mirror: None,
+            });
+
+        config.version = env!("CARGO_PKG_VERSION").to_string();
+        config.active_zig = Some(crate::app::config::ActiveZig {
+            version: zig_install.version.to_string(),
+            path: zig_install.path.to_string_lossy().to_string(),
+            is_master: false,
+            zig_sha256: zig_install.zig_sha256(),
+            external: true,
+        });
+
+        crate::app::config::save_zv_config(&self.zv_config_file, &config)?;
+        self.active_install = Some(zig_install);
+        Ok(())
+    }
+
+    /// Installations recorded in `installations.toml`, or a fresh [Self::scan_installations]
+    /// if versions/ has changed since the manifest was written (or there is none)
+    pub(crate) fn load_installations(
+        versions_path: &Path,
+        installations_file: &Path,
+    ) -> Result<Vec<ZigInstall>> {
         if !versions_path.is_dir() {
-            return Ok(out);
+            return Ok(Vec::new());
+        }
+        if let Some(manifest) = InstallationsManifest::load(installations_file) {
+            let current = InstallationsManifest::current(versions_path, manifest.installations);
+            if manifest.versions_mtime.is_some()
+                && current.versions_mtime == manifest.versions_mtime
+                && current.master_mtime == manifest.master_mtime
+                && current.local_master == manifest.local_master
+            {
+                tracing::trace!(target: TARGET, "Using installations from {}", installations_file.display());
+                return Ok(current.installations);
+            }
+            tracing::debug!(target: TARGET, "versions/ changed outside zv, rescanning");
         }
 
-        // Determine zv root and load local_master_zig from config or master file
+        let installations = Self::scan_installations(versions_path)?;
+        InstallationsManifest::current(versions_path, installations.clone())
+            .save(installations_file);
+        Ok(installations)
+    }
+
+    /// Scan versions/ again regardless of `installations.toml`, rewriting it.
+    /// Returns how many installations were found.
+    pub fn rescan_installations(&mut self) -> Result<usize> {
+        self.installations = Self::scan_installations(&self.versions_path)?;
+        self.save_manifest();
+        Ok(self.installations.len())
+    }
+
+    /// Record the current installations in `installations.toml` after zv changed versions/
+    fn save_manifest(&self) {
+        InstallationsManifest::current(&self.versions_path, self.installations.clone())
+            .save(&self.installations_file);
+    }
+
+    /// `local_master_zig` from zv.toml, falling back to the master file beside versions/
+    fn local_master_version(versions_path: &Path) -> Option<String> {
         let zv_root = versions_path.parent().unwrap_or(versions_path);
         let zv_config_path = zv_root.join("zv.toml");
 
@@ -194,7 +566,7 @@ impl ToolchainManager {
             };
 
         // Fallback to reading master file directly if config doesn't have it or can't be read
-        let local_master_version = local_master_version.or_else(|| {
+        local_master_version.or_else(|| {
             let master_file = zv_root.join(ZV_MASTER_FILE);
             if master_file.exists() {
                 std::fs::read_to_string(master_file)
@@ -203,7 +575,19 @@ impl ToolchainManager {
             } else {
                 None
             }
-        });
+        })
+    }
+
+    /// Scan installations in `versions_path` and return a sorted list of found [ZigInstall]s
+    pub(crate) fn scan_installations(versions_path: &Path) -> Result<Vec<ZigInstall>> {
+        use walkdir::WalkDir;
+
+        let mut out = Vec::new();
+        if !versions_path.is_dir() {
+            return Ok(out);
+        }
+
+        let local_master_version = Self::local_master_version(versions_path);
 
         let zig_exe = Shim::Zig.executable_name();
 
@@ -262,36 +646,43 @@ impl ToolchainManager {
         Ok(out)
     }
 
+    /// Directory `rzv` is installed into: `versions/master/<version>` for master builds,
+    /// `versions/<version>` otherwise
+    pub fn version_install_dir(&self, rzv: &ResolvedZigVersion) -> PathBuf {
+        self.install_dir_for(rzv.version(), rzv.is_master())
+    }
+
+    fn install_dir_for(&self, version: &semver::Version, is_master: bool) -> PathBuf {
+        if is_master {
+            self.versions_path.join("master").join(version.to_string())
+        } else {
+            self.versions_path.join(version.to_string())
+        }
+    }
+
     /// Check if a specific version is installed
     pub fn is_version_installed(&self, rzv: &ResolvedZigVersion) -> Option<PathBuf> {
         let version = rzv.version();
         if rzv.is_master() {
-            let base = self.versions_path.join("master").join(version.to_string());
-            if !base.is_dir() {
-                return None;
-            }
-            let zig = base.join(Shim::Zig.executable_name());
+            // The same master build may already be on disk under versions/master/<version>, or under
+            // versions/<version> if an older layout was flattened; either way it can be reused
+            let zig = self
+                .install_dir_for(version, true)
+                .join(Shim::Zig.executable_name());
             if zig.is_file() {
                 return Some(zig);
             }
-            // For older masters that've been moved into `versions/<semver>` following the oncoming changes:
-            let alt_base = self.versions_path.join(version.to_string());
-            let alt_zig = alt_base.join(Shim::Zig.executable_name());
-            if alt_zig.is_file() {
-                return Some(alt_zig);
-            }
         }
         // Else fallback to checking versions/<semver>
         let zig = self
-            .versions_path
-            .join(version.to_string())
+            .install_dir_for(version, false)
             .join(Shim::Zig.executable_name());
         if zig.is_file() {
             Some(zig)
         } else {
             // Check master dir for pre-releases semver which might not trigger is_master():
             if !version.pre.is_empty() {
-                let alt_base = self.versions_path.join("master").join(version.to_string());
+                let alt_base = self.install_dir_for(version, true);
                 let alt_zig = alt_base.join(Shim::Zig.executable_name());
                 if alt_zig.is_file() {
                     return Some(alt_zig);
@@ -301,6 +692,74 @@ impl ToolchainManager {
         }
     }
 
+    /// Package an installed version into a portable archive at `output`: tar.xz on Unix, zip on
+    /// Windows. Everything sits under a `zig-<arch>-<os>-<version>/` directory, as in the
+    /// official release archives, so the result can be served from an internal mirror.
+    pub async fn export_toolchain(
+        &self,
+        version: &ResolvedZigVersion,
+        output: &Path,
+    ) -> Result<()> {
+        let install_dir = self
+            .is_version_installed(version)
+            .and_then(|zig| zig.parent().map(Path::to_path_buf))
+            .ok_or_else(|| ZvError::ZigNotFound(eyre!("Zig {} is not installed", version)))?;
+        let ext = if cfg!(windows) {
+            ArchiveExt::Zip
+        } else {
+            ArchiveExt::TarXz
+        };
+        let root_name = crate::app::utils::zig_tarball(version.version(), Some(ext))
+            .and_then(|name| name.strip_suffix(&format!(".{ext}")).map(str::to_string))
+            .ok_or_else(|| eyre!("Unsupported host platform for exporting Zig {}", version))?;
+        tracing::debug!(target: TARGET, install = %install_dir.display(), output = %output.display(), "Exporting toolchain");
+
+        let output_path = output.to_path_buf();
+        let written = tokio::task::spawn_blocking(move || match ext {
+            ArchiveExt::TarXz => write_tar_xz(&install_dir, &root_name, &output_path),
+            ArchiveExt::Zip => write_zip(&install_dir, &root_name, &output_path),
+            ArchiveExt::TarGz => unreachable!("exports are written as tar.xz or zip"),
+        })
+        .await
+        .wrap_err("Export task did not complete")?;
+        if let Err(e) = written {
+            let _ = fs::remove_file(output).await;
+            return Err(e.wrap_err(format!("Failed to write {}", output.display())));
+        }
+        Ok(())
+    }
+
+    /// Install a portable archive, e.g. one written by [Self::export_toolchain], as `version`.
+    /// The archive carries no version metadata of its own, so the caller supplies it. The
+    /// format is taken from the file name (`.tar.xz`, `.tar.gz` or `.zip`).
+    pub async fn import_toolchain(
+        &mut self,
+        archive: &Path,
+        version: &semver::Version,
+        is_master: bool,
+    ) -> Result<PathBuf> {
+        let name = archive
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some(ext) = ArchiveExt::from_file_name(&name) else {
+            return Err(eyre!(
+                "Unsupported archive {}: expected a .tar.xz, .tar.gz or .zip",
+                archive.display()
+            ));
+        };
+        if !archive.is_file() {
+            return Err(ZvError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} does not exist", archive.display()),
+            ))
+            .into());
+        }
+        tracing::debug!(target: TARGET, archive = %archive.display(), %version, is_master, "Importing toolchain");
+        self.install_version(archive, version, ext, is_master, InstallOrigin::Import)
+            .await
+    }
+
     /// Install a Zig version from a downloaded archive
     pub async fn install_version(
         &mut self,
@@ -308,22 +767,39 @@ impl ToolchainManager {
         version: &semver::Version,
         ext: ArchiveExt,
         is_master: bool,
+        origin: InstallOrigin,
     ) -> Result<PathBuf> {
         const TARGET: &str = "zv::toolchain";
 
-        let install_destination = if is_master {
-            self.versions_path.join("master").join(version.to_string())
-        } else {
-            self.versions_path.join(version.to_string())
-        };
+        let install_destination = self.install_dir_for(version, is_master);
         tracing::debug!(target: TARGET, %version, is_master, dest = %install_destination.display(), "Installation destination");
 
+        // An earlier interrupted install may have left the destination without a zig binary
+        if install_destination.is_dir()
+            && !install_destination
+                .join(Shim::Zig.executable_name())
+                .is_file()
+        {
+            let from = std::fs::metadata(&install_destination)
+                .and_then(|m| m.modified())
+                .map(|t| {
+                    chrono::DateTime::<chrono::Local>::from(t)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| "an unknown time".to_string());
+            crate::tools::warn(format!(
+                "Found incomplete installation of {version} from {from}, replacing it"
+            ));
+        }
+
         let archive_tmp = self.versions_path.join("archive_tmp");
         if archive_tmp.exists() {
             fs::remove_dir_all(&archive_tmp).await?;
         }
         fs::create_dir_all(&archive_tmp).await?;
         let progress_handle = ProgressHandle::spawn();
+        let archive_shasum = crate::app::utils::sha256_hex(archive_path).await?;
         let bytes = fs::read(archive_path).await?;
         let archive_name = archive_path
             .file_name()
@@ -331,15 +807,56 @@ impl ToolchainManager {
             .unwrap_or_else(|| "zig archive".to_string());
         // extract archive
         match ext {
-            ArchiveExt::TarXz => {
+            ArchiveExt::TarXz | ArchiveExt::TarGz => {
                 let _ = progress_handle
                     .start(format!("Extracting {archive_name}"))
                     .await;
-                let xz = xz2::read::XzDecoder::new(std::io::Cursor::new(bytes));
-                let mut ar = tar::Archive::new(xz);
-                if let Err(e) = ar.unpack(&archive_tmp) {
+                let bytes = std::io::Cursor::new(bytes);
+                let decoder: Box<dyn std::io::Read> = if ext == ArchiveExt::TarGz {
+                    Box::new(flate2::read::GzDecoder::new(bytes))
+                } else {
+                    Box::new(xz2::read::XzDecoder::new(bytes))
+                };
+                let mut ar = tar::Archive::new(decoder);
+                let entries = match ar.entries() {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        let _ = progress_handle
+                            .finish_with_error(format!("Failed to extract {ext} archive"))
+                            .await;
+                        return Err(e.into());
+                    }
+                };
+                // Directories are unpacked last, deepest first, as `Archive::unpack` does, so a
+                // read-only directory only gets its permissions once its contents are in place
+                let mut directories = Vec::new();
+                for entry in entries {
+                    let file = match entry.and_then(|mut entry| {
+                        let file = entry.path()?.to_string_lossy().to_string();
+                        if entry.header().entry_type() == tar::EntryType::Directory {
+                            directories.push(entry);
+                        } else {
+                            entry.unpack_in(&archive_tmp)?;
+                        }
+                        Ok(file)
+                    }) {
+                        Ok(file) => file,
+                        Err(e) => {
+                            let _ = progress_handle
+                                .finish_with_error(format!("Failed to extract {ext} archive"))
+                                .await;
+                            return Err(e.into());
+                        }
+                    };
+                    let _ = progress_handle.extract(file).await;
+                }
+                directories.sort_by(|a, b| b.path_bytes().cmp(&a.path_bytes()));
+                if let Err(e) = directories
+                    .iter_mut()
+                    .try_for_each(|dir| dir.unpack_in(&archive_tmp).map(drop))
+                {
                     let _ = progress_handle
-                        .finish_with_error("Failed to extract tar.xz archive")
+                        .finish_with_error(format!("Failed to extract {ext} archive"))
                         .await;
                     return Err(e.into());
                 }
@@ -367,6 +884,7 @@ impl ToolchainManager {
                             return Err(e.into());
                         }
                     };
+                    let _ = progress_handle.extract(file.name()).await;
                     let out = archive_tmp.join(file.name());
                     if file.is_dir() {
                         if let Err(e) = fs::create_dir_all(&out).await {
@@ -426,26 +944,25 @@ impl ToolchainManager {
             return Err(eyre!("Zig executable not found after installation"));
         }
 
+        // stage the complete install next to the destination so that an interruption
+        // never leaves the canonical version directory half-written
+        let staging = self.versions_path.join(format!(".staging-{version}"));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).await?;
+        }
+        fs::rename(&actual_root, &staging).await?;
+        if actual_root != archive_tmp {
+            fs::remove_dir_all(&archive_tmp).await.ok();
+        }
+
         // promote to final location
         if install_destination.exists() {
             fs::remove_dir_all(&install_destination).await?;
         }
-
-        // Move contents of actual_root, not the directory itself
-        if actual_root != archive_tmp {
-            // We have a wrapper directory - move its contents to the install destination
-            fs::create_dir_all(&install_destination).await?;
-            let mut entries = fs::read_dir(&actual_root).await?;
-            while let Some(entry) = entries.next_entry().await? {
-                let src = entry.path();
-                let dst = install_destination.join(entry.file_name());
-                fs::rename(&src, &dst).await?;
-            }
-            fs::remove_dir_all(&archive_tmp).await.ok();
-        } else {
-            // Already flat - move the entire directory
-            fs::rename(&archive_tmp, &install_destination).await?;
+        if let Some(parent) = install_destination.parent() {
+            fs::create_dir_all(parent).await?;
         }
+        fs::rename(&staging, &install_destination).await?;
 
         // update cache
         let new_install = ZigInstall {
@@ -454,6 +971,10 @@ impl ToolchainManager {
             is_master,
         };
 
+        if let Err(e) = new_install.write_metadata(&archive_shasum, origin) {
+            tracing::warn!(target: TARGET, "Failed to record install checksums: {}", e);
+        }
+
         // Update local_master_zig if this is a master version
         if is_master {
             if let Ok(mut config) = crate::app::config::load_zv_config(&self.zv_config_file) {
@@ -468,6 +989,8 @@ impl ToolchainManager {
                     active_zig: None,
                     local_master_zig: Some(version.to_string()),
                     zls: None,
+                    master_keep: None,
+                    mirror: None,
                 };
                 if let Err(e) = crate::app::config::save_zv_config(&self.zv_config_file, &config) {
                     tracing::error!(target: TARGET, "Failed to create config with local_master_zig: {}", e);
@@ -483,6 +1006,7 @@ impl ToolchainManager {
             Ok(pos) => self.installations[pos] = new_install,
             Err(pos) => self.installations.insert(pos, new_install),
         }
+        self.save_manifest();
 
         Ok(exe_path)
     }
@@ -507,6 +1031,8 @@ impl ToolchainManager {
                 active_zig: None,
                 local_master_zig: None,
                 zls: None,
+                master_keep: None,
+                mirror: None,
             });
 
         config.version = env!("CARGO_PKG_VERSION").to_string();
@@ -514,6 +1040,8 @@ impl ToolchainManager {
             version: install.version.to_string(),
             path: install.path.to_string_lossy().to_string(),
             is_master: install.is_master,
+            zig_sha256: install.zig_sha256(),
+            external: false,
         });
 
         crate::app::config::save_zv_config(&self.zv_config_file, &config)?;
@@ -551,6 +1079,8 @@ impl ToolchainManager {
                 active_zig: None,
                 local_master_zig: None,
                 zls: None,
+                master_keep: None,
+                mirror: None,
             });
 
         config.version = env!("CARGO_PKG_VERSION").to_string();
@@ -558,6 +1088,8 @@ impl ToolchainManager {
             version: zig_install.version.to_string(),
             path: zig_install.path.to_string_lossy().to_string(),
             is_master: zig_install.is_master,
+            zig_sha256: zig_install.zig_sha256(),
+            external: false,
         });
 
         crate::app::config::save_zv_config(&self.zv_config_file, &config)?;
@@ -567,7 +1099,7 @@ impl ToolchainManager {
     }
     /// Validates that the zv binary exists in the bin directory
     /// Similar to setup logic - checks existence and warns about checksum mismatches but continues
-    fn validate_zv_binary(&self) -> Result<PathBuf> {
+    async fn validate_zv_binary(&self) -> Result<PathBuf> {
         use crate::tools::files_have_same_hash;
 
         let zv_bin_path = self.bin_path.join(Shim::Zv.executable_name());
@@ -592,7 +1124,7 @@ impl ToolchainManager {
             std::env::current_exe().wrap_err("Failed to get current executable path")?;
 
         // Compare checksums like setup does
-        match files_have_same_hash(&current_exe, &zv_bin_path) {
+        match files_have_same_hash(&current_exe, &zv_bin_path).await {
             Ok(true) => {
                 tracing::debug!(target: TARGET, zv_path = %zv_bin_path.display(), "Validated zv binary (checksum match)");
             }
@@ -623,7 +1155,7 @@ impl ToolchainManager {
     ) -> Result<()> {
         let zv_path = if !skip_zv_bin_check {
             // Validate that zv binary exists
-            self.validate_zv_binary()?
+            self.validate_zv_binary().await?
         } else {
             self.bin_path.join(Shim::Zv.executable_name())
         };
@@ -632,6 +1164,9 @@ impl ToolchainManager {
 
         self.create_shim(&zv_path, Shim::Zig).await?;
         self.create_shim(&zv_path, Shim::Zls).await?;
+        for leftover in self.remove_shim_leftovers(&zv_path) {
+            tracing::info!(target: TARGET, "Removed leftover {}", leftover.display());
+        }
 
         if let Some(ref pub_dir) = self.public_bin_dir {
             self.create_public_shims(&zv_path, pub_dir).await?;
@@ -643,7 +1178,7 @@ impl ToolchainManager {
         Ok(())
     }
 
-    /// Creates a single shim (hard link or symlink) to the zv binary
+    /// Creates a single shim (symlink, hard link or copy) to the zv binary
     async fn create_shim(&self, zv_path: &Path, shim: Shim) -> Result<()> {
         let shim_path = self.bin_path.join(shim.executable_name());
 
@@ -660,9 +1195,20 @@ impl ToolchainManager {
             return Ok(());
         }
 
-        // Remove existing file/symlink if it exists
+        // Remove existing file/symlink if it exists, unless it's someone else's binary
         if shim_path.exists() || shim_path.is_symlink() {
+            if !self.force_shims && !self.is_replaceable_shim(&shim_path, zv_path) {
+                return Err(ZvError::ShimConflict {
+                    path: shim_path,
+                    context: None,
+                }
+                .into());
+            }
             fs::remove_file(&shim_path).await?;
+            tracing::info!(target: TARGET,
+                "Replaced {} shim that no longer pointed at zv",
+                shim.executable_name()
+            );
         }
 
         tracing::info!(target: TARGET,
@@ -672,28 +1218,33 @@ impl ToolchainManager {
             zv_path.display()
         );
 
-        #[cfg(unix)]
-        tokio::fs::symlink(zv_path, &shim_path).await?;
-
-        #[cfg(windows)]
-        {
-            match tokio::fs::symlink_file(zv_path, &shim_path).await {
-                Ok(()) => {
-                    tracing::debug!(target: TARGET, "Created symlink successfully for {}", shim.executable_name());
-                }
-                Err(symlink_err) => {
-                    tracing::debug!(target: TARGET, "Symlink failed for {}: {}, trying hard link", shim.executable_name(), symlink_err);
-                    std::fs::hard_link(zv_path, &shim_path).wrap_err_with(|| {
-                        format!(
-                            "Failed to create hard link from {} to {}",
-                            zv_path.display(),
-                            shim_path.display()
-                        )
-                    })?;
-                    tracing::debug!(target: TARGET, "Created hard link successfully for {}", shim.executable_name());
-                }
+        match self.shim_link() {
+            ShimLink::Symlink => {
+                #[cfg(unix)]
+                tokio::fs::symlink(zv_path, &shim_path).await?;
+                #[cfg(windows)]
+                tokio::fs::symlink_file(zv_path, &shim_path).await?;
+            }
+            ShimLink::HardLink => {
+                std::fs::hard_link(zv_path, &shim_path).wrap_err_with(|| {
+                    format!(
+                        "Failed to create hard link from {} to {}",
+                        zv_path.display(),
+                        shim_path.display()
+                    )
+                })?;
+            }
+            ShimLink::Copy => {
+                fs::copy(zv_path, &shim_path).await.wrap_err_with(|| {
+                    format!(
+                        "Failed to copy {} to {}",
+                        zv_path.display(),
+                        shim_path.display()
+                    )
+                })?;
             }
         }
+        tracing::debug!(target: TARGET, link = ?self.shim_link(), "Created shim {}", shim.executable_name());
 
         Ok(())
     }
@@ -792,30 +1343,116 @@ impl ToolchainManager {
             return Ok(true);
         }
 
-        // Check for symlinks
-        if shim_path.is_symlink()
-            && let Ok(target) = std::fs::read_link(shim_path)
+        // Check for symlinks, either on the file itself or on any directory leading to it
+        if let Ok(resolved_target) = canonicalize(shim_path)
+            && let Ok(target_handle) = Handle::from_path(&resolved_target)
+            && target_handle == zv_handle
         {
-            let resolved_target = if target.is_absolute() {
-                target
-            } else {
-                shim_path.parent().unwrap_or(shim_path).join(&target)
-            };
+            return Ok(true);
+        }
 
-            if let Ok(target_handle) = Handle::from_path(&resolved_target)
-                && target_handle == zv_handle
-            {
-                return Ok(true);
+        Ok(false)
+    }
+
+    /// Whether an existing `shim_path` that isn't a valid shim is still zv's to replace: a dangling
+    /// symlink, a symlink to some zv executable, or a copy of `zv_path`
+    fn is_stale_shim(shim_path: &Path, zv_path: &Path) -> bool {
+        let Ok(resolved) = canonicalize(shim_path) else {
+            return true;
+        };
+        if resolved
+            .file_name()
+            .is_some_and(|name| name == Shim::Zv.executable_name())
+        {
+            return true;
+        }
+        let sha256 = |path: &Path| crate::tools::calculate_file_sha256(path).ok();
+        sha256(&resolved).is_some_and(|shim_sha| sha256(zv_path) == Some(shim_sha))
+    }
+
+    /// Whether zv may replace the existing zig/zls at `shim_path`. Where shims are copies, a
+    /// regular file there is one of them even when it's a copy of an older zv (e.g. after
+    /// `zv update` replaced bin/zv), so it no longer hashes like `zv_path`.
+    fn is_replaceable_shim(&self, shim_path: &Path, zv_path: &Path) -> bool {
+        let is_regular_file = shim_path
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.is_file());
+        (self.shim_link() == ShimLink::Copy && is_regular_file)
+            || Self::is_stale_shim(shim_path, zv_path)
+    }
+
+    /// Remove `zig.old`, `zls.bak` and similar backups of zv and its shims from bin/, returning
+    /// the paths removed. Only files [Self::remove_shims] would take as zv's are removed, unless
+    /// shims are forced.
+    fn remove_shim_leftovers(&self, zv_path: &Path) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        for shim in [Shim::Zv, Shim::Zig, Shim::Zls] {
+            for suffix in SHIM_LEFTOVER_SUFFIXES {
+                let leftover = self
+                    .bin_path
+                    .join(format!("{}{suffix}", shim.executable_name()));
+                if leftover.symlink_metadata().is_err() {
+                    continue;
+                }
+                let ours = self.force_shims || self.is_replaceable_shim(&leftover, zv_path);
+                if ours && std::fs::remove_file(&leftover).is_ok() {
+                    removed.push(leftover);
+                }
             }
         }
+        removed
+    }
 
-        Ok(false)
+    /// Remove zv and its zig/zls shims from the bin dir, leaving any other file there alone.
+    /// Returns how many were removed.
+    pub fn remove_shims(&self) -> usize {
+        let zv_path = self.bin_path.join(Shim::Zv.executable_name());
+        let mut removed = 0;
+        for shim in [Shim::Zig, Shim::Zls] {
+            let shim_path = self.bin_path.join(shim.executable_name());
+            if shim_path.symlink_metadata().is_ok()
+                && self.is_replaceable_shim(&shim_path, &zv_path)
+                && std::fs::remove_file(&shim_path).is_ok()
+            {
+                removed += 1;
+            }
+        }
+        if std::fs::remove_file(&zv_path).is_ok() {
+            removed += 1;
+        }
+        removed
     }
 
     /// Get the currently active installation, if any
     pub fn get_active_install(&self) -> Option<&ZigInstall> {
         self.active_install.as_ref()
     }
+
+    /// Compare the active zig binary against the SHA-256 recorded in zv.toml at activation time
+    pub fn verify_active_zig(&self) -> ActiveZigIntegrity {
+        let Some(install) = self.active_install.as_ref() else {
+            return ActiveZigIntegrity::Unrecorded;
+        };
+        let expected = crate::app::config::load_zv_config(&self.zv_config_file)
+            .ok()
+            .and_then(|c| c.active_zig)
+            .filter(|a| a.version == install.version.to_string())
+            .and_then(|a| a.zig_sha256);
+        let Some(expected) = expected else {
+            return ActiveZigIntegrity::Unrecorded;
+        };
+
+        let actual = install.zig_sha256();
+        if actual.as_deref() == Some(expected.as_str()) {
+            ActiveZigIntegrity::Verified
+        } else {
+            ActiveZigIntegrity::Mismatch {
+                zig_path: install.path.join(Shim::Zig.executable_name()),
+                expected,
+                actual,
+            }
+        }
+    }
     /// List all installed versions, returning a tuple of (version, is_active, is_master)
     pub fn list_installations(&self) -> Vec<(semver::Version, bool, bool)> {
         self.installations
@@ -830,6 +1467,11 @@ impl ToolchainManager {
             .collect()
     }
 
+    /// All installations found under the versions directory, sorted
+    pub fn installations(&self) -> &[ZigInstall] {
+        &self.installations
+    }
+
     /// Check if there are any installed versions
     /// Returns `true`` if no installations are available, `false` otherwise.
     pub fn installations_empty(&self) -> bool {
@@ -844,6 +1486,8 @@ impl ToolchainManager {
                 active_zig: None,
                 local_master_zig: config.local_master_zig,
                 zls: config.zls,
+                master_keep: config.master_keep,
+                mirror: config.mirror,
             };
 
             if let Err(e) =
@@ -861,6 +1505,8 @@ impl ToolchainManager {
                 active_zig: None,
                 local_master_zig: None,
                 zls: None,
+                master_keep: None,
+                mirror: None,
             };
 
             if let Err(e) = crate::app::config::save_zv_config(&self.zv_config_file, &config) {
@@ -895,13 +1541,43 @@ impl ToolchainManager {
         if let Some(pos) = self.installations.iter().position(|i| i == install) {
             self.installations.remove(pos);
         }
+        self.save_manifest();
 
         Ok(())
     }
 
+    /// Delete all but the newest `keep` master builds. The active install is never removed.
+    pub async fn prune_master_installs(&mut self, keep: usize) -> PruneSummary {
+        let mut masters: Vec<ZigInstall> = self
+            .installations
+            .iter()
+            .filter(|i| i.is_master)
+            .cloned()
+            .collect();
+        masters.sort_by(|a, b| b.version.cmp(&a.version));
+
+        let mut summary = PruneSummary::default();
+        for install in masters.into_iter().skip(keep) {
+            if self.active_install.as_ref() == Some(&install) {
+                tracing::debug!(target: TARGET, version = %install.version, "Skipping prune of active master");
+                continue;
+            }
+            let size = crate::tools::dir_size(&install.path);
+            match self.delete_install(&install).await {
+                Ok(()) => {
+                    summary.removed.push(install.version);
+                    summary.freed_bytes += size;
+                }
+                Err(e) => {
+                    tracing::warn!(target: TARGET, "Failed to prune master/{}: {}", install.version, e);
+                }
+            }
+        }
+        summary
+    }
+
     /// Clean the downloads cache directory
-    pub async fn clean_downloads_cache(&self) -> Result<()> {
-        let downloads_path = self.versions_path.parent().unwrap().join("downloads"); // version_path is <root>/versions
+    pub async fn clean_downloads_cache(&self, downloads_path: &Path) -> Result<()> {
         tracing::debug!(target: TARGET, path = %downloads_path.display(), "Cleaning downloads directory");
 
         if !downloads_path.exists() {
@@ -917,6 +1593,38 @@ impl ToolchainManager {
         Ok(())
     }
 
+    /// Remove cached downloads last modified more than `max_age` ago, including stale leftovers
+    /// in `tmp/`. A download in progress keeps a fresh mtime, so it is never touched.
+    /// Returns the number of files removed and the bytes they occupied.
+    pub async fn clean_downloads_older_than(
+        &self,
+        downloads_path: &Path,
+        max_age: std::time::Duration,
+    ) -> Result<(usize, u64)> {
+        tracing::debug!(target: TARGET, path = %downloads_path.display(), ?max_age, "Trimming downloads directory");
+
+        let Some(cutoff) = std::time::SystemTime::now().checked_sub(max_age) else {
+            return Ok((0, 0));
+        };
+        let (mut removed, mut freed_bytes) = (0, 0);
+        for dir in [downloads_path.to_path_buf(), downloads_path.join("tmp")] {
+            let Ok(mut entries) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Some(entry) = entries.next_entry().await.map_err(ZvError::Io)? {
+                let metadata = entry.metadata().await.map_err(ZvError::Io)?;
+                if !metadata.is_file() || !metadata.modified().is_ok_and(|m| m < cutoff) {
+                    continue;
+                }
+                fs::remove_file(entry.path()).await.map_err(ZvError::Io)?;
+                tracing::debug!(target: TARGET, file = %entry.path().display(), "Removed old download");
+                removed += 1;
+                freed_bytes += metadata.len();
+            }
+        }
+        Ok((removed, freed_bytes))
+    }
+
     /// Delete all installed versions
     pub async fn delete_all_versions(&mut self) -> Result<()> {
         tracing::debug!(target: TARGET, "Deleting all versions");
@@ -932,6 +1640,7 @@ impl ToolchainManager {
 
         self.installations.clear();
         self.active_install = None;
+        self.save_manifest();
 
         // Also clear config? Or just let verify/init handle it?
         // Ideally we should clear active_zig from config.
@@ -958,3 +1667,504 @@ impl ToolchainManager {
         }
     }
 }
+
+/// Write `dir` into a tar.xz at `output`, with its contents under `root_name/`
+fn write_tar_xz(dir: &Path, root_name: &str, output: &Path) -> Result<()> {
+    let file = std::fs::File::create(output)?;
+    let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(file, 6));
+    // Keep symlinks in the toolchain (e.g. lib/ aliases) as links rather than copies
+    builder.follow_symlinks(false);
+    builder.append_dir_all(root_name, dir)?;
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Write `dir` into a zip at `output`, with its contents under `root_name/`
+fn write_zip(dir: &Path, root_name: &str, output: &Path) -> Result<()> {
+    use zip::write::SimpleFileOptions;
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for entry in walkdir::WalkDir::new(dir).min_depth(1) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(dir)?;
+        let name = std::iter::once(root_name.to_string())
+            .chain(relative.iter().map(|c| c.to_string_lossy().into_owned()))
+            .collect::<Vec<_>>()
+            .join("/");
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options)?;
+        } else {
+            zip.start_file(name, options)?;
+            std::io::copy(&mut std::fs::File::open(entry.path())?, &mut zip)?;
+        }
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+/// A manager over `root/bin`, `root/versions` and `root/zv.toml`, with an empty zig binary for
+/// each of `installs` (a directory under versions/, e.g. `0.14.0` or `master/0.16.0-dev.1+abc`)
+#[cfg(test)]
+pub(crate) async fn manager_with_installs(root: &Path, installs: &[&str]) -> ToolchainManager {
+    let versions = root.join("versions");
+    for install in installs {
+        let dir = versions.join(install);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(Shim::Zig.executable_name()), "").unwrap();
+    }
+    ToolchainManager::new(
+        root.join("bin"),
+        &versions,
+        root.join("installations.toml"),
+        root.join("zv.toml"),
+        None,
+    )
+    .await
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[tokio::test]
+    async fn deploy_shims_refuses_to_replace_foreign_binaries() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut manager = manager_with_installs(temp.path(), &[]).await;
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join(Shim::Zv.executable_name()), "zv").unwrap();
+        // A copy of zv is a stale shim; a real zig is the user's
+        std::fs::write(bin.join(Shim::Zls.executable_name()), "zv").unwrap();
+        let user_zig = bin.join(Shim::Zig.executable_name());
+        std::fs::write(&user_zig, "real zig").unwrap();
+        let install = ZigInstall {
+            version: semver::Version::new(0, 15, 1),
+            path: temp.path().join("versions/0.15.1"),
+            is_master: false,
+        };
+
+        let err = manager
+            .deploy_shims(&install, true, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ZvError>(),
+            Some(ZvError::ShimConflict { path, .. }) if *path == user_zig
+        ));
+        assert_eq!(std::fs::read_to_string(&user_zig).unwrap(), "real zig");
+
+        manager.set_force_shims(true);
+        manager.deploy_shims(&install, true, true).await.unwrap();
+        for shim in [Shim::Zig, Shim::Zls] {
+            assert!(bin.join(shim.executable_name()).is_symlink());
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn deploy_shims_replaces_broken_shims_and_removes_leftovers() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        std::fs::write(bin.join(Shim::Zv.executable_name()), "zv").unwrap();
+        let zig = bin.join(Shim::Zig.executable_name());
+        std::os::unix::fs::symlink(temp.path().join("gone"), &zig).unwrap();
+        let leftover = |name: &str, contents: &str| {
+            let path = bin.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+        let zv_old = leftover("zv.old", "zv");
+        let user_zv = leftover("zv.bak", "some other zv");
+        let zig_bak = leftover("zig.bak", "zv");
+        let user_zls = leftover("zls.old", "the user's zls");
+        let install = ZigInstall {
+            version: semver::Version::new(0, 15, 1),
+            path: temp.path().join("versions/0.15.1"),
+            is_master: false,
+        };
+
+        manager.deploy_shims(&install, true, true).await.unwrap();
+        assert_eq!(
+            canonicalize(&zig).unwrap(),
+            canonicalize(bin.join(Shim::Zv.executable_name())).unwrap()
+        );
+        assert!(!zv_old.exists());
+        assert!(!zig_bak.exists());
+        assert!(user_zv.exists());
+        assert!(user_zls.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shims_are_copies_where_links_are_unsupported() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        assert_eq!(ShimLink::probe(&bin), ShimLink::Symlink);
+        assert_eq!(std::fs::read_dir(&bin).unwrap().count(), 0);
+        assert_eq!(ShimLink::detect(&bin), None);
+
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        manager.shim_link.set(ShimLink::Copy).unwrap();
+        std::fs::write(bin.join(Shim::Zv.executable_name()), "zv").unwrap();
+        let install = ZigInstall {
+            version: semver::Version::new(0, 15, 1),
+            path: temp.path().join("versions/0.15.1"),
+            is_master: false,
+        };
+
+        manager.deploy_shims(&install, true, true).await.unwrap();
+        // Redeploying replaces the copies rather than treating them as foreign binaries
+        manager.deploy_shims(&install, true, true).await.unwrap();
+        for shim in [Shim::Zig, Shim::Zls] {
+            let shim_path = bin.join(shim.executable_name());
+            assert!(!shim_path.is_symlink());
+            assert_eq!(std::fs::read_to_string(shim_path).unwrap(), "zv");
+        }
+
+        // Copies of the zv that `zv update` replaced are still shims, not foreign binaries
+        std::fs::write(bin.join(Shim::Zv.executable_name()), "updated zv").unwrap();
+        manager.deploy_shims(&install, true, true).await.unwrap();
+        for shim in [Shim::Zig, Shim::Zls] {
+            let shim_path = bin.join(shim.executable_name());
+            assert_eq!(std::fs::read_to_string(shim_path).unwrap(), "updated zv");
+        }
+
+        // Detection reads the shims that are there, whichever way they were made
+        let (zig, zv) = (
+            bin.join(Shim::Zig.executable_name()),
+            bin.join(Shim::Zv.executable_name()),
+        );
+        assert_eq!(ShimLink::detect(&bin), Some(ShimLink::Copy));
+        std::fs::remove_file(&zig).unwrap();
+        std::fs::hard_link(&zv, &zig).unwrap();
+        assert_eq!(ShimLink::detect(&bin), Some(ShimLink::HardLink));
+        std::fs::remove_file(&zig).unwrap();
+        std::os::unix::fs::symlink(&zv, &zig).unwrap();
+        assert_eq!(ShimLink::detect(&bin), Some(ShimLink::Symlink));
+    }
+
+    #[tokio::test]
+    async fn version_install_dir_separates_master_builds() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        let stable = semver::Version::new(0, 15, 1);
+        let master = semver::Version::parse("0.16.0-dev.1+abc").unwrap();
+
+        assert_eq!(
+            manager.version_install_dir(&ResolvedZigVersion::Semver(stable)),
+            versions.join("0.15.1")
+        );
+        assert_eq!(
+            manager.version_install_dir(&ResolvedZigVersion::Master(master)),
+            versions.join("master").join("0.16.0-dev.1+abc")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn export_toolchain_packs_install_under_release_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let manager = manager_with_installs(temp.path(), &["0.15.1"]).await;
+        std::fs::create_dir_all(versions.join("0.15.1/lib/std")).unwrap();
+        std::fs::write(versions.join("0.15.1/lib/std/std.zig"), "pub const x = 1;").unwrap();
+        let version = ResolvedZigVersion::Semver(semver::Version::new(0, 15, 1));
+        let output = temp.path().join("export.tar.xz");
+
+        manager.export_toolchain(&version, &output).await.unwrap();
+
+        let root = crate::app::utils::zig_tarball(version.version(), Some(ArchiveExt::TarXz))
+            .unwrap()
+            .trim_end_matches(".tar.xz")
+            .to_string();
+        let file = std::fs::File::open(&output).unwrap();
+        let mut archive = tar::Archive::new(xz2::read::XzDecoder::new(file));
+        let entries: Vec<PathBuf> = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().into_owned())
+            .collect();
+        assert!(entries.contains(&PathBuf::from(&root).join("zig")));
+        assert!(entries.contains(&PathBuf::from(&root).join("lib/std/std.zig")));
+
+        let missing = ResolvedZigVersion::Semver(semver::Version::new(0, 9, 0));
+        assert!(
+            manager
+                .export_toolchain(&missing, &temp.path().join("missing.tar.xz"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn imported_export_is_registered_as_given_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let mut manager = manager_with_installs(temp.path(), &["0.15.1"]).await;
+        std::fs::create_dir_all(versions.join("0.15.1/lib/std")).unwrap();
+        std::fs::write(versions.join("0.15.1/lib/std/std.zig"), "pub const x = 1;").unwrap();
+        let archive = temp.path().join("portable.tar.xz");
+        manager
+            .export_toolchain(
+                &ResolvedZigVersion::Semver(semver::Version::new(0, 15, 1)),
+                &archive,
+            )
+            .await
+            .unwrap();
+
+        let master = semver::Version::parse("0.16.0-dev.42+abc").unwrap();
+        let zig = manager
+            .import_toolchain(&archive, &master, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            zig,
+            versions
+                .join("master/0.16.0-dev.42+abc")
+                .join(Shim::Zig.executable_name())
+        );
+        assert!(zig.with_file_name("lib/std/std.zig").is_file());
+        assert!(
+            manager
+                .list_installations()
+                .iter()
+                .any(|(v, _, is_master)| *v == master && *is_master)
+        );
+
+        // An archive without a zig binary is not a toolchain
+        let bogus = temp.path().join("bogus.tar.xz");
+        let empty = temp.path().join("empty");
+        std::fs::create_dir_all(empty.join("lib")).unwrap();
+        write_tar_xz(&empty, "zig-bogus", &bogus).unwrap();
+        let version = semver::Version::new(0, 9, 0);
+        assert!(
+            manager
+                .import_toolchain(&bogus, &version, false)
+                .await
+                .is_err()
+        );
+        assert!(!versions.join("0.9.0").exists());
+        assert!(
+            manager
+                .import_toolchain(&temp.path().join("zig.tar.gz"), &version, false)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn tar_gz_archive_installs_like_tar_xz() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let archive = temp.path().join("zig-x86_64-linux-0.14.1.tar.gz");
+        {
+            let gz = flate2::write::GzEncoder::new(
+                std::fs::File::create(&archive).unwrap(),
+                flate2::Compression::fast(),
+            );
+            let mut builder = tar::Builder::new(gz);
+            let mut lib = tar::Header::new_gnu();
+            lib.set_entry_type(tar::EntryType::Directory);
+            lib.set_size(0);
+            lib.set_mode(0o750);
+            lib.set_cksum();
+            builder
+                .append_data(&mut lib, "zig-x86_64-linux-0.14.1/lib/", std::io::empty())
+                .unwrap();
+            for (path, contents) in [
+                (Shim::Zig.executable_name().to_string(), "zig"),
+                ("lib/std/std.zig".to_string(), "pub const x = 1;"),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o755);
+                header.set_cksum();
+                builder
+                    .append_data(
+                        &mut header,
+                        format!("zig-x86_64-linux-0.14.1/{path}"),
+                        contents.as_bytes(),
+                    )
+                    .unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+        assert_eq!(
+            ArchiveExt::from_file_name("zig-x86_64-linux-0.14.1.tar.gz"),
+            Some(ArchiveExt::TarGz)
+        );
+        assert_eq!(
+            ArchiveExt::TarGz.to_string().parse::<ArchiveExt>().unwrap(),
+            ArchiveExt::TarGz
+        );
+
+        let mut manager = manager_with_installs(temp.path(), &[]).await;
+        let version = semver::Version::new(0, 14, 1);
+        let zig = manager
+            .import_toolchain(&archive, &version, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            zig,
+            versions.join("0.14.1").join(Shim::Zig.executable_name())
+        );
+        assert_eq!(std::fs::read_to_string(&zig).unwrap(), "zig");
+        assert!(zig.with_file_name("lib/std/std.zig").is_file());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let lib = std::fs::metadata(zig.with_file_name("lib")).unwrap();
+            assert_eq!(lib.permissions().mode() & 0o777, 0o750);
+        }
+    }
+
+    #[tokio::test]
+    async fn installed_master_build_is_found_in_either_layout() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        let version = semver::Version::parse("0.16.0-dev.1+abc").unwrap();
+        let master = ResolvedZigVersion::Master(version.clone());
+        assert_eq!(manager.is_version_installed(&master), None);
+
+        // Flattened layout, with no versions/master directory at all
+        let flat = versions.join(version.to_string());
+        std::fs::create_dir_all(&flat).unwrap();
+        std::fs::write(flat.join(Shim::Zig.executable_name()), "").unwrap();
+        assert_eq!(
+            manager.is_version_installed(&master),
+            Some(flat.join(Shim::Zig.executable_name()))
+        );
+
+        let nested = manager.version_install_dir(&master);
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join(Shim::Zig.executable_name()), "").unwrap();
+        assert_eq!(
+            manager.is_version_installed(&master),
+            Some(nested.join(Shim::Zig.executable_name()))
+        );
+    }
+
+    #[tokio::test]
+    async fn installations_come_from_the_manifest_until_versions_dir_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let manifest = temp.path().join("installations.toml");
+        let add_version = |version: &str| {
+            let dir = versions.join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(Shim::Zig.executable_name()), "").unwrap();
+        };
+        let listed = || {
+            ToolchainManager::load_installations(&versions, &manifest)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.version.to_string())
+                .collect::<Vec<_>>()
+        };
+        add_version("0.14.0");
+        assert_eq!(listed(), ["0.14.0"]);
+        assert!(manifest.is_file());
+
+        // Removing the binary leaves versions/ untouched, so the manifest still stands
+        std::fs::remove_file(versions.join("0.14.0").join(Shim::Zig.executable_name())).unwrap();
+        assert_eq!(listed(), ["0.14.0"]);
+
+        add_version("0.15.1");
+        assert_eq!(listed(), ["0.15.1"]);
+
+        let mut manager = manager_with_installs(temp.path(), &[]).await;
+        std::fs::remove_file(versions.join("0.15.1").join(Shim::Zig.executable_name())).unwrap();
+        assert_eq!(manager.rescan_installations().unwrap(), 0);
+        assert!(listed().is_empty());
+    }
+
+    #[test]
+    fn version_matches_ignores_install_path() {
+        let install = |version: &str, path: &str, is_master: bool| ZigInstall {
+            version: semver::Version::parse(version).unwrap(),
+            path: PathBuf::from(path),
+            is_master,
+        };
+        let active = install("0.15.1", "/old/zv/versions/0.15.1", false);
+
+        assert!(active.version_matches(&install("0.15.1", "/new/zv/versions/0.15.1", false)));
+        assert!(!active.version_matches(&install("0.15.1", "/old/zv/versions/0.15.1", true)));
+        assert!(!active.version_matches(&install("0.14.1", "/old/zv/versions/0.14.1", false)));
+    }
+
+    #[tokio::test]
+    async fn clean_downloads_older_than_keeps_recent_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        let downloads = temp.path().join("downloads");
+        std::fs::create_dir_all(downloads.join("tmp")).unwrap();
+
+        let month_ago = SystemTime::now() - Duration::from_secs(30 * 24 * 3600);
+        for (name, age) in [
+            ("zig-old.tar.xz", Some(month_ago)),
+            ("tmp/zig-stale.tar.xz.tmp", Some(month_ago)),
+            ("zig-new.tar.xz", None),
+            ("tmp/zig-live.tar.xz.tmp", None),
+        ] {
+            let file = std::fs::File::create(downloads.join(name)).unwrap();
+            file.set_len(10).unwrap();
+            if let Some(age) = age {
+                file.set_modified(age).unwrap();
+            }
+        }
+
+        let (removed, freed) = manager
+            .clean_downloads_older_than(&downloads, Duration::from_secs(14 * 24 * 3600))
+            .await
+            .unwrap();
+        assert_eq!((removed, freed), (2, 20));
+        assert!(downloads.join("zig-new.tar.xz").is_file());
+        assert!(downloads.join("tmp/zig-live.tar.xz.tmp").is_file());
+        assert!(!downloads.join("zig-old.tar.xz").exists());
+    }
+
+    #[tokio::test]
+    async fn remove_shims_leaves_foreign_files_in_the_bin_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join(Shim::Zv.executable_name()), "zv").unwrap();
+        std::fs::write(bin.join(Shim::Zig.executable_name()), "zv").unwrap();
+        std::fs::write(bin.join(Shim::Zls.executable_name()), "someone else's zls").unwrap();
+        std::fs::write(bin.join("rg"), "ripgrep").unwrap();
+
+        assert_eq!(manager.remove_shims(), 2);
+        assert!(!bin.join(Shim::Zv.executable_name()).exists());
+        assert!(!bin.join(Shim::Zig.executable_name()).exists());
+        assert!(bin.join(Shim::Zls.executable_name()).is_file());
+        assert!(bin.join("rg").is_file());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shims_are_valid_through_a_symlinked_bin_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = manager_with_installs(temp.path(), &[]).await;
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let zv = bin.join("zv");
+        std::fs::write(&zv, "zv").unwrap();
+        std::os::unix::fs::symlink("zv", bin.join("zig")).unwrap();
+        std::fs::write(bin.join("zls"), "not zv").unwrap();
+        let linked = temp.path().join("linked-bin");
+        std::os::unix::fs::symlink(&bin, &linked).unwrap();
+
+        assert!(manager.is_valid_shim(&linked.join("zig"), &zv).unwrap());
+        assert!(!manager.is_valid_shim(&linked.join("zls"), &zv).unwrap());
+    }
+}
@@ -7,7 +7,12 @@ use color_eyre::eyre::eyre;
 use indicatif::{ProgressBar, ProgressStyle};
 use same_file::Handle;
 use sha2::{Digest, Sha256};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
 use std::time::Duration;
 
 /// Checks if a file is a valid zv shim by comparing it with the current executable
@@ -20,36 +25,54 @@ fn is_zv_shim(shim_path: &Path, current_exe_handle: &Handle) -> bool {
         return true;
     }
 
-    // Check for symlinks
-    if shim_path.is_symlink()
-        && let Ok(target) = std::fs::read_link(shim_path)
+    // Check for symlinks, either on the file itself or on any directory leading to it
+    if let Ok(resolved_target) = canonicalize(shim_path)
+        && let Ok(target_handle) = Handle::from_path(&resolved_target)
+        && target_handle == *current_exe_handle
     {
-        // Handle both absolute and relative symlink targets
-        let resolved_target = if target.is_absolute() {
-            canonicalize(&target)
-        } else {
-            // For relative symlinks, resolve relative to the symlink's parent directory
-            if let Some(parent) = shim_path.parent() {
-                canonicalize(parent.join(&target))
-            } else {
-                canonicalize(&target)
-            }
-        };
-
-        if let Ok(resolved_target) = resolved_target {
-            // Compare the resolved target with current exe using same-file
-            if let Ok(target_handle) = Handle::from_path(&resolved_target)
-                && target_handle == *current_exe_handle
-            {
-                tracing::debug!("Found ZV shim (symlink) at {:?}", shim_path);
-                return true;
-            }
-        }
+        tracing::debug!("Found ZV shim (symlink) at {:?}", shim_path);
+        return true;
     }
 
     false
 }
 
+/// Whether `path` sits directly inside `dir`, resolving symlinks on both sides so that a
+/// symlinked `bin/` directory or a symlink to one of its files still counts
+pub(crate) fn is_in_dir(path: &Path, dir: &Path) -> bool {
+    if path.parent() == Some(dir) {
+        return true;
+    }
+    let Ok(dir) = canonicalize(dir) else {
+        return false;
+    };
+    let parent_matches = path
+        .parent()
+        .and_then(|parent| canonicalize(parent).ok())
+        .is_some_and(|parent| parent == dir);
+    parent_matches
+        || canonicalize(path)
+            .ok()
+            .is_some_and(|resolved| resolved.parent() == Some(dir.as_path()))
+}
+
+/// Depth of zv → zv hops that led to this process, from `ZV_RECURSION_COUNT`
+pub(crate) fn recursion_count() -> u32 {
+    std::env::var("ZV_RECURSION_COUNT")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Whether `path` is `dir` or anywhere beneath it, resolving symlinks when both exist
+pub(crate) fn is_within(path: &Path, dir: &Path) -> bool {
+    path.starts_with(dir)
+        || canonicalize(path)
+            .ok()
+            .zip(canonicalize(dir).ok())
+            .is_some_and(|(path, dir)| path.starts_with(dir))
+}
+
 /// Detect and validate ZV shim in the bin directory
 /// Returns the canonicalized path if a valid ZV shim is found
 pub fn detect_shim(bin_path: &Path, shim: Shim) -> Option<PathBuf> {
@@ -141,6 +164,8 @@ pub fn zig_tarball(
         ArchiveExt::TarXz
     };
     if semver_version.le(&semver::Version::new(0, 14, 0)) {
+        // 32-bit ARM tarballs were named armv7a before the 0.14.1 rename
+        let arch = if arch == "arm" { "armv7a" } else { arch };
         Some(format!("zig-{os}-{arch}-{semver_version}.{ext}"))
     } else {
         Some(format!("zig-{arch}-{os}-{semver_version}.{ext}"))
@@ -176,27 +201,167 @@ pub fn host_target() -> Option<String> {
     Some(format!("{arch}-{os}"))
 }
 
+/// Spelling of a target key used by Zig releases up to 0.14.0, if it differs from today's
+pub fn legacy_target_key(key: &str) -> Option<String> {
+    key.strip_prefix("arm-").map(|os| format!("armv7a-{os}"))
+}
+
+/// Whether a cache's sync stamp lies in the future, which happens when the system clock moved
+/// back after the cache was written or the cache came from a machine with a fast clock. Callers
+/// treat such a cache as expired, so it is refreshed rather than looking fresh until the clock
+/// catches up. Logs a warning naming `cache`.
+pub fn is_future_stamp(stamp: chrono::DateTime<chrono::Utc>, cache: &str) -> bool {
+    let now = chrono::Utc::now();
+    if stamp <= now {
+        return false;
+    }
+    tracing::warn!(
+        "The {} cache says it was synced at {}, after the current time {}; the system clock may be skewed, so it will be refreshed",
+        cache,
+        stamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+    true
+}
+
 /// User-Agent string for network requests
 pub const fn zv_agent() -> &'static str {
     concat!("zv-cli/", env!("CARGO_PKG_VERSION"))
 }
 
+/// Set by `--progress json`: progress is reported as NDJSON events on stderr instead of spinners
+static JSON_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Report progress of every [ProgressHandle] spawned from now on as NDJSON events on stderr
+pub fn set_json_progress(enabled: bool) {
+    JSON_PROGRESS.store(enabled, Ordering::Relaxed);
+}
+
+/// Set by `zv use/install --quiet`: informational output and spinners are suppressed
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Handle on the default `zv=info` log filter, registered by `init_tracing` unless `ZV_LOG`
+/// chose a filter of its own
+static LOG_FILTER: std::sync::OnceLock<
+    tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>,
+> = std::sync::OnceLock::new();
+
+/// Let [set_quiet] raise the default log filter through `handle`
+pub fn set_log_filter_handle(
+    handle: tracing_subscriber::reload::Handle<
+        tracing_subscriber::EnvFilter,
+        tracing_subscriber::Registry,
+    >,
+) {
+    let _ = LOG_FILTER.set(handle);
+}
+
+/// Suppress informational output and draw no spinners from now on. Errors still reach stderr,
+/// and so do warnings logged through tracing; info logs are dropped.
+pub fn set_quiet(enabled: bool) {
+    QUIET.store(enabled, Ordering::Relaxed);
+    if let Some(handle) = LOG_FILTER.get() {
+        let filter = if enabled { "zv=warn" } else { "zv=info" };
+        if let Err(e) = handle.reload(tracing_subscriber::EnvFilter::new(filter)) {
+            tracing::debug!("Failed to change the log filter: {e}");
+        }
+    }
+}
+
+/// Whether `--quiet` is in effect, see [set_quiet]
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
 /// Messages that can be sent to the progress bar actor
 #[derive(Debug, Clone)]
 pub enum ProgressMessage {
-    Start { message: String },
-    Update { message: String },
-    Finish { message: String },
-    FinishWithError { message: String },
+    Start {
+        message: String,
+    },
+    Update {
+        message: String,
+    },
+    /// Bytes received so far; `message` is what the spinner shows
+    Download {
+        bytes: u64,
+        total: Option<u64>,
+        message: String,
+    },
+    /// An archive entry was extracted
+    Extract {
+        file: String,
+    },
+    Finish {
+        message: String,
+    },
+    FinishWithError {
+        message: String,
+    },
     Shutdown,
 }
 
+/// One line of `--progress json` output
+#[derive(serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ProgressEvent<'a> {
+    Start {
+        message: &'a str,
+    },
+    Status {
+        message: &'a str,
+    },
+    Download {
+        bytes: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+    },
+    Extract {
+        file: &'a str,
+    },
+    Done {
+        message: &'a str,
+    },
+    Error {
+        message: &'a str,
+    },
+}
+
+impl<'a> ProgressEvent<'a> {
+    fn from_message(msg: &'a ProgressMessage) -> Option<Self> {
+        Some(match msg {
+            ProgressMessage::Start { message } => Self::Start { message },
+            ProgressMessage::Update { message } => Self::Status { message },
+            ProgressMessage::Download { bytes, total, .. } => Self::Download {
+                bytes: *bytes,
+                total: *total,
+            },
+            ProgressMessage::Extract { file } => Self::Extract { file },
+            ProgressMessage::Finish { message } => Self::Done { message },
+            ProgressMessage::FinishWithError { message } => Self::Error { message },
+            ProgressMessage::Shutdown => return None,
+        })
+    }
+}
+
 /// Progress bar actor that runs in its own thread
 struct ProgressActor {
     rx: tokio::sync::mpsc::Receiver<ProgressMessage>,
 }
 
 impl ProgressActor {
+    /// Write each message as a JSON line to `out` instead of drawing a spinner
+    fn run_json(mut self, mut out: impl Write) {
+        while let Some(msg) = self.rx.blocking_recv() {
+            let Some(event) = ProgressEvent::from_message(&msg) else {
+                break;
+            };
+            if let Ok(line) = serde_json::to_string(&event) {
+                let _ = writeln!(out, "{line}").and_then(|_| out.flush());
+            }
+        }
+    }
+
     fn run(mut self) {
         let mut spinner: Option<ProgressBar> = None;
 
@@ -214,11 +379,12 @@ impl ProgressActor {
                     pb.enable_steady_tick(Duration::from_millis(120));
                     spinner = Some(pb);
                 }
-                ProgressMessage::Update { message } => {
+                ProgressMessage::Update { message } | ProgressMessage::Download { message, .. } => {
                     if let Some(ref pb) = spinner {
                         pb.set_message(message);
                     }
                 }
+                ProgressMessage::Extract { .. } => {}
                 ProgressMessage::Finish { message } => {
                     if let Some(pb) = spinner.take() {
                         pb.finish_with_message(message);
@@ -244,11 +410,22 @@ impl ProgressActor {
 pub struct ProgressHandle {
     tx: tokio::sync::mpsc::Sender<ProgressMessage>,
     handle: Option<std::thread::JoinHandle<()>>,
+    /// Whether per-file [ProgressMessage::Extract] messages are sent, which only JSON output uses
+    file_events: bool,
 }
 
 impl ProgressHandle {
-    /// Spawn a new progress bar actor in its own thread
+    /// Spawn a new progress bar actor in its own thread. With `--progress json` the actor
+    /// writes NDJSON events to stderr instead, and with `--quiet` it discards every message.
     pub fn spawn() -> Self {
+        if JSON_PROGRESS.load(Ordering::Relaxed) {
+            return Self::spawn_json(std::io::stderr());
+        }
+        if is_quiet() {
+            let mut discarded = Self::spawn_json(std::io::sink());
+            discarded.file_events = false;
+            return discarded;
+        }
         let (tx, rx) = tokio::sync::mpsc::channel(32);
 
         let handle = std::thread::spawn(move || {
@@ -259,6 +436,23 @@ impl ProgressHandle {
         Self {
             tx,
             handle: Some(handle),
+            file_events: false,
+        }
+    }
+
+    /// Spawn an actor that writes one JSON event per line to `out`
+    pub fn spawn_json(out: impl Write + Send + 'static) -> Self {
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+
+        let handle = std::thread::spawn(move || {
+            let actor = ProgressActor { rx };
+            actor.run_json(out);
+        });
+
+        Self {
+            tx,
+            handle: Some(handle),
+            file_events: true,
         }
     }
 
@@ -292,6 +486,34 @@ impl ProgressHandle {
         .await
     }
 
+    /// Report `bytes` of `total` downloaded, showing `message` on the spinner
+    pub async fn download(
+        &self,
+        bytes: u64,
+        total: Option<u64>,
+        message: impl Into<String>,
+    ) -> Result<(), tokio::sync::mpsc::error::SendError<ProgressMessage>> {
+        self.send(ProgressMessage::Download {
+            bytes,
+            total,
+            message: message.into(),
+        })
+        .await
+    }
+
+    /// Report an extracted archive entry. Only JSON output lists files, so otherwise this
+    /// sends nothing.
+    pub async fn extract(
+        &self,
+        file: impl Into<String>,
+    ) -> Result<(), tokio::sync::mpsc::error::SendError<ProgressMessage>> {
+        if !self.file_events {
+            return Ok(());
+        }
+        self.send(ProgressMessage::Extract { file: file.into() })
+            .await
+    }
+
     /// Finish the progress bar with a success message
     pub async fn finish(
         &self,
@@ -383,16 +605,27 @@ pub async fn remove_files(paths: &[impl AsRef<Path>]) {
     }
 }
 
+/// Checks that `dir` is writable by creating and removing a probe file in it
+pub(crate) fn ensure_dir_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".zv-write-probe-{}", std::process::id()));
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)
+}
+
+/// How often [`verify_checksum`] reports hashing progress
+const VERIFY_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Verify SHA-256 checksum of a file
 ///
 /// Reads the file and computes its SHA-256 hash, comparing it with the expected checksum.
 /// Returns an error if the checksums don't match or if file reading fails.
+/// Hashing runs on the blocking pool; if `progress` is given it shows "Verifying… NN%" meanwhile.
 /// Enhanced with comprehensive error handling and detailed logging for debugging.
 pub(crate) async fn verify_checksum(
     file_path: &Path,
     expected_shasum: &str,
+    progress: Option<&ProgressHandle>,
 ) -> Result<(), ZvError> {
-    use tokio::io::AsyncReadExt;
     const TARGET: &str = "zv::utils::verify_checksum";
     tracing::debug!(target: TARGET, "Starting checksum verification for file: {}", file_path.display());
     tracing::debug!(target: TARGET, "Expected SHA-256: {}", expected_shasum);
@@ -446,15 +679,38 @@ pub(crate) async fn verify_checksum(
         }
     };
 
-    // Open the file for reading
-    let mut file = match tokio::fs::File::open(file_path).await {
-        Ok(file) => {
-            tracing::debug!(target: TARGET, "Successfully opened file for checksum verification");
-            file
+    // Hash on the blocking pool so a multi-hundred-megabyte read doesn't stall the runtime,
+    // reporting how far along it is through `progress` meanwhile
+    let file_size = file_metadata.len();
+    let hashed = Arc::new(AtomicU64::new(0));
+    let mut task = tokio::task::spawn_blocking({
+        let file_path = file_path.to_path_buf();
+        let hashed = Arc::clone(&hashed);
+        move || sha256_file(&file_path, &hashed)
+    });
+
+    tracing::debug!(target: TARGET, "Starting SHA-256 computation with 64KB buffer");
+
+    let mut ticker = tokio::time::interval(VERIFY_PROGRESS_INTERVAL);
+    let joined = loop {
+        tokio::select! {
+            joined = &mut task => break joined,
+            _ = ticker.tick() => {
+                if let Some(progress) = progress
+                    && file_size > 0
+                {
+                    let percent = hashed.load(Ordering::Relaxed) * 100 / file_size;
+                    let _ = progress.update(format!("Verifying… {percent}%")).await;
+                }
+            }
         }
-        Err(e) => {
+    };
+
+    let (computed_hash, total_bytes_read) = match joined {
+        Ok(Ok(hashed)) => hashed,
+        Ok(Err(e)) => {
             let error_msg = format!(
-                "Failed to open file for checksum verification: {}",
+                "Failed to read file for checksum verification: {}",
                 file_path.display()
             );
             tracing::error!(target: TARGET, "{}: {}", error_msg, e);
@@ -467,60 +723,26 @@ pub(crate) async fn verify_checksum(
                 std::io::ErrorKind::PermissionDenied => {
                     tracing::error!(target: TARGET, "Permission denied - check file read permissions");
                 }
+                std::io::ErrorKind::UnexpectedEof => {
+                    tracing::error!(target: TARGET, "Unexpected end of file - file may be truncated or corrupted");
+                }
                 _ => {
-                    tracing::error!(target: TARGET, "Unexpected I/O error opening file: {:?}", e.kind());
+                    tracing::error!(target: TARGET, "Unexpected I/O error during file read: {:?}", e.kind());
                 }
             }
 
             return Err(ZvError::Io(e));
         }
-    };
-
-    // Create SHA-256 hasher
-    let mut hasher = <Sha256 as Digest>::new();
-    let mut buffer = [0u8; 8192]; // 8KB buffer for efficient reading
-    let mut total_bytes_read = 0u64;
-    let file_size = file_metadata.len();
-
-    tracing::debug!(target: TARGET, "Starting SHA-256 computation with 8KB buffer");
-
-    // Read file in chunks and update hasher
-    loop {
-        let bytes_read = match file.read(&mut buffer).await {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                let error_msg = format!(
-                    "Failed to read file during checksum verification: {}",
-                    file_path.display()
-                );
-                tracing::error!(target: TARGET, "{}: {}", error_msg, e);
-
-                // Provide specific error context
-                match e.kind() {
-                    std::io::ErrorKind::UnexpectedEof => {
-                        tracing::error!(target: TARGET, "Unexpected end of file - file may be truncated or corrupted");
-                    }
-                    std::io::ErrorKind::Interrupted => {
-                        tracing::warn!(target: TARGET, "Read operation interrupted - this is usually recoverable");
-                        continue; // Retry the read operation
-                    }
-                    _ => {
-                        tracing::error!(target: TARGET, "Unexpected I/O error during file read: {:?}", e.kind());
-                    }
-                }
-
-                return Err(ZvError::Io(e));
-            }
-        };
-
-        if bytes_read == 0 {
-            tracing::debug!(target: TARGET, "Reached end of file after reading {} bytes", total_bytes_read);
-            break; // End of file
+        Err(join_err) => {
+            tracing::error!(target: TARGET, "Checksum task did not complete: {}", join_err);
+            return Err(ZvError::General(eyre!(
+                "Checksum verification of {} did not complete: {}",
+                file_path.display(),
+                join_err
+            )));
         }
-
-        hasher.update(&buffer[..bytes_read]);
-        total_bytes_read += bytes_read as u64;
-    }
+    };
+    tracing::debug!(target: TARGET, "Reached end of file after reading {} bytes", total_bytes_read);
 
     // Verify we read the expected amount of data
     if total_bytes_read != file_size {
@@ -532,8 +754,6 @@ pub(crate) async fn verify_checksum(
         return Err(ZvError::General(eyre!(error_msg)));
     }
 
-    // Finalize hash and convert to hex string
-    let computed_hash = hasher.finalize();
     let computed_hex = format!("{:x}", computed_hash);
 
     tracing::debug!(target: TARGET, "Computed SHA-256: {}", computed_hex);
@@ -566,3 +786,119 @@ pub(crate) async fn verify_checksum(
         Err(ZvError::General(eyre!(error_msg)))
     }
 }
+
+/// Hex SHA-256 of the file at `path`, read on the blocking pool
+pub(crate) async fn sha256_hex(path: &Path) -> std::io::Result<String> {
+    let path = path.to_path_buf();
+    let (digest, _) = tokio::task::spawn_blocking(move || sha256_file(&path, &AtomicU64::new(0)))
+        .await
+        .map_err(std::io::Error::other)??;
+    Ok(format!("{digest:x}"))
+}
+
+/// SHA-256 of the file at `path`, adding each chunk's length to `hashed` as it goes.
+/// Blocking; returns the digest and the number of bytes read.
+fn sha256_file(
+    path: &Path,
+    hashed: &AtomicU64,
+) -> std::io::Result<(sha2::digest::Output<Sha256>, u64)> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = <Sha256 as Digest>::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let bytes_read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..bytes_read]);
+        total += bytes_read as u64;
+        hashed.store(total, Ordering::Relaxed);
+    }
+    Ok((hasher.finalize(), total))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn is_within_matches_nested_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        std::fs::create_dir_all(versions.join("0.14.1/bin")).unwrap();
+
+        assert!(is_within(&versions, &versions));
+        assert!(is_within(&versions.join("0.14.1/bin"), &versions));
+        assert!(is_within(&versions.join("not-created-yet"), &versions));
+        assert!(!is_within(&temp.path().join("bin"), &versions));
+        #[cfg(unix)]
+        {
+            let linked = temp.path().join("linked");
+            std::os::unix::fs::symlink(versions.join("0.14.1"), &linked).unwrap();
+            assert!(is_within(&linked, &versions));
+        }
+    }
+
+    #[test]
+    fn is_in_dir_resolves_symlinks_on_either_side() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        let local_bin = temp.path().join("local-bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(&local_bin).unwrap();
+        std::fs::write(bin.join("zig"), "").unwrap();
+        let linked = temp.path().join("linked-bin");
+        symlink(&bin, &linked).unwrap();
+        symlink(bin.join("zig"), local_bin.join("zig")).unwrap();
+        std::fs::write(local_bin.join("zls"), "").unwrap();
+
+        assert!(is_in_dir(&bin.join("zig"), &bin));
+        assert!(is_in_dir(&linked.join("zig"), &bin));
+        assert!(is_in_dir(&bin.join("zig"), &linked));
+        assert!(is_in_dir(&local_bin.join("zig"), &bin));
+        assert!(!is_in_dir(&local_bin.join("zls"), &bin));
+    }
+
+    #[test]
+    fn detect_shim_through_a_symlinked_bin_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        symlink(
+            std::env::current_exe().unwrap(),
+            bin.join(Shim::Zig.executable_name()),
+        )
+        .unwrap();
+        let linked = temp.path().join("linked-bin");
+        symlink(&bin, &linked).unwrap();
+
+        assert!(detect_shim(&linked, Shim::Zig).is_some());
+        assert!(detect_shim(&linked, Shim::Zls).is_none());
+    }
+
+    #[tokio::test]
+    async fn verify_checksum_hashes_large_file_off_runtime() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("zig.tar.xz");
+        let contents: Vec<u8> = (0..3 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &contents).unwrap();
+        let expected = format!("{:x}", Sha256::digest(&contents));
+
+        let progress = ProgressHandle::spawn();
+        verify_checksum(&path, &expected, Some(&progress))
+            .await
+            .unwrap();
+        assert_eq!(sha256_hex(&path).await.unwrap(), expected);
+        assert!(verify_checksum(&path, &"0".repeat(64), None).await.is_err());
+        assert!(matches!(
+            verify_checksum(&temp.path().join("missing"), &expected, None).await,
+            Err(ZvError::General(_) | ZvError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,261 @@
+//! Zig source tarballs for bootstrapping (`zv use <version> --source`, `zv fetch-src`)
+//!
+//! `zv fetch-src` only downloads and verifies the tarball. With `--source`, sources are extracted
+//! to `ZV_DIR/sources/<version>` and are never registered as an installed toolchain, so no shims
+//! point at them.
+
+use super::App;
+use crate::app::network::{ArtifactInfo, ZigDownload};
+use crate::app::utils::{ProgressHandle, remove_files};
+use crate::{ArchiveExt, ZvError};
+use color_eyre::eyre::eyre;
+use std::path::{Component, Path, PathBuf};
+
+const TARGET: &str = "zv::app::zig_source";
+
+/// Download the source tarball of the release loaded in `app.to_install` and extract it into
+/// `ZV_DIR/sources/<version>`, returning that directory. An existing extraction is kept unless
+/// `reinstall` is set.
+pub async fn install_zig_source(
+    app: &mut App,
+    force_ziglang: bool,
+    reinstall: bool,
+) -> Result<PathBuf, ZvError> {
+    let (version, src) = loaded_source(app)?;
+    let dest = app.paths.sources_dir().join(version.to_string());
+    if dest.join("build.zig").is_file() && !reinstall {
+        tracing::debug!(target: TARGET, dest = %dest.display(), "Sources already extracted");
+        return Ok(dest);
+    }
+
+    let (tarball, ext) = source_tarball_name(&src)?;
+    let ZigDownload {
+        tarball_path,
+        minisig_path,
+        ..
+    } = download_source(app, &version, &src, &tarball, force_ziglang).await?;
+
+    let progress_handle = ProgressHandle::spawn();
+    let _ = progress_handle.start(format!("Extracting {tarball}")).await;
+    let archive = tarball_path.clone();
+    let target_dir = dest.clone();
+    let extracted = tokio::task::spawn_blocking(move || extract_source(&archive, ext, &target_dir))
+        .await
+        .map_err(|e| ZvError::Io(std::io::Error::other(e)))
+        .and_then(|result| result);
+    match &extracted {
+        Ok(()) => {
+            let _ = progress_handle.finish("Extraction complete").await;
+        }
+        Err(_) => {
+            let _ = progress_handle
+                .finish_with_error("Failed to extract source archive")
+                .await;
+        }
+    }
+    remove_files(&[tarball_path.as_path(), minisig_path.as_path()]).await;
+    extracted?;
+    Ok(dest)
+}
+
+/// Download and verify (checksum and minisign) the source tarball of the release loaded in
+/// `app.to_install` without extracting it. The tarball and its `.minisig` stay in the
+/// downloads cache, or are moved into `out_dir` when one is given.
+pub async fn fetch_zig_source(
+    app: &mut App,
+    out_dir: Option<&Path>,
+    force_ziglang: bool,
+) -> Result<ZigDownload, ZvError> {
+    let (version, src) = loaded_source(app)?;
+    let (tarball, _) = source_tarball_name(&src)?;
+    let mut download = download_source(app, &version, &src, &tarball, force_ziglang).await?;
+    let Some(out_dir) = out_dir else {
+        return Ok(download);
+    };
+
+    tokio::fs::create_dir_all(out_dir)
+        .await
+        .map_err(ZvError::Io)?;
+    for path in [&mut download.tarball_path, &mut download.minisig_path] {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let moved = out_dir.join(name);
+        // The downloads cache may be on another filesystem, where a rename fails
+        if tokio::fs::rename(&*path, &moved).await.is_err() {
+            tokio::fs::copy(&*path, &moved).await.map_err(ZvError::Io)?;
+            let _ = tokio::fs::remove_file(&*path).await;
+        }
+        *path = moved;
+    }
+    Ok(download)
+}
+
+/// Version and source artifact of the release loaded in `app.to_install`
+fn loaded_source(app: &mut App) -> Result<(semver::Version, ArtifactInfo), ZvError> {
+    let release = app
+        .to_install
+        .take()
+        .and_then(|z| z.into_release())
+        .ok_or_else(|| {
+            ZvError::ZigVersionResolveError(eyre!(
+                "No ZigRelease is currently loaded for installation"
+            ))
+        })?;
+    let src = release.src_artifact().cloned().ok_or_else(|| {
+        ZvError::ZigNotFound(eyre!(
+            "The index lists no source tarball for Zig {}",
+            release.version_string()
+        ))
+    })?;
+    Ok((release.resolved_version().version().clone(), src))
+}
+
+/// File name and format of a source tarball, going by its URL
+fn source_tarball_name(src: &ArtifactInfo) -> Result<(String, ArchiveExt), ZvError> {
+    let tarball = src
+        .ziglang_org_tarball
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            ZvError::General(eyre!(
+                "Could not derive a file name from '{}'",
+                src.ziglang_org_tarball
+            ))
+        })?;
+    let ext = ArchiveExt::from_file_name(&tarball)
+        .ok_or_else(|| ZvError::General(eyre!("Unsupported source archive '{tarball}'")))?;
+    Ok((tarball, ext))
+}
+
+/// Download `src` from a community mirror, or from ziglang.org with `force_ziglang`, checking
+/// its checksum and minisign signature
+async fn download_source(
+    app: &mut App,
+    version: &semver::Version,
+    src: &ArtifactInfo,
+    tarball: &str,
+    force_ziglang: bool,
+) -> Result<ZigDownload, ZvError> {
+    if force_ziglang {
+        app.ensure_network().await?;
+        app.network
+            .as_ref()
+            .unwrap()
+            .direct_download(
+                &src.ziglang_org_tarball,
+                &format!("{}.minisig", src.ziglang_org_tarball),
+                tarball,
+                crate::app::constants::ZIG_MINSIGN_PUBKEY,
+                Some(&src.shasum),
+                Some(src.size),
+            )
+            .await
+    } else {
+        app.ensure_network_with_mirrors().await?;
+        app.network
+            .as_mut()
+            .unwrap()
+            .download_version(version, tarball, Some(src))
+            .await
+    }
+}
+
+/// Extract a source tarball into `dest`, dropping the archive's top-level directory. Entries
+/// are unpacked next to `dest` first so an interrupted extraction never leaves a partial tree.
+fn extract_source(archive: &Path, ext: ArchiveExt, dest: &Path) -> Result<(), ZvError> {
+    let file = std::fs::File::open(archive).map_err(ZvError::Io)?;
+    let decoder: Box<dyn std::io::Read> = match ext {
+        ArchiveExt::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+        ArchiveExt::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        ArchiveExt::Zip => {
+            return Err(ZvError::General(eyre!(
+                "Source archives are expected to be tarballs, got {}",
+                archive.display()
+            )));
+        }
+    };
+
+    let partial = partial_dir(dest);
+    if partial.exists() {
+        std::fs::remove_dir_all(&partial).map_err(ZvError::Io)?;
+    }
+    std::fs::create_dir_all(&partial).map_err(ZvError::Io)?;
+
+    let mut tar = tar::Archive::new(decoder);
+    for entry in tar.entries().map_err(ZvError::Io)? {
+        let mut entry = entry.map_err(ZvError::Io)?;
+        let path = entry.path().map_err(ZvError::Io)?.into_owned();
+        let relative: PathBuf = path.components().skip(1).collect();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(ZvError::General(eyre!(
+                "Refusing to extract unsafe source entry '{}'",
+                path.display()
+            )));
+        }
+        let out = partial.join(relative);
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent).map_err(ZvError::Io)?;
+        }
+        entry.unpack(out).map_err(ZvError::Io)?;
+    }
+
+    if dest.exists() {
+        std::fs::remove_dir_all(dest).map_err(ZvError::Io)?;
+    }
+    std::fs::rename(&partial, dest).map_err(ZvError::Io)
+}
+
+/// Sibling of `dest` an extraction is unpacked into before it replaces `dest`
+fn partial_dir(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    dest.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_source_drops_the_top_level_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("zig-0.14.1.tar.xz");
+        {
+            let xz = xz2::write::XzEncoder::new(std::fs::File::create(&archive).unwrap(), 1);
+            let mut builder = tar::Builder::new(xz);
+            for (path, contents) in [
+                ("zig-0.14.1/build.zig", "pub fn build() void {}"),
+                ("zig-0.14.1/src/main.zig", "pub fn main() void {}"),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, path, contents.as_bytes())
+                    .unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dest = temp.path().join("sources/0.14.1");
+        std::fs::create_dir_all(&dest).unwrap();
+        std::fs::write(dest.join("stale.zig"), "").unwrap();
+        extract_source(&archive, ArchiveExt::TarXz, &dest).unwrap();
+
+        assert!(dest.join("build.zig").is_file());
+        assert!(dest.join("src/main.zig").is_file());
+        assert!(!dest.join("stale.zig").exists());
+        assert!(!temp.path().join("sources/0.14.1.partial").exists());
+        assert!(extract_source(&archive, ArchiveExt::Zip, &dest).is_err());
+    }
+}
@@ -6,16 +6,8 @@ use color_eyre::eyre::eyre;
 use std::path::{Path, PathBuf};
 
 fn archive_extension_from_name(name: &str) -> Result<ArchiveExt, ZvError> {
-    if name.ends_with(".zip") {
-        Ok(ArchiveExt::Zip)
-    } else if name.ends_with(".tar.xz") {
-        Ok(ArchiveExt::TarXz)
-    } else {
-        Err(ZvError::General(eyre!(
-            "Unsupported ZLS artifact extension for '{}'",
-            name
-        )))
-    }
+    ArchiveExt::from_file_name(name)
+        .ok_or_else(|| ZvError::General(eyre!("Unsupported ZLS artifact extension for '{}'", name)))
 }
 
 fn extract_filename_from_url(url: &str) -> Result<String, ZvError> {
@@ -50,6 +42,13 @@ async fn extract_zls_binary(
                 ZvError::General(eyre!("Failed to extract ZLS tar.xz archive: {e}"))
             })?;
         }
+        ArchiveExt::TarGz => {
+            let gz = flate2::read::GzDecoder::new(std::io::Cursor::new(bytes));
+            let mut archive = tar::Archive::new(gz);
+            archive.unpack(&temp_dir).map_err(|e| {
+                ZvError::General(eyre!("Failed to extract ZLS tar.gz archive: {e}"))
+            })?;
+        }
         ArchiveExt::Zip => {
             let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
                 .map_err(|e| ZvError::General(eyre!("Failed to open ZLS zip archive: {e}")))?;
@@ -4,18 +4,31 @@ use crate::{
 };
 use clap::{Parser, Subcommand};
 use color_eyre::eyre::eyre;
+use std::path::PathBuf;
 use std::str::FromStr;
 use yansi::Paint;
+mod cache;
+mod changelog;
 mod clean;
+mod config;
+mod default;
+mod exec;
+mod export;
+mod fetch_src;
+mod import;
+mod info;
 mod init;
 mod install;
 mod list;
+mod mirrors;
 mod setup;
+mod shell;
 mod stats;
 pub mod sync; // Make sync public so other modules can use check_and_update_zv_binary
 mod uninstall;
 mod update;
 mod r#use;
+mod verify;
 mod zig;
 mod zls;
 mod zls_cmd;
@@ -28,6 +41,8 @@ pub use zls::zls_main;
 pub enum CleanTarget {
     All,
     Downloads,
+    /// Cached ZLS builds and the ZLS source checkout
+    Zls,
     Versions(Vec<ZigVersion>),
 }
 
@@ -36,6 +51,7 @@ fn parse_clean_target(s: &str) -> Result<CleanTarget, String> {
     match s.to_lowercase().as_str() {
         "all" => Ok(CleanTarget::All),
         "downloads" => Ok(CleanTarget::Downloads),
+        "zls" => Ok(CleanTarget::Zls),
         _ => {
             // Try parsing as comma-separated version list
             let versions: Result<Vec<ZigVersion>, _> = s
@@ -52,6 +68,67 @@ fn parse_clean_target(s: &str) -> Result<CleanTarget, String> {
     }
 }
 
+/// Represents the target for a use operation
+#[derive(Debug, Clone)]
+pub enum UseTarget {
+    Version(ZigVersion),
+    /// A non-zv `zig` found on PATH
+    System,
+    /// `-`: read the version from stdin
+    Stdin,
+}
+
+/// Release channel for `zv use --channel`
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Channel {
+    /// Latest stable release, same as `zv use latest`
+    Stable,
+    /// Latest master build, same as `zv use master`
+    Master,
+}
+
+/// Order of installed versions in `zv list --sort`
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ListSort {
+    /// Oldest version first
+    #[default]
+    Version,
+    /// Earliest installed first
+    Date,
+    /// Smallest on disk first
+    Size,
+}
+
+/// How long-running operations report progress, set by `--progress`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProgressFormat {
+    /// Spinners for a terminal
+    #[default]
+    Human,
+    /// One JSON event per line on stderr
+    Json,
+}
+
+impl From<Channel> for ZigVersion {
+    fn from(channel: Channel) -> Self {
+        match channel {
+            Channel::Stable => ZigVersion::Latest(None),
+            Channel::Master => ZigVersion::Master(None),
+        }
+    }
+}
+
+/// Parse use target string into UseTarget enum
+fn parse_use_target(s: &str) -> Result<UseTarget, String> {
+    match s.to_lowercase().as_str() {
+        "system" => Ok(UseTarget::System),
+        "-" => Ok(UseTarget::Stdin),
+        _ => ZigVersion::from_str(s)
+            .map(UseTarget::Version)
+            .map_err(|e| e.to_string()),
+    }
+}
+
 pub async fn zv_main() -> super::Result<()> {
     let zv_cli = <ZvCli as clap::Parser>::parse();
     let paths = tools::ZvPaths::resolve()?;
@@ -62,14 +139,22 @@ pub async fn zv_main() -> super::Result<()> {
         );
     }
     let using_env = paths.using_env_var;
-    let app = App::init(UserConfig {
+    let app = match App::init(UserConfig {
         paths,
         shell: Some(Shell::detect()),
     })
-    .await?;
+    .await
+    {
+        Ok(app) => app,
+        Err(e) => return report_error(e.into()),
+    };
 
     match zv_cli.command {
-        Some(cmd) => cmd.execute(app, using_env).await?,
+        Some(cmd) => {
+            if let Err(e) = cmd.execute(app, using_env, zv_cli.global).await {
+                return report_error(e);
+            }
+        }
         None => {
             print_welcome_message(app);
         }
@@ -77,6 +162,48 @@ pub async fn zv_main() -> super::Result<()> {
     Ok(())
 }
 
+/// Print `e` with what the user can do about it and exit, or hand it back to color_eyre when
+/// it carries no [crate::ZvError] to suggest a fix for
+fn report_error(e: color_eyre::eyre::Report) -> super::Result<()> {
+    if is_empty_mirrors(&e) {
+        tracing::debug!("{e:?}");
+        error("No community mirror is available to download from");
+        suggest!("Refresh the mirror list with {}", cmd = "zv sync");
+        suggest!(
+            "Download straight from ziglang.org by passing {}",
+            cmd = "--force-ziglang"
+        );
+        suggest!(
+            "Check the mirrors zv knows about with {}",
+            cmd = "zv list --mirrors"
+        );
+        std::process::exit(1);
+    }
+    let Some(zv_error) = e.chain().find_map(|c| c.downcast_ref::<crate::ZvError>()) else {
+        return Err(e);
+    };
+    eprintln!("Error: {e:?}");
+    suggest!("{}", zv_error.recovery_suggestion());
+    std::process::exit(1);
+}
+
+/// Whether `e` comes from having no usable community mirror, which the user can recover from
+fn is_empty_mirrors(e: &color_eyre::eyre::Report) -> bool {
+    e.chain().any(|cause| {
+        cause
+            .downcast_ref::<crate::NetErr>()
+            .is_some_and(|err| matches!(err.root(), crate::NetErr::EmptyMirrors))
+    })
+}
+
+impl GlobalArgs {
+    /// `--quiet` for `use` and `install`: asked for, or implied by `--progress json`, unless
+    /// `--verbose` came last
+    fn quiet_output(&self, output: OutputArgs) -> bool {
+        (output.quiet || self.progress == ProgressFormat::Json) && !output.verbose
+    }
+}
+
 /// zv - Zig Version (zv) Manager
 ///
 /// Download, install, and manage Zig versions
@@ -92,10 +219,103 @@ pub async fn zv_main() -> super::Result<()> {
 )]
 pub struct ZvCli {
     /// Global options
+    #[command(flatten)]
+    pub(crate) global: GlobalArgs,
     #[command(subcommand)]
     pub(crate) command: Option<Commands>,
 }
 
+/// How much `use` and `install` print
+#[derive(clap::Args, Debug, Clone, Copy, Default)]
+pub struct OutputArgs {
+    /// Only print the final result and errors
+    #[arg(
+        long,
+        short = 'q',
+        overrides_with = "verbose",
+        help = "Print only the final result line and errors, without progress spinners. Implied by --progress json"
+    )]
+    quiet: bool,
+    /// Print informational output even with --quiet or --progress json
+    #[arg(long, short = 'v', overrides_with = "quiet")]
+    verbose: bool,
+}
+
+/// Options that apply to every subcommand, given before it (e.g. `zv --force clean`)
+#[derive(clap::Args, Debug, Clone, Copy, Default)]
+pub struct GlobalArgs {
+    /// Skip confirmation prompts and redo work zv would otherwise skip
+    #[arg(
+        long = "force",
+        help = "Skip confirmation prompts and assume the default answer",
+        long_help = "Skip confirmation prompts across commands and assume the default answer.\n\
+                     • clean  - don't ask before `zv clean all` removes every version\n\
+                     • init   - initialize the project inside a directory that already exists\n\
+                     • setup  - create directories and apply changes without asking\n\
+                     • update - reinstall even if already on the latest version\n\
+                     • zls    - re-provision even if a compatible ZLS is installed"
+    )]
+    pub force: bool,
+
+    /// Never prompt; take each prompt's default answer
+    #[arg(
+        long = "no-interactive",
+        global = true,
+        help = "Disable interactive prompts and use default choices for automation",
+        long_help = "Disable interactive prompts across commands and use their default choices\n\
+                     for automation (setup, clean, init and sync confirmations). Same as\n\
+                     ZV_NON_INTERACTIVE=1. Interactive mode is automatically disabled in CI\n\
+                     environments, when TERM=dumb, or when TTY is not available."
+    )]
+    pub no_interactive: bool,
+
+    /// Allow zv to spawn a shell where it would otherwise refuse
+    #[arg(
+        long = "allow-shell",
+        help = "Allow zv to spawn a shell where it would otherwise refuse (e.g. a nested `zv shell`)"
+    )]
+    pub allow_shell: bool,
+
+    /// Replace a zig/zls in ZV_DIR/bin that zv didn't create
+    #[arg(
+        long = "force-shims",
+        global = true,
+        help = "Overwrite a zig or zls in ZV_DIR/bin that isn't a zv shim"
+    )]
+    pub force_shims: bool,
+
+    /// Network timeout in seconds
+    #[arg(
+        long = "timeout",
+        value_name = "SECS",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Timeout in seconds for network requests (overrides ZV_FETCH_TIMEOUT_SECS)",
+        long_help = "Timeout in seconds for network requests, overriding ZV_FETCH_TIMEOUT_SECS.\n\
+                     Applies per request: to fetching the download index, the mirrors list and\n\
+                     ZLS lookups, to establishing any connection, and to how long a download may\n\
+                     stall without receiving data. A download that keeps making progress is never\n\
+                     cut off, however long it takes."
+    )]
+    pub timeout: Option<u64>,
+
+    /// Progress output format
+    #[arg(
+        long = "progress",
+        value_name = "FORMAT",
+        global = true,
+        default_value = "human",
+        help = "How to report download and extraction progress",
+        long_help = "How to report download and extraction progress.\n\
+                     • human - spinners on the terminal (default)\n\
+                     • json  - one JSON event per line on stderr, for scripts and GUIs, e.g.\n\
+                     {\"event\":\"download\",\"bytes\":1234,\"total\":56789}\n\
+                     {\"event\":\"extract\",\"file\":\"lib/std/std.zig\"}\n\
+                     {\"event\":\"done\",\"message\":\"Extraction complete\"}"
+    )]
+    pub progress: ProgressFormat,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Initialize a new Zig project from lean or standard zig template
@@ -137,15 +357,18 @@ pub enum Commands {
         /// With --zls, download prebuilt ZLS instead of building from source
         #[arg(long, short = 'd', requires = "zls")]
         download: bool,
+        #[command(flatten)]
+        output: OutputArgs,
         /// Version(s) of Zig to install (comma-separated for multiple versions)
         #[arg(
             value_delimiter = ',',
             value_parser = clap::value_parser!(ZigVersion),
             help = "The version(s) of Zig to install. Use 'master', 'stable@<version>', 'stable', 'latest', or simply <version> (e.g., '0.15.1'). Multiple versions can be comma-separated.",
             long_help = "The version(s) of Zig to install. Options:\n\
-                         • master             - Install master branch build\n\
+                         • master             - Install master branch build (aliases: nightly, dev, trunk)\n\
                          • <semver>           - Install specific version (e.g., 0.13.0, 1.2.3)\n\
-                         • stable@<version>   - Install specific stable version. Identical to just <version> (e.g., stable@0.13.0)\n\
+                         • stable@<semver>    - Install specific stable version. Identical to just <semver> (e.g., stable@0.13.0)\n\
+                         • stable@<major.minor> - Install the newest stable patch release of a series (e.g., stable@0.14 picks the newest 0.14.x, while a bare 0.14 means 0.14.0)\n\
                          • stable             - Install latest stable release\n\
                          • latest             - Install latest stable release (queries network instead of relying on cached index)\n\
                          Multiple versions can be specified as comma-separated values."
@@ -162,26 +385,209 @@ pub enum Commands {
             long_help = "Force using ziglang.org as a download source. Default is to use community mirrors."
         )]
         force_ziglang: bool,
+        /// Download and extract the version again even if it is already installed
+        #[arg(
+            long,
+            help = "Download and extract the version again even if the same build is already installed"
+        )]
+        reinstall: bool,
         /// Also provision matching ZLS for the selected Zig version
         #[arg(long)]
         zls: bool,
+        /// Fetch the source tarball into ZV_DIR/sources/<version> instead of a prebuilt Zig
+        #[arg(
+            long,
+            conflicts_with = "zls",
+            help = "Download and extract the source tarball for bootstrapping instead of a prebuilt Zig. The active version is not changed"
+        )]
+        source: bool,
         /// With --zls, download prebuilt ZLS instead of building from source
         #[arg(long, short = 'd', requires = "zls")]
         download: bool,
+        /// Use the latest release of a channel when no version is given
+        #[arg(
+            long,
+            value_enum,
+            help = "Use the latest release of a channel (stable = latest, master = master). An explicit version overrides this"
+        )]
+        channel: Option<Channel>,
+        /// Let `latest` pick release candidates too
+        #[arg(
+            long,
+            help = "With 'latest', pick the highest version in the index including pre-releases (master is still excluded). Rejected with any other version"
+        )]
+        prerelease: bool,
+        #[command(flatten)]
+        output: OutputArgs,
         /// Version of Zig to use
         #[arg(
-            value_parser = clap::value_parser!(ZigVersion),
-            help = "The version of Zig to use. Use 'master', 'stable@<version>', 'stable', 'latest', or simply <version> (e.g., '0.15.1')",
+            value_parser = parse_use_target,
+            help = "The version of Zig to use. Use 'master', 'stable@<version>', 'stable', 'latest', 'system', or simply <version> (e.g., '0.15.1')",
             long_help = "The version of Zig to use. Options:\n\
-                         • master             - Use master branch build\n\
+                         • master             - Use master branch build (aliases: nightly, dev, trunk)\n\
                          • <semver>           - Use specific version (e.g., 0.13.0, 1.2.3)\n\
-                         • stable@<version>   - Use specific stable version. Identical to just <version> (e.g., stable@0.13.0)\n\
+                         • stable@<semver>    - Use specific stable version. Identical to just <semver> (e.g., stable@0.13.0)\n\
+                         • stable@<major.minor> - Use the newest stable patch release of a series (e.g., stable@0.14 picks the newest 0.14.x, while a bare 0.14 means 0.14.0)\n\
                          • stable             - Use latest stable release\n\
-                         • latest             - Use latest stable release (queries network instead of relying on cached index)"
+                         • latest             - Use latest stable release (queries network instead of relying on cached index)\n\
+                         • system             - Use a zig already on PATH that is not managed by zv\n\
+                         • -                  - Read the version from a single line on stdin\n\
+                         Without a version or --channel, ZV_DEFAULT_VERSION is used if set."
+        )]
+        version: Option<UseTarget>,
+    },
+
+    /// Pin a global default Zig version, used by the zig shim when no version is active
+    Default {
+        /// Forget the global default
+        #[arg(long, conflicts_with = "version")]
+        unset: bool,
+        /// Version to record as the global default
+        #[arg(
+            help = "Version to pin globally, e.g. 0.14.1, stable or master. Prints the current default if omitted",
+            long_help = "Version to pin globally, e.g. 0.14.1, stable or master. Prints the current default if omitted.\n\
+                         The zig shim picks a version in this order:\n\
+                         • +<version> as the first argument to zig\n\
+                         • the ZIG_VERSION environment variable\n\
+                         • a .zigversion file beside the nearest build.zig\n\
+                         • the active version set by `zv use`\n\
+                         • the ZV_DEFAULT_VERSION environment variable\n\
+                         • this global default"
+        )]
+        version: Option<String>,
+    },
+
+    /// Run a command with an installed Zig version first on PATH
+    Exec {
+        /// Installed Zig version to run the command with
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "Installed Zig version to use. 'master', 'stable' and 'latest' pick the newest matching install",
+            long_help = "Installed Zig version to use. No download is attempted. Options:\n\
+                         • <semver>           - Use a specific installed version (e.g., 0.13.0)\n\
+                         • master             - Use the newest installed master build\n\
+                         • stable | latest    - Use the newest installed stable release"
+        )]
+        version: ZigVersion,
+        /// Command to run, separated from zv's arguments by `--`
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Package an installed Zig version into a portable archive for offline use or an internal mirror
+    Export {
+        /// Installed Zig version to export
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "Installed Zig version to export. 'master', 'stable' and 'latest' pick the newest matching install"
+        )]
+        version: ZigVersion,
+        /// Archive to write
+        #[arg(
+            long,
+            short = 'o',
+            help = "Archive to write (tar.xz on Unix, zip on Windows). Defaults to the official archive name in the current directory"
+        )]
+        output: Option<PathBuf>,
+        /// Minisign secret key to sign the archive with
+        #[arg(
+            long,
+            value_name = "SECRET_KEY",
+            help = "Sign the archive with this minisign secret key, writing <archive>.minisig (requires the minisign CLI)"
+        )]
+        sign: Option<PathBuf>,
+    },
+
+    /// Check installed Zig versions against the checksums recorded when they were installed
+    Verify {
+        /// Installed version to check; all of them when omitted
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "Installed Zig version to check. Checks every installed version when omitted"
         )]
         version: Option<ZigVersion>,
     },
 
+    /// Download and verify a Zig version's source tarball without extracting it
+    #[clap(name = "fetch-src")]
+    FetchSrc {
+        /// Version whose sources to fetch
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "The Zig version whose source tarball to fetch, e.g. 0.13.0, stable or master"
+        )]
+        version: ZigVersion,
+        /// Directory to write the tarball and its signature to
+        #[arg(
+            long,
+            short = 'o',
+            value_name = "DIR",
+            help = "Directory to write the tarball and its .minisig to. Defaults to the downloads cache"
+        )]
+        out: Option<PathBuf>,
+        /// Force using ziglang.org as a download source. Default is to use community mirrors.
+        #[arg(
+            long = "force-ziglang",
+            short = 'f',
+            long_help = "Force using ziglang.org as a download source. Default is to use community mirrors."
+        )]
+        force_ziglang: bool,
+    },
+
+    /// Show where a Zig version's release notes are, optionally opening or fetching them
+    Changelog {
+        /// Version whose release notes to show
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "The Zig version whose release notes to show, e.g. 0.14.0, stable or master"
+        )]
+        version: ZigVersion,
+        /// Open the release notes in the default browser
+        #[arg(long, conflicts_with = "fetch")]
+        open: bool,
+        /// Download the release notes and page them as plain text
+        #[arg(long)]
+        fetch: bool,
+    },
+
+    /// Show what the index lists for a Zig version and whether it is installed
+    Info {
+        /// Version to describe
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "The Zig version to describe, e.g. 0.14.0, stable or master"
+        )]
+        version: ZigVersion,
+        /// Print the details as JSON
+        #[arg(
+            long = "json",
+            help = "Print the details as a JSON object",
+            long_help = "Print the details as a JSON object with version, is_master, date, installed \
+                         (path or null), host, tarball, size, shasum, targets and notes"
+        )]
+        json: bool,
+    },
+
+    /// Install a portable Zig archive, e.g. one made by `zv export`, without network access
+    Import {
+        /// Archive to install
+        #[arg(
+            help = "Zig archive to install (.tar.xz, .tar.gz or .zip), e.g. written by `zv export`"
+        )]
+        archive: PathBuf,
+        /// Version to register the archive as
+        #[arg(
+            long,
+            value_name = "SEMVER",
+            value_parser = clap::value_parser!(semver::Version),
+            help = "Zig version the archive contains, e.g. 0.14.1 or 0.15.0-dev.1034+abc (archives carry no version metadata)"
+        )]
+        version: semver::Version,
+        /// Register the archive as a master build
+        #[arg(long, help = "Register the archive as a master build")]
+        master: bool,
+    },
+
     /// List installed Zig versions
     #[clap(name = "list", alias = "ls")]
     List {
@@ -202,9 +608,61 @@ pub enum Commands {
         #[arg(
             long = "refresh",
             short = 'r',
-            help = "Force refresh mirrors and/or index from network (only affects -a/--all and -m/--mirrors)"
+            help = "Force refresh mirrors and/or index from network (only affects -a/--all, -m/--mirrors and --stale)"
         )]
         refresh: bool,
+        /// Print installed versions as JSON
+        #[arg(
+            long = "json",
+            conflicts_with_all = ["all", "mirrors"],
+            conflicts_with = "compact",
+            help = "Print installed versions as a JSON array",
+            long_help = "Print installed versions as a JSON array of objects with \
+                         version, is_master, is_active, path and installed_at (RFC 3339, null if unknown)"
+        )]
+        json: bool,
+        /// Print one installed version per line
+        #[arg(
+            long = "compact",
+            conflicts_with_all = ["all", "mirrors"],
+            help = "Print one installed version per line, '*' marking the active one",
+            long_help = "Print one installed version per line for shell scripts: \
+                         '* 0.13.0' for the active version, '  0.12.0' otherwise and \
+                         'master/<version>' for master builds"
+        )]
+        compact: bool,
+        /// Order of installed versions
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = ListSort::Version,
+            conflicts_with_all = ["all", "mirrors"],
+            help = "Order installed versions by version, install date or size on disk"
+        )]
+        sort: ListSort,
+        /// Only list master builds
+        #[arg(long, conflicts_with_all = ["all", "mirrors", "stable_only"])]
+        master_only: bool,
+        /// Only list stable (non-master) versions
+        #[arg(long, conflicts_with_all = ["all", "mirrors"])]
+        stable_only: bool,
+        /// Only list installs the index has moved past
+        #[arg(
+            long,
+            value_name = "MINORS",
+            num_args = 0..=1,
+            default_missing_value = "1",
+            conflicts_with_all = ["all", "mirrors"],
+            help = "Only list stale installs and why: stable versions more than MINORS (default 1) minor versions behind the latest, or master builds older than the current master",
+            long_help = "Only list installs the index has moved past, each with the reason:\n\
+                         • stable versions more than MINORS (default 1) minor versions behind the latest stable\n\
+                         • master builds older than the current master\n\
+                         Pair with `zv clean` and `zv prune` to remove them."
+        )]
+        stale: Option<u64>,
+        /// Disable ANSI colors even when stdout is a TTY
+        #[arg(long)]
+        no_color: bool,
     },
 
     /// Clean up Zig installations. Non-zv managed installations will not be affected.
@@ -215,10 +673,12 @@ pub enum Commands {
             long = "except",
             value_delimiter = ',',
             value_parser = clap::value_parser!(ZigVersion),
+            conflicts_with = "targets",
             help = "Clean all except specified versions (comma-separated)",
             long_help = "Clean all installed versions except the ones specified.\n\
                          Accepts comma-separated list of versions.\n\
-                         Examples: --except 0.13.0,0.14.0 or --except master"
+                         Examples: --except 0.13.0,0.14.0 or --except master\n\
+                         Cannot be combined with a target; pass the versions to keep here instead."
         )]
         except: Vec<ZigVersion>,
 
@@ -232,21 +692,71 @@ pub enum Commands {
         )]
         outdated: bool,
 
-        /// Target to clean: 'all', 'downloads', version(s), or 'master'
+        /// Only remove cached downloads older than this age
+        #[arg(
+            long = "older-than",
+            value_parser = tools::parse_duration,
+            requires = "targets",
+            conflicts_with_all = ["except", "outdated"],
+            help = "With 'downloads', only remove cached files older than this (e.g. 12h, 14d, 2w)"
+        )]
+        older_than: Option<std::time::Duration>,
+
+        /// Pick installed versions and caches to remove from a checklist
+        #[arg(
+            long,
+            short = 'i',
+            conflicts_with_all = ["targets", "except", "outdated", "older_than"],
+            help = "Pick installed versions (with sizes) and the downloads cache to remove from a checklist (requires a terminal)"
+        )]
+        interactive: bool,
+
+        /// Target to clean: 'all', 'downloads', 'zls', version(s), or 'master'
         #[arg(
 
             value_parser = parse_clean_target,
-            help = "What to clean: 'all', 'downloads', version(s), or omit for all",
+            help = "What to clean: 'all', 'downloads', 'zls', version(s), or omit to pick interactively",
             long_help = "Specify what to clean:\n\
                          • all          - Clean everything\n\
                          • downloads    - Clean downloads directory only\n\
+                         • zls          - Clean cached ZLS builds and the ZLS source checkout\n\
                          • <version>    - Clean specific version (e.g., 0.13.0, master)\n\
                          • <v1,v2,...>  - Clean multiple versions (comma-separated)\n\
-                         • master       - Clean all master versions (use with --outdated to keep latest)"
+                         • master       - Clean all master versions (use with --outdated to keep latest)\n\
+                         With no target, pick what to remove from a checklist (requires a terminal)."
         )]
         targets: Vec<CleanTarget>,
     },
 
+    /// Remove all but the N newest stable versions and the newest master build
+    Prune {
+        /// Number of newest stable versions to keep
+        #[arg(
+            long,
+            short = 'k',
+            help = "Number of newest stable versions to keep",
+            long_help = "Number of newest stable versions to keep.\n\
+                         The newest master build and the active version are always kept."
+        )]
+        keep: usize,
+        /// Show what would be removed without deleting anything
+        #[arg(
+            long,
+            help = "Preview removals and reclaimed space without deleting anything"
+        )]
+        dry_run: bool,
+    },
+
+    /// Start a subshell with an installed Zig version first on PATH, without changing the active version
+    Shell {
+        /// Installed Zig version to use inside the subshell
+        #[arg(
+            value_parser = clap::value_parser!(ZigVersion),
+            help = "Installed Zig version to use. 'master', 'stable' and 'latest' pick the newest matching install"
+        )]
+        version: ZigVersion,
+    },
+
     /// Setup shell environment for zv (required to make zig binaries available in $PATH)
     ///
     /// Interactive mode is enabled by default, providing clear prompts about system changes.
@@ -261,15 +771,13 @@ pub enum Commands {
             help = "Preview changes without applying them"
         )]
         dry_run: bool,
-        /// Disable interactive prompts and use default choices for automation
+        /// Shell startup file to add zv to, instead of the one picked for your shell
         #[arg(
-            long = "no-interactive",
-            help = "Disable interactive prompts and use default choices for automation",
-            long_help = "Disable interactive prompts and use default choices for automation.\n\
-                         Interactive mode is automatically disabled in CI environments,\n\
-                         when TERM=dumb, or when TTY is not available."
+            long = "rc-file",
+            value_name = "PATH",
+            help = "Shell startup file to add zv to, overriding the default choice. An error where setup edits no startup file (Linux, XDG layouts on macOS, Windows shells)"
         )]
-        no_interactive: bool,
+        rc_file: Option<PathBuf>,
     },
     /// Update zv to using Github releases.
     #[clap(alias = "upgrade")]
@@ -284,7 +792,48 @@ pub enum Commands {
         rc: bool,
     },
     /// Synchronize index, mirrors list and metadata for zv. Also replaces `ZV_DIR/bin/zv` if outdated against current invocation.
-    Sync,
+    Sync {
+        #[arg(
+            long = "add-mirror",
+            value_name = "URL",
+            help = "Add a community mirror by URL after checking that it is reachable"
+        )]
+        add_mirror: Option<String>,
+        #[arg(
+            long = "dump-index",
+            value_name = "PATH",
+            help = "Also write the upstream index.json, as fetched, to PATH"
+        )]
+        dump_index: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Rescan installed versions instead of trusting the cached installations.toml"
+        )]
+        rescan: bool,
+        #[arg(
+            long = "allow-downgrade",
+            help = "Replace a newer ZV_DIR/bin/zv with this binary without asking"
+        )]
+        allow_downgrade: bool,
+    },
+
+    /// Read or change persistent zv settings stored in zv.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Inspect or clear the download, index and mirrors caches
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Inspect the community mirrors zv downloads from
+    Mirrors {
+        #[command(subcommand)]
+        action: MirrorsAction,
+    },
 
     /// Show files, folders and disk usage managed by zv on this system
     Stats {
@@ -299,6 +848,9 @@ pub enum Commands {
         no_color: bool,
     },
 
+    /// Print the directory holding the zv, zig and zls shims, e.g. for `export PATH="$(zv bin):$PATH"`
+    Bin,
+
     /// Uninstall zv and remove all installed Zig versions
     Uninstall,
 
@@ -316,8 +868,61 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Set a configuration value
+    Set {
+        /// Setting to change
+        #[arg(
+            help = "Setting to change",
+            long_help = "Setting to change. Supported keys:\n\
+                         • mirror.priority <url> <rank> - Pin a community mirror's rank (1 = most preferred)"
+        )]
+        key: String,
+        /// Value(s) for the setting
+        #[arg(required = true, num_args = 1..)]
+        values: Vec<String>,
+    },
+    /// Open zv.toml in $VISUAL/$EDITOR and validate it afterwards
+    Edit,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Remove the cached index, mirrors list and downloads
+    Clear,
+    /// List cached files with their size and age
+    List,
+    /// Show the size of each cache and how long until it is refreshed
+    Stats,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MirrorsAction {
+    /// Show download successes, failures and bytes served per mirror
+    Stats {
+        /// Emit machine-readable JSON instead of a table
+        #[arg(long, conflicts_with = "reset")]
+        json: bool,
+        /// Clear the recorded history of every mirror
+        #[arg(long)]
+        reset: bool,
+    },
+}
+
 impl Commands {
-    pub(crate) async fn execute(self, mut app: App, using_env: bool) -> super::Result<()> {
+    pub(crate) async fn execute(
+        self,
+        mut app: App,
+        using_env: bool,
+        global: GlobalArgs,
+    ) -> super::Result<()> {
+        app.toolchain_manager.set_force_shims(global.force_shims);
+        if let Some(secs) = global.timeout {
+            app.set_timeout(secs);
+        }
+        crate::app::utils::set_json_progress(global.progress == ProgressFormat::Json);
+        tools::set_no_interactive(global.no_interactive);
         match self {
             Commands::Init {
                 project_name,
@@ -325,12 +930,15 @@ impl Commands {
                 package: zon,
             } => {
                 if !app.is_initialized() {
-                    error(
-                        "zv is not initialized. Run 'zv sync' first to set up directories and the zv binary.",
+                    error("zv is not initialized");
+                    suggest!(
+                        "Set up its directories and the zv binary with {}",
+                        cmd = "zv sync"
                     );
                     std::process::exit(1);
                 }
                 use crate::{Template, TemplateType};
+                let project_name = project_name.map(init::offer_valid_name);
                 if zig {
                     init::init_project(
                         Template::new(
@@ -347,34 +955,79 @@ impl Commands {
                                 );
                                 eyre!("No Zig executable found")
                             })?),
-                        ),
+                        )
+                        .with_force(global.force),
                         app,
                     )
                     .await
                 } else {
-                    init::init_project(Template::new(project_name, TemplateType::App { zon }), app)
-                        .await
+                    init::init_project(
+                        Template::new(project_name, TemplateType::App { zon })
+                            .with_force(global.force),
+                        app,
+                    )
+                    .await
                 }
             }
             Commands::Use {
                 version,
                 force_ziglang,
+                reinstall,
                 zls,
+                source,
                 download,
+                channel,
+                prerelease,
+                output,
             } => {
+                crate::app::utils::set_quiet(global.quiet_output(output));
                 if !app.is_initialized() {
-                    error(
-                        "zv is not initialized. Run 'zv sync' first to set up directories and the zv binary.",
+                    error("zv is not initialized");
+                    suggest!(
+                        "Set up its directories and the zv binary with {}",
+                        cmd = "zv sync"
                     );
                     std::process::exit(1);
                 }
-                match version {
-                    Some(version) => {
-                        r#use::use_version(version, &mut app, force_ziglang, zls, download).await
+                // An explicit version always wins over --channel, which wins over ZV_DEFAULT_VERSION
+                let target = match (version, channel) {
+                    (Some(UseTarget::Stdin), _) => Some(r#use::use_target_from_stdin()?),
+                    (Some(target), _) => Some(target),
+                    (None, Some(channel)) => Some(UseTarget::Version(channel.into())),
+                    (None, None) => r#use::use_target_from_env()?,
+                };
+                if prerelease
+                    && !matches!(target, Some(UseTarget::Version(ZigVersion::Latest(None))))
+                {
+                    error("--prerelease only applies to `zv use latest` (or `--channel stable`)");
+                    std::process::exit(2);
+                }
+                match target {
+                    Some(UseTarget::Version(version)) if source => {
+                        r#use::use_source(version, &mut app, force_ziglang, reinstall, prerelease)
+                            .await
+                    }
+                    Some(UseTarget::System) if source => {
+                        error("A system zig has no source tarball to fetch");
+                        std::process::exit(2);
+                    }
+                    Some(UseTarget::Version(version)) => {
+                        r#use::use_version(
+                            version,
+                            &mut app,
+                            force_ziglang,
+                            reinstall,
+                            zls,
+                            download,
+                            prerelease,
+                        )
+                        .await
                     }
+                    Some(UseTarget::System) => r#use::use_system(&mut app).await,
+                    Some(UseTarget::Stdin) => unreachable!("stdin is read above"),
                     None => {
                         error(
-                            "Version must be specified. e.g., `zv use latest` or `zv use 0.15.1`",
+                            "Version must be specified. e.g., `zv use latest`, `zv use --channel master` or `zv use 0.15.1`, or set ZV_DEFAULT_VERSION",
                         );
                         std::process::exit(2);
                     }
@@ -385,42 +1038,143 @@ impl Commands {
                 force_ziglang,
                 zls,
                 download,
+                output,
             } => {
+                crate::app::utils::set_quiet(global.quiet_output(output));
                 if !app.is_initialized() {
-                    error(
-                        "zv is not initialized. Run 'zv sync' first to set up directories and the zv binary.",
+                    error("zv is not initialized");
+                    suggest!(
+                        "Set up its directories and the zv binary with {}",
+                        cmd = "zv sync"
                     );
                     std::process::exit(1);
                 }
                 install::install_versions(versions, &mut app, force_ziglang, zls, download).await
             }
+            Commands::Exec { version, command } => exec::exec(&app, version, command).await,
+            Commands::Export {
+                version,
+                output,
+                sign,
+            } => export::export(&app, version, output, sign).await,
+            Commands::Verify { version } => verify::verify(&mut app, version).await,
+            Commands::FetchSrc {
+                version,
+                out,
+                force_ziglang,
+            } => fetch_src::fetch_src(&mut app, version, out.as_deref(), force_ziglang).await,
+            Commands::Changelog {
+                version,
+                open,
+                fetch,
+            } => changelog::changelog(&mut app, version, open, fetch).await,
+            Commands::Info { version, json } => info::info(&mut app, version, json).await,
+            Commands::Import {
+                archive,
+                version,
+                master,
+            } => import::import(&mut app, &archive, &version, master).await,
             Commands::List {
                 all,
                 mirrors,
                 refresh,
-            } => list::list_opts(app, all, mirrors, refresh).await,
+                json,
+                compact,
+                sort,
+                master_only,
+                stable_only,
+                stale,
+                no_color,
+            } => {
+                let listing = list::InstalledListing {
+                    json,
+                    compact,
+                    sort,
+                    master_only,
+                    stable_only,
+                    stale: stale.map(list::StaleCheck::behind),
+                };
+                list::list_opts(app, all, mirrors, refresh, listing, no_color).await
+            }
             Commands::Clean {
                 except,
                 outdated,
+                older_than,
+                interactive,
                 targets,
-            } => clean::clean(&mut app, targets, except, outdated).await,
-            Commands::Setup {
-                dry_run,
-                no_interactive,
-            } => setup::setup_shell(&mut app, using_env, dry_run, no_interactive).await,
+            } => {
+                clean::clean(
+                    &mut app,
+                    targets,
+                    except,
+                    outdated,
+                    older_than,
+                    interactive,
+                    global.force,
+                )
+                .await
+            }
+            Commands::Shell { version } => shell::shell(&app, version, global.allow_shell).await,
+            Commands::Prune { keep, dry_run } => clean::prune(&mut app, keep, dry_run).await,
+            Commands::Setup { dry_run, rc_file } => {
+                setup::setup_shell(
+                    &mut app,
+                    using_env,
+                    dry_run,
+                    global.no_interactive,
+                    global.force,
+                    rc_file,
+                )
+                .await
+            }
             Commands::Stats {
                 verbose,
                 json,
                 no_color,
-            } => stats::run(&app, verbose, json, no_color).await,
-            Commands::Sync => sync::sync(&mut app).await,
+            } => stats::run(&mut app, verbose, json, no_color).await,
+            Commands::Sync {
+                add_mirror,
+                dump_index,
+                rescan,
+                allow_downgrade,
+            } => {
+                sync::sync(
+                    &mut app,
+                    allow_downgrade,
+                    add_mirror.as_deref(),
+                    dump_index.as_deref(),
+                    rescan,
+                )
+                .await
+            }
+            Commands::Config { action } => match action {
+                ConfigAction::Set { key, values } => config::set(&mut app, &key, &values),
+                ConfigAction::Edit => config::edit(&app),
+            },
+            Commands::Cache { action } => match action {
+                CacheAction::Clear => cache::clear(&mut app).await,
+                CacheAction::List => cache::list(&app),
+                CacheAction::Stats => cache::stats(&mut app).await,
+            },
+            Commands::Default { version, unset } => {
+                default::default(&app, version.as_deref(), unset)
+            }
+            Commands::Mirrors { action } => match action {
+                MirrorsAction::Stats { json, reset } => mirrors::stats(&mut app, json, reset).await,
+            },
+            Commands::Bin => {
+                println!("{}", app.bin_path().display());
+                Ok(())
+            }
             Commands::Uninstall => uninstall::uninstall(&mut app).await,
-            Commands::Update { force, rc } => update::update_zv(&mut app, force, rc).await,
+            Commands::Update { force, rc } => {
+                update::update_zv(&mut app, force || global.force, rc).await
+            }
             Commands::Zls {
                 download,
                 force,
                 update,
-            } => zls_cmd::provision_zls(&mut app, download, force, update).await,
+            } => zls_cmd::provision_zls(&mut app, download, force || global.force, update).await,
         }
     }
 }
@@ -445,7 +1199,7 @@ fn zv_line_with_color(line: &str, color: yansi::Color) -> String {
 fn zv_status_line(app: &App) -> String {
     use crate::shell::path_utils::check_dir_in_path;
 
-    if app.source_set {
+    if app.is_setup() {
         return Paint::green("✔ Ready to Use").to_string();
     }
 
@@ -586,16 +1340,25 @@ fn print_welcome_message(app: App) {
     let active_zig_str = active_zig
         .as_ref()
         .map_or_else(|| "none".to_string(), |v| v.to_string());
-    let help_text = if active_zig.is_none() {
-        format!(
+    let help_text = match (&active_zig, app.is_setup()) {
+        (Some(_), _) => String::new(),
+        (None, true) => format!(" (use {} to set one)", Paint::blue("zv use <version>")),
+        (None, false) => format!(
             " (use {} to set one | or run {} to get started)",
             Paint::blue("zv use <version>"),
             Paint::blue("zv setup")
-        )
-    } else {
-        String::new()
+        ),
     };
 
+    if !app.is_setup() {
+        println!(
+            "{} {}",
+            Paint::yellow("⚠ zv is not on your PATH yet:").bold(),
+            zv_status_line(&app)
+        );
+        println!();
+    }
+
     println!(
         "Current active Zig: {}{}",
         Paint::yellow(&active_zig_str),
@@ -627,11 +1390,51 @@ fn print_welcome_message(app: App) {
         "use",
         "Select which Zig version to use - master | latest | stable | <semver>",
     );
+    print_command(
+        "default",
+        "Pin a global default Zig version for when none is active - zv default <version> | --unset",
+    );
+    print_command(
+        "export",
+        "Package an installed Zig version into a portable archive - zv export <version> [-o <file>] [--sign <key>]",
+    );
+    print_command(
+        "verify",
+        "Check installed Zig versions against the checksums recorded at install - zv verify [version]",
+    );
+    print_command(
+        "fetch-src",
+        "Download and verify a version's source tarball without extracting it - zv fetch-src <version> [-o <dir>]",
+    );
+    print_command(
+        "changelog",
+        "Show the release notes link for a version - zv changelog <version> [--open | --fetch]",
+    );
+    print_command(
+        "info",
+        "Show a version's release date, targets, host archive and install status - zv info <version> [--json]",
+    );
+    print_command(
+        "import",
+        "Install a portable Zig archive without network access - zv import <archive> --version <semver> [--master]",
+    );
     print_command("list  | ls", "List installed Zig versions");
+    print_command(
+        "exec",
+        "Run a command with an installed Zig version on PATH - zv exec <version> -- <cmd>",
+    );
     print_command(
         "clean | rm",
         "Clean up Zig installations. Non-zv managed installations will not be affected",
     );
+    print_command(
+        "prune",
+        "Keep only the N newest stable versions and newest master - zv prune --keep N",
+    );
+    print_command(
+        "shell",
+        "Start a subshell with an installed Zig version on PATH - zv shell <version>",
+    );
     print_command(
         "setup",
         "Setup shell environment for zv with interactive prompts (use --no-interactive to disable)",
@@ -640,11 +1443,27 @@ fn print_welcome_message(app: App) {
         "sync",
         "Synchronize index, mirrors list and metadata for zv",
     );
+    print_command(
+        "config",
+        "Edit or change persistent zv settings - zv config edit | set <key> <value>",
+    );
     print_command("zls", "Provision ZLS for the currently active Zig version");
+    print_command(
+        "cache",
+        "Inspect or clear the download, index and mirrors caches - zv cache list | stats | clear",
+    );
+    print_command(
+        "mirrors",
+        "Show per-mirror download history - zv mirrors stats [--json | --reset]",
+    );
     print_command(
         "stats",
         "Show files, folders and disk usage managed by zv on this system",
     );
+    print_command(
+        "bin",
+        "Print the directory holding the zv, zig and zls shims",
+    );
     print_command(
         "uninstall",
         "Uninstall zv and remove all installed Zig versions",
@@ -680,3 +1499,395 @@ fn get_random_color_scheme() -> (yansi::Color, yansi::Color) {
     let mut rng = rand::rng();
     schemes[rng.random_range(0..schemes.len())]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_parses_named_targets_and_version_lists() {
+        let cli = ZvCli::try_parse_from(["zv", "clean", "zls", "0.13.0,master"]).unwrap();
+        let Some(Commands::Clean { targets, .. }) = cli.command else {
+            panic!("expected the clean command");
+        };
+        assert!(matches!(targets[0], CleanTarget::Zls));
+        assert!(matches!(&targets[1], CleanTarget::Versions(v) if v.len() == 2));
+    }
+
+    #[test]
+    fn clean_older_than_needs_a_target_and_no_other_mode() {
+        let parse = |args: &[&str]| ZvCli::try_parse_from([&["zv", "clean"], args].concat());
+        assert!(parse(&["downloads", "--older-than", "14d"]).is_ok());
+        assert!(parse(&["--older-than", "14d"]).is_err());
+        assert!(parse(&["--outdated", "--older-than", "14d"]).is_err());
+        assert!(parse(&["--except", "0.13.0", "--older-than", "14d"]).is_err());
+    }
+
+    #[test]
+    fn every_zv_error_suggests_a_command_or_setting() {
+        let errors = [
+            crate::ZvError::ZvBinPathNotFound { context: None },
+            crate::ZvError::NetworkError(crate::NetErr::EmptyMirrors),
+            crate::ZvError::NetworkError(crate::NetErr::TooManyRetries { attempts: 3 }),
+            crate::ZvError::ZigNotFound(eyre!("no zig")),
+            crate::ZvError::ShimConflict {
+                path: PathBuf::from("zig"),
+                context: None,
+            },
+            crate::ZvError::General(eyre!("anything")),
+        ];
+        for error in errors {
+            let suggestion = error.recovery_suggestion();
+            assert!(suggestion.contains('`'), "{error:?}: {suggestion}");
+        }
+    }
+
+    #[test]
+    fn empty_mirrors_is_found_through_wrapped_errors() {
+        use color_eyre::eyre::WrapErr;
+
+        let wrapped = Err::<(), _>(crate::ZvError::NetworkError(crate::NetErr::EmptyMirrors))
+            .wrap_err("Failed to download and install Zig version 0.15.1")
+            .unwrap_err();
+        assert!(is_empty_mirrors(&wrapped));
+        let with_context = crate::ZvError::NetworkError(crate::NetErr::EmptyMirrors)
+            .with_context("downloading 0.15.1");
+        assert!(is_empty_mirrors(&color_eyre::Report::new(with_context)));
+        assert!(!is_empty_mirrors(&eyre!("No valid mirrors found")));
+    }
+
+    #[test]
+    fn use_source_excludes_zls() {
+        let cli = ZvCli::try_parse_from(["zv", "use", "0.14.1", "--source"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Use {
+                source: true,
+                zls: false,
+                ..
+            })
+        ));
+        let err = ZvCli::try_parse_from(["zv", "use", "0.14.1", "--source", "--zls"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn clean_rejects_except_with_explicit_targets() {
+        let err =
+            ZvCli::try_parse_from(["zv", "clean", "--except", "0.14.0", "0.13.0"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+        assert!(ZvCli::try_parse_from(["zv", "clean", "--except", "0.14.0,master"]).is_ok());
+    }
+
+    #[test]
+    fn no_interactive_is_global() {
+        for args in [
+            &["zv", "--no-interactive", "clean", "all"][..],
+            &["zv", "setup", "--no-interactive"],
+        ] {
+            assert!(ZvCli::try_parse_from(args).unwrap().global.no_interactive);
+        }
+        assert!(
+            !ZvCli::try_parse_from(["zv", "setup"])
+                .unwrap()
+                .global
+                .no_interactive
+        );
+    }
+
+    #[test]
+    fn global_flags_precede_the_subcommand() {
+        let cli = ZvCli::try_parse_from(["zv", "--force", "--allow-shell", "clean"]).unwrap();
+        assert!(cli.global.force && cli.global.allow_shell);
+        assert!(matches!(cli.command, Some(Commands::Clean { .. })));
+
+        // `zv update --force` keeps its own flag
+        let cli = ZvCli::try_parse_from(["zv", "update", "--force"]).unwrap();
+        assert!(!cli.global.force);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update { force: true, .. })
+        ));
+    }
+
+    #[test]
+    fn force_shims_is_accepted_after_the_subcommand() {
+        let cli = ZvCli::try_parse_from(["zv", "use", "0.15.1", "--force-shims"]).unwrap();
+        assert!(cli.global.force_shims);
+        assert!(matches!(cli.command, Some(Commands::Use { .. })));
+    }
+
+    #[test]
+    fn import_requires_explicit_version() {
+        let cli = ZvCli::try_parse_from([
+            "zv",
+            "import",
+            "zig.tar.xz",
+            "--version",
+            "0.16.0-dev.42+abc",
+            "--master",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Import { archive, version, master: true })
+                if archive == std::path::Path::new("zig.tar.xz") && version.to_string() == "0.16.0-dev.42+abc"
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "import", "zig.tar.xz"]).is_err());
+        assert!(
+            ZvCli::try_parse_from(["zv", "import", "zig.tar.xz", "--version", "stable"]).is_err()
+        );
+    }
+
+    #[test]
+    fn clean_interactive_takes_no_targets() {
+        let cli = ZvCli::try_parse_from(["zv", "clean", "-i"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Clean { interactive: true, ref targets, .. }) if targets.is_empty()
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "clean", "--interactive", "0.13.0"]).is_err());
+        assert!(
+            ZvCli::try_parse_from(["zv", "clean", "--interactive", "--except", "0.13.0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn fetch_src_takes_a_version_and_an_optional_out_dir() {
+        let cli = ZvCli::try_parse_from(["zv", "fetch-src", "0.13.0", "--out", "vendor"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::FetchSrc { version, out: Some(out), force_ziglang: false })
+                if version.to_string() == "0.13.0" && out == std::path::Path::new("vendor")
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "fetch-src"]).is_err());
+    }
+
+    #[test]
+    fn changelog_opens_or_fetches_but_not_both() {
+        let cli = ZvCli::try_parse_from(["zv", "changelog", "0.14.0", "--fetch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Changelog { version, open: false, fetch: true })
+                if version.to_string() == "0.14.0"
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "changelog", "master", "--open", "--fetch"]).is_err());
+    }
+
+    #[test]
+    fn quiet_is_implied_by_json_progress_and_overridden_by_verbose() {
+        let quiet_of = |args: &[&str]| {
+            let cli = ZvCli::try_parse_from(args).unwrap();
+            match cli.command {
+                Some(Commands::Use { output, .. }) | Some(Commands::Install { output, .. }) => {
+                    cli.global.quiet_output(output)
+                }
+                _ => panic!("expected use or install"),
+            }
+        };
+        assert!(!quiet_of(&["zv", "use", "0.14.1"]));
+        assert!(quiet_of(&["zv", "use", "0.14.1", "-q"]));
+        assert!(quiet_of(&["zv", "install", "0.14.1", "--progress", "json"]));
+        assert!(!quiet_of(&[
+            "zv",
+            "use",
+            "0.14.1",
+            "--progress",
+            "json",
+            "-v"
+        ]));
+        assert!(!quiet_of(&["zv", "use", "0.14.1", "-q", "-v"]));
+        assert!(quiet_of(&["zv", "use", "0.14.1", "-v", "--quiet"]));
+    }
+
+    #[test]
+    fn info_takes_a_version_and_json() {
+        let cli = ZvCli::try_parse_from(["zv", "info", "stable", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Info {
+                version: ZigVersion::Stable(None),
+                json: true
+            })
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "info"]).is_err());
+    }
+
+    #[test]
+    fn list_stale_takes_an_optional_minor_count() {
+        let stale_of = |args: &[&str]| match ZvCli::try_parse_from(args).unwrap().command {
+            Some(Commands::List { stale, .. }) => stale,
+            _ => panic!("expected list"),
+        };
+        assert_eq!(stale_of(&["zv", "list"]), None);
+        assert_eq!(stale_of(&["zv", "list", "--stale"]), Some(1));
+        assert_eq!(stale_of(&["zv", "list", "--stale", "3"]), Some(3));
+        assert!(ZvCli::try_parse_from(["zv", "list", "--stale", "--all"]).is_err());
+    }
+
+    #[test]
+    fn progress_format_is_global() {
+        let cli = ZvCli::try_parse_from(["zv", "install", "0.14.1", "--progress", "json"]).unwrap();
+        assert_eq!(cli.global.progress, ProgressFormat::Json);
+        let cli = ZvCli::try_parse_from(["zv", "sync"]).unwrap();
+        assert_eq!(cli.global.progress, ProgressFormat::Human);
+        assert!(ZvCli::try_parse_from(["zv", "--progress", "xml", "sync"]).is_err());
+    }
+
+    #[test]
+    fn timeout_applies_to_any_subcommand() {
+        let cli = ZvCli::try_parse_from(["zv", "sync", "--timeout", "60"]).unwrap();
+        assert_eq!(cli.global.timeout, Some(60));
+        assert!(ZvCli::try_parse_from(["zv", "--timeout", "0", "sync"]).is_err());
+    }
+
+    #[test]
+    fn sync_takes_an_optional_mirror_to_add() {
+        let cli = ZvCli::try_parse_from(["zv", "sync"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync {
+                add_mirror: None,
+                dump_index: None,
+                rescan: false,
+                allow_downgrade: false
+            })
+        ));
+        let cli = ZvCli::try_parse_from(["zv", "sync", "--add-mirror", "https://example.com/zig"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync { add_mirror: Some(url), .. }) if url == "https://example.com/zig"
+        ));
+        let cli = ZvCli::try_parse_from(["zv", "sync", "--dump-index", "index.json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync { dump_index: Some(path), .. }) if path == std::path::Path::new("index.json")
+        ));
+        let cli = ZvCli::try_parse_from(["zv", "sync", "--rescan"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync { rescan: true, .. })
+        ));
+        // --force takes the downgrade prompt's default (no); replacing a newer zv is explicit
+        let cli = ZvCli::try_parse_from(["zv", "--force", "sync"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync {
+                allow_downgrade: false,
+                ..
+            })
+        ));
+        let cli = ZvCli::try_parse_from(["zv", "sync", "--allow-downgrade"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync {
+                allow_downgrade: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn default_takes_a_version_or_unset() {
+        let cli = ZvCli::try_parse_from(["zv", "default", "0.14.1"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Default { version: Some(v), unset: false }) if v == "0.14.1"
+        ));
+        let cli = ZvCli::try_parse_from(["zv", "default", "--unset"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Default {
+                version: None,
+                unset: true
+            })
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "default", "0.14.1", "--unset"]).is_err());
+    }
+
+    #[test]
+    fn list_sort_and_filters_only_apply_to_installed_versions() {
+        let cli = ZvCli::try_parse_from(["zv", "list", "--sort", "size", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::List {
+                sort: ListSort::Size,
+                json: true,
+                ..
+            })
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "list", "--all"]).is_ok());
+        assert!(ZvCli::try_parse_from(["zv", "list", "--all", "--sort", "date"]).is_err());
+        assert!(ZvCli::try_parse_from(["zv", "list", "--master-only", "--stable-only"]).is_err());
+    }
+
+    #[test]
+    fn mirrors_stats_rejects_json_with_reset() {
+        let cli = ZvCli::try_parse_from(["zv", "mirrors", "stats", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Mirrors {
+                action: MirrorsAction::Stats {
+                    json: true,
+                    reset: false
+                }
+            })
+        ));
+        assert!(ZvCli::try_parse_from(["zv", "mirrors", "stats", "--json", "--reset"]).is_err());
+    }
+
+    #[test]
+    fn bin_takes_no_arguments() {
+        let cli = ZvCli::try_parse_from(["zv", "bin"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Bin)));
+        assert!(ZvCli::try_parse_from(["zv", "bin", "extra"]).is_err());
+    }
+
+    #[test]
+    fn use_accepts_dash_for_stdin() {
+        let cli = ZvCli::try_parse_from(["zv", "use", "-"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Use {
+                version: Some(UseTarget::Stdin),
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn setup_without_a_shell_is_an_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut app = App::init(UserConfig::with_no_shell(temp.path().to_path_buf()))
+            .await
+            .unwrap();
+        assert!(app.shell.is_none());
+        assert!(
+            setup::setup_shell(&mut app, true, true, true, false, None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn setup_rejects_rc_file_where_it_edits_no_startup_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut app = App::init(UserConfig::with_no_shell(temp.path().to_path_buf()))
+            .await
+            .unwrap();
+        app.shell = Some(Shell::default());
+        let rc_file = Some(temp.path().join(".bashrc"));
+
+        let err = setup::setup_shell(&mut app, false, true, true, false, rc_file)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--rc-file"), "{err}");
+        assert!(
+            setup::setup_shell(&mut app, false, true, true, false, None)
+                .await
+                .is_ok()
+        );
+    }
+}
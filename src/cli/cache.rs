@@ -0,0 +1,152 @@
+use super::stats::{format_hours, index_freshness};
+use crate::app::network::mirror::{MirrorsIndex, relative_time};
+use crate::tools::{dir_size, human_size};
+use crate::{App, Result};
+use std::path::Path;
+use std::time::Duration;
+use yansi::Paint;
+
+/// Remove the cached index, mirrors list and downloads (in-progress downloads in `tmp/` stay)
+pub async fn clear(app: &mut App) -> Result<()> {
+    let (removed, freed_bytes) = app.clear_all_caches().await?;
+    if removed == 0 {
+        println!("{}", "Cache is already empty.".italic());
+    } else {
+        println!(
+            "{} Removed {} cached file{}, freed {}",
+            Paint::green("✓"),
+            removed,
+            if removed == 1 { "" } else { "s" },
+            human_size(freed_bytes)
+        );
+    }
+    Ok(())
+}
+
+/// Print every cached file with its size and age
+pub fn list(app: &App) -> Result<()> {
+    let files = app.cached_files();
+    if files.is_empty() {
+        println!("{}", "Cache is empty.".italic());
+        return Ok(());
+    }
+
+    for path in files {
+        let name = path
+            .strip_prefix(&app.paths.cache_dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let age = file_age(&path)
+            .and_then(|age| chrono::Duration::from_std(age).ok())
+            .map(relative_time)
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  {:<48} {:>9}  {}",
+            name,
+            human_size(entry_size(&path)),
+            age.dim()
+        );
+    }
+    Ok(())
+}
+
+/// Print the size of each cache component, its TTL status and the total
+pub async fn stats(app: &mut App) -> Result<()> {
+    // Go by the sync stamps inside the cache files, which is what decides when they refresh
+    let index_ttl = index_freshness(app)
+        .await
+        .and_then(|freshness| freshness.ttl_remaining);
+    let mirrors_ttl = MirrorsIndex::load_from_disk(&app.paths.mirrors_file)
+        .await
+        .ok()
+        .and_then(|index| index.get_ttl_remaining());
+
+    let paths = &app.paths;
+    let downloads: Vec<_> = app
+        .cached_files()
+        .into_iter()
+        .filter(|p| p.starts_with(&paths.downloads_dir))
+        .collect();
+    let downloads_size: u64 = downloads.iter().map(|p| entry_size(p)).sum();
+    let index_size = entry_size(&paths.index_file) + entry_size(&paths.raw_index_file());
+    let mirrors_size = entry_size(&paths.mirrors_file);
+
+    let rows = [
+        (
+            "downloads/",
+            downloads_size,
+            format!(
+                "{} item{}, kept until `zv clean downloads`",
+                downloads.len(),
+                if downloads.len() == 1 { "" } else { "s" }
+            ),
+        ),
+        (
+            "index",
+            index_size,
+            ttl_status(paths.index_file.exists(), index_ttl),
+        ),
+        (
+            "mirrors",
+            mirrors_size,
+            ttl_status(paths.mirrors_file.exists(), mirrors_ttl),
+        ),
+    ];
+    for (name, size, status) in rows {
+        println!("  {:<12} {:>9}  {}", name, human_size(size), status);
+    }
+    println!(
+        "  {:<12} {:>9}",
+        "total".bold(),
+        human_size(downloads_size + index_size + mirrors_size)
+    );
+    Ok(())
+}
+
+fn entry_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        dir_size(path)
+    } else {
+        std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+fn file_age(path: &Path) -> Option<Duration> {
+    std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .elapsed()
+        .ok()
+}
+
+/// e.g. "fresh, expires in 5 days" for a cache file with `ttl_remaining` left before it is
+/// refreshed, `None` once it has expired
+fn ttl_status(cached: bool, ttl_remaining: Option<Duration>) -> String {
+    match ttl_remaining {
+        _ if !cached => "not cached".to_string(),
+        Some(remaining) => format!(
+            "fresh, expires in {}",
+            format_hours((remaining.as_secs() / 3600) as i64)
+        ),
+        None => Paint::red("expired, refreshed on next use").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_status_counts_down_to_expiry() {
+        let days = |n: u64| Some(Duration::from_secs(n * 86400));
+        assert_eq!(ttl_status(false, days(21)), "not cached");
+        assert_eq!(ttl_status(true, days(21)), "fresh, expires in 21 days");
+        assert_eq!(
+            ttl_status(true, Some(Duration::from_secs(3600))),
+            "fresh, expires in 1 hour"
+        );
+        assert!(ttl_status(true, None).contains("expired"));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::app::Either;
+use crate::{App, NetErr, ResolvedZigVersion, Result, ZigVersion, ZvError};
+use color_eyre::eyre::{WrapErr, eyre};
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+use yansi::Paint;
+
+/// Print where the release notes of `version` live, optionally opening or rendering them
+pub(crate) async fn changelog(
+    app: &mut App,
+    version: ZigVersion,
+    open: bool,
+    fetch: bool,
+) -> Result<()> {
+    let resolved = super::r#use::resolve_zig_version(app, &version, false)
+        .await
+        .wrap_err_with(|| format!("Failed to resolve version '{version}'"))?;
+    let url = match app.to_install.take() {
+        Some(Either::Release(release)) => release
+            .notes_url()
+            .map(str::to_string)
+            .or_else(|| master_commit_range(&resolved)),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        ZvError::ZigNotFound(eyre!(
+            "The index lists no release notes for Zig {}",
+            resolved.version()
+        ))
+    })?;
+
+    println!(
+        "Release notes for Zig {}:",
+        Paint::cyan(&resolved.version())
+    );
+    println!("  {}", Paint::cyan(&url));
+
+    if open {
+        open::that_detached(&url).wrap_err_with(|| format!("Failed to open {url}"))?;
+    } else if fetch {
+        let text = match fetch_as_text(app, &url).await {
+            Ok(text) => text,
+            Err(e) => {
+                // Offline, the link printed above is all there is
+                eprintln!("{} Could not fetch the release notes: {e}", "⚠".yellow());
+                return Ok(());
+            }
+        };
+        page(&text)?;
+    }
+    Ok(())
+}
+
+/// Master builds have no release notes; link the commits made since the last minor release,
+/// going by the `0.16.0-dev.N+<commit>` version
+fn master_commit_range(resolved: &ResolvedZigVersion) -> Option<String> {
+    let ResolvedZigVersion::Master(version) = resolved else {
+        return None;
+    };
+    let commit = version.build.as_str();
+    if commit.is_empty() {
+        return None;
+    }
+    Some(match version.minor.checked_sub(1) {
+        Some(minor) => format!(
+            "https://github.com/ziglang/zig/compare/{}.{minor}.0...{commit}",
+            version.major
+        ),
+        None => format!("https://github.com/ziglang/zig/commit/{commit}"),
+    })
+}
+
+/// Download `url` and reduce the HTML to plain text wrapped to the terminal width
+async fn fetch_as_text(app: &App, url: &str) -> Result<String, ZvError> {
+    let client = crate::app::network::create_index_client(&app.net_timeouts())?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| ZvError::NetworkError(NetErr::Reqwest(e)))?;
+    if !response.status().is_success() {
+        return Err(ZvError::NetworkError(NetErr::HTTP(response.status())));
+    }
+    let html = response
+        .bytes()
+        .await
+        .map_err(|e| ZvError::NetworkError(NetErr::Reqwest(e)))?;
+
+    let width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0 as usize)
+        .unwrap_or(80);
+    html2text::from_read(&html[..], width).map_err(|e| ZvError::General(eyre!(e)))
+}
+
+/// Show `text` through `$PAGER` (`less` by default) when stdout is a terminal, else print it
+fn page(text: &str) -> Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "more" } else { "less" }.to_string());
+    let mut parts = pager.split_whitespace();
+    let spawned = std::io::stdout().is_terminal().then(|| {
+        Command::new(parts.next().unwrap_or_default())
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()
+    });
+    let Some(Ok(mut child)) = spawned else {
+        println!("\n{text}");
+        return Ok(());
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager closing early (e.g. `q` in less) breaks the pipe; that's not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().wrap_err("Failed to wait for the pager")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn master_links_commits_since_the_previous_minor_release() {
+        let master = ResolvedZigVersion::Master(
+            semver::Version::parse("0.16.0-dev.1484+d0ba6642b").unwrap(),
+        );
+        assert_eq!(
+            master_commit_range(&master).as_deref(),
+            Some("https://github.com/ziglang/zig/compare/0.15.0...d0ba6642b")
+        );
+
+        let tagged = ResolvedZigVersion::Semver(semver::Version::new(0, 14, 0));
+        assert_eq!(master_commit_range(&tagged), None);
+    }
+}
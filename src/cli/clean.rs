@@ -1,6 +1,9 @@
-use crate::app::toolchain::ToolchainManager;
+use crate::app::toolchain::{PruneSummary, ToolchainManager, ZigInstall};
 use crate::cli::CleanTarget;
+use crate::shell::setup::interactive::ZvTheme;
+use crate::tools::human_size;
 use crate::{App, ResolvedZigVersion, ZigVersion};
+use color_eyre::eyre::eyre;
 use yansi::Paint;
 
 pub async fn clean(
@@ -8,7 +11,22 @@ pub async fn clean(
     targets: Vec<CleanTarget>,
     except: Vec<ZigVersion>,
     outdated: bool,
+    older_than: Option<std::time::Duration>,
+    interactive: bool,
+    force: bool,
 ) -> crate::Result<()> {
+    // Handle --interactive flag, which always picks from the checklist
+    if interactive {
+        if !crate::tools::supports_interactive_prompts() {
+            eprintln!(
+                "{} zv clean --interactive needs a terminal. Usage: zv clean all | downloads | zls | <version>...",
+                Paint::red("✗")
+            );
+            return Err(eyre!("No terminal for interactive clean"));
+        }
+        return clean_selected(app).await;
+    }
+
     // Handle --outdated flag
     if outdated {
         let should_clean_outdated = if targets.is_empty() {
@@ -29,9 +47,22 @@ pub async fn clean(
         return clean_except_versions(app, except).await;
     }
 
+    // Handle --older-than flag, which only trims the downloads cache
+    if let Some(max_age) = older_than {
+        if targets.is_empty() || !targets.iter().all(|t| matches!(t, CleanTarget::Downloads)) {
+            eprintln!(
+                "{} Usage: zv clean downloads --older-than <age>",
+                Paint::red("✗")
+            );
+            return Err(eyre!("--older-than only applies to the downloads target"));
+        }
+        return clean_old_downloads(app, max_age).await;
+    }
+
     // Strict Target Parsing
     let mut should_clean_all = false;
     let mut should_clean_downloads = false;
+    let mut should_clean_zls = false;
 
     let has_all = targets.iter().any(|t| matches!(t, CleanTarget::All));
     let has_versions = targets
@@ -50,21 +81,31 @@ pub async fn clean(
     let mut specific_versions = Vec::new();
 
     if targets.is_empty() {
-        // No targets -> prompt for all
-        if !confirm_clean_all()? {
+        // No target: let the user pick on a TTY, never fall back to removing everything
+        if force || !crate::tools::supports_interactive_prompts() {
+            eprintln!(
+                "{} Usage: zv clean all | downloads | zls | <version>...",
+                Paint::red("✗")
+            );
+            return Err(eyre!("No clean target given"));
+        }
+        return clean_selected(app).await;
+    }
+
+    if has_all {
+        // Explicit `all` -> confirm, unless --force
+        if !force && !confirm_clean_all()? {
             return Ok(());
         }
         should_clean_all = true;
         should_clean_downloads = true;
-    } else if has_all {
-        should_clean_all = true;
-        should_clean_downloads = true;
     } else {
         // Collect versions
         for target in targets {
             match target {
                 CleanTarget::Versions(versions) => specific_versions.extend(versions),
                 CleanTarget::Downloads => should_clean_downloads = true,
+                CleanTarget::Zls => should_clean_zls = true,
                 _ => {}
             }
         }
@@ -80,6 +121,10 @@ pub async fn clean(
         clean_downloads(app).await?;
     }
 
+    if should_clean_zls {
+        clean_zls(app).await?;
+    }
+
     // Summary
     if should_clean_all && should_clean_downloads {
         println!("{}", Paint::green("Full cleanup completed!").bold());
@@ -88,6 +133,30 @@ pub async fn clean(
     Ok(())
 }
 
+/// Remove whatever the user picks from the interactive checklist
+async fn clean_selected(app: &mut App) -> crate::Result<()> {
+    let mut versions = Vec::new();
+    let mut downloads = false;
+    for target in select_clean_targets(app)? {
+        match target {
+            CleanTarget::Versions(v) => versions.extend(v),
+            CleanTarget::Downloads => downloads = true,
+            _ => {}
+        }
+    }
+    if versions.is_empty() && !downloads {
+        println!("{}", "Nothing selected, nothing removed.".italic());
+        return Ok(());
+    }
+    if !versions.is_empty() {
+        clean_specific_versions(app, versions).await?;
+    }
+    if downloads {
+        clean_downloads(app).await?;
+    }
+    Ok(())
+}
+
 fn confirm_clean_all() -> crate::Result<bool> {
     if !crate::tools::supports_interactive_prompts() {
         return Ok(true); // Assume yes in non-interactive mode
@@ -106,7 +175,129 @@ fn confirm_clean_all() -> crate::Result<bool> {
         .with_prompt("Are you sure you want to continue?")
         .default(true)
         .interact()
-        .map_err(|e| crate::ZvError::from(color_eyre::eyre::eyre!(e)).into())
+        .map_err(|e| crate::ZvError::from(eyre!(e)).into())
+}
+
+/// Let the user check which installed versions and caches to remove. Nothing is pre-selected.
+/// Removing the active version takes a second confirmation, and the space to be reclaimed is
+/// confirmed before anything is removed.
+fn select_clean_targets(app: &App) -> crate::Result<Vec<CleanTarget>> {
+    let installations = ToolchainManager::scan_installations(app.versions_path())?;
+    let choices = clean_choices(
+        &installations,
+        app.toolchain_manager.get_active_install(),
+        crate::tools::dir_size(app.download_cache()),
+    );
+    let labels: Vec<&str> = choices.iter().map(|choice| choice.label.as_str()).collect();
+
+    let mut selected = dialoguer::MultiSelect::with_theme(&ZvTheme::new())
+        .with_prompt("Select what to remove (space to toggle, enter to confirm)")
+        .items(&labels)
+        .interact()
+        .map_err(|e| crate::ZvError::from(eyre!(e)))?;
+
+    if let Some(position) = selected.iter().position(|&index| choices[index].active) {
+        let keep_active = !dialoguer::Confirm::with_theme(&ZvTheme::new())
+            .with_prompt(format!(
+                "{} is the active version. Remove it anyway?",
+                choices[selected[position]].name
+            ))
+            .default(false)
+            .interact()
+            .map_err(|e| crate::ZvError::from(eyre!(e)))?;
+        if keep_active {
+            selected.remove(position);
+        }
+    }
+    if selected.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reclaimed: u64 = selected.iter().map(|&index| choices[index].size).sum();
+    let proceed = dialoguer::Confirm::with_theme(&ZvTheme::new())
+        .with_prompt(format!(
+            "Remove {} item(s), reclaiming {}?",
+            selected.len(),
+            human_size(reclaimed)
+        ))
+        .default(true)
+        .interact()
+        .map_err(|e| crate::ZvError::from(eyre!(e)))?;
+    if !proceed {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    let mut targets = Vec::new();
+    for index in selected {
+        match &choices[index].target {
+            CleanTarget::Versions(v) => versions.extend(v.iter().cloned()),
+            target => targets.push(target.clone()),
+        }
+    }
+    if !versions.is_empty() {
+        targets.insert(0, CleanTarget::Versions(versions));
+    }
+    Ok(targets)
+}
+
+/// One checklist entry of the interactive `zv clean`
+struct CleanChoice {
+    name: String,
+    label: String,
+    target: CleanTarget,
+    size: u64,
+    active: bool,
+}
+
+/// Menu entries for the interactive `zv clean`: each installed version with its size, then
+/// the downloads cache
+fn clean_choices(
+    installations: &[ZigInstall],
+    active: Option<&ZigInstall>,
+    downloads_size: u64,
+) -> Vec<CleanChoice> {
+    let mut choices: Vec<CleanChoice> = installations
+        .iter()
+        .map(|install| {
+            let (name, version) = if install.is_master {
+                (
+                    format!("master/{}", install.version),
+                    ZigVersion::Master(Some(install.version.clone())),
+                )
+            } else {
+                (
+                    install.version.to_string(),
+                    ZigVersion::Semver(install.version.clone()),
+                )
+            };
+            let is_active = active.is_some_and(|a| a.version_matches(install));
+            let size = crate::tools::dir_size(&install.path);
+            CleanChoice {
+                label: format!(
+                    "{name:<32} {:>9}{}",
+                    human_size(size),
+                    if is_active { " (active)" } else { "" }
+                ),
+                name,
+                target: CleanTarget::Versions(vec![version]),
+                size,
+                active: is_active,
+            }
+        })
+        .collect();
+    choices.push(CleanChoice {
+        name: "downloads cache".to_string(),
+        label: format!(
+            "{:<32} {:>9}",
+            "downloads cache",
+            human_size(downloads_size)
+        ),
+        target: CleanTarget::Downloads,
+        size: downloads_size,
+        active: false,
+    });
+    choices
 }
 
 /// Clean specific versions from the list
@@ -189,9 +380,9 @@ async fn clean_specific_versions(app: &mut App, versions: Vec<ZigVersion>) -> cr
 
         match installation {
             Some(install) => {
-                let is_active = active_install.as_ref().is_some_and(|active| {
-                    active.version == install.version && active.is_master == install.is_master
-                });
+                let is_active = active_install
+                    .as_ref()
+                    .is_some_and(|active| active.version_matches(install));
 
                 if is_active {
                     active_version_removed = true;
@@ -348,7 +539,7 @@ async fn clean_except_versions(
         } else {
             let is_active = active_install
                 .as_ref()
-                .is_some_and(|active| active == install);
+                .is_some_and(|active| active.version_matches(install));
 
             if is_active {
                 active_version_removed = true;
@@ -470,7 +661,7 @@ async fn clean_outdated_master(app: &mut App) -> crate::Result<()> {
         if install.version != latest_master.version {
             let is_active = active_install
                 .as_ref()
-                .is_some_and(|active| active == install);
+                .is_some_and(|active| active.version_matches(install));
 
             if is_active {
                 active_version_removed = true;
@@ -523,6 +714,95 @@ async fn clean_outdated_master(app: &mut App) -> crate::Result<()> {
     Ok(())
 }
 
+/// Keep the `keep` newest stable versions and the newest master build, removing the rest.
+/// The active version is always kept.
+pub async fn prune(app: &mut App, keep: usize, dry_run: bool) -> crate::Result<()> {
+    let installations = ToolchainManager::scan_installations(app.versions_path())?;
+    let active_install = app.toolchain_manager.get_active_install().cloned();
+    let (mut masters, mut stables): (Vec<_>, Vec<_>) = installations
+        .into_iter()
+        .partition(|install| install.is_master);
+    masters.sort_by(|a, b| b.version.cmp(&a.version));
+    stables.sort_by(|a, b| b.version.cmp(&a.version));
+
+    let candidates: Vec<_> = stables
+        .into_iter()
+        .skip(keep)
+        .chain(masters.into_iter().skip(1))
+        .collect();
+
+    let mut summary = PruneSummary::default();
+    for install in &candidates {
+        let name = if install.is_master {
+            format!("master/{}", install.version)
+        } else {
+            install.version.to_string()
+        };
+        if active_install
+            .as_ref()
+            .is_some_and(|active| active.version_matches(install))
+        {
+            println!("{} Keeping active version: {}", Paint::cyan("ℹ"), name);
+            continue;
+        }
+
+        let size = crate::tools::dir_size(&install.path);
+        if dry_run {
+            println!(
+                "{} Would remove: {} ({})",
+                Paint::yellow("→"),
+                name,
+                crate::tools::human_size(size)
+            );
+            summary.removed.push(install.version.clone());
+            summary.freed_bytes += size;
+            continue;
+        }
+
+        match app.toolchain_manager.delete_install(install).await {
+            Ok(()) => {
+                println!(
+                    "{} Removed: {} ({})",
+                    Paint::red("✗"),
+                    name,
+                    crate::tools::human_size(size)
+                );
+                summary.removed.push(install.version.clone());
+                summary.freed_bytes += size;
+            }
+            Err(e) => {
+                eprintln!("{} Failed to remove {}: {}", Paint::red("✗"), name, e);
+            }
+        }
+    }
+
+    if summary.removed.is_empty() {
+        println!("{} Nothing to prune", Paint::green("✓"));
+    } else if dry_run {
+        println!(
+            "{} Would remove {} version(s), reclaiming {}",
+            Paint::cyan("ℹ"),
+            summary.removed.len(),
+            crate::tools::human_size(summary.freed_bytes)
+        );
+    } else {
+        println!(
+            "{} Pruned {} version(s), reclaimed {}",
+            Paint::green("✓"),
+            summary.removed.len(),
+            crate::tools::human_size(summary.freed_bytes)
+        );
+    }
+
+    // The active install is never pruned, but a stale active entry whose files are gone
+    // gets repaired the same way `clean` does
+    if !dry_run && active_install.is_some_and(|active| !active.path.exists()) {
+        handle_active_version_removal(app).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn clean_all_versions(app: &mut App) -> crate::Result<()> {
     println!("{}", Paint::cyan("Removing all versions...").bold());
 
@@ -549,7 +829,11 @@ pub async fn clean_all_versions(app: &mut App) -> crate::Result<()> {
 pub async fn clean_downloads(app: &mut App) -> crate::Result<()> {
     println!("{}", Paint::cyan("Cleaning downloads directory...").bold());
 
-    match app.toolchain_manager.clean_downloads_cache().await {
+    match app
+        .toolchain_manager
+        .clean_downloads_cache(app.download_cache())
+        .await
+    {
         Ok(()) => {
             println!(
                 "{} Successfully cleaned downloads directory",
@@ -569,6 +853,59 @@ pub async fn clean_downloads(app: &mut App) -> crate::Result<()> {
     Ok(())
 }
 
+/// Remove cached ZLS builds and the ZLS source checkout
+async fn clean_zls(app: &App) -> crate::Result<()> {
+    println!("{}", Paint::cyan("Cleaning ZLS builds...").bold());
+
+    let mut freed_bytes = 0;
+    for dir in [app.paths.zls_dir(), app.paths.zls_src_dir()] {
+        if !dir.exists() {
+            continue;
+        }
+        freed_bytes += crate::tools::dir_size(&dir);
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            eprintln!(
+                "{} Failed to remove {}: {}",
+                Paint::red("✗"),
+                dir.display(),
+                e
+            );
+            return Err(crate::ZvError::Io(e).into());
+        }
+    }
+
+    println!(
+        "{} Removed cached ZLS builds, freed {}",
+        Paint::green("✓"),
+        crate::tools::human_size(freed_bytes)
+    );
+    Ok(())
+}
+
+/// Remove cached downloads older than `max_age`, keeping recent ones for reinstalls
+async fn clean_old_downloads(app: &mut App, max_age: std::time::Duration) -> crate::Result<()> {
+    let (removed, freed_bytes) = app
+        .toolchain_manager
+        .clean_downloads_older_than(app.download_cache(), max_age)
+        .await?;
+
+    if removed == 0 {
+        println!(
+            "{} No cached downloads old enough to remove",
+            Paint::cyan("ℹ")
+        );
+    } else {
+        println!(
+            "{} Removed {} cached download{}, freed {}",
+            Paint::green("✓"),
+            removed,
+            if removed == 1 { "" } else { "s" },
+            crate::tools::human_size(freed_bytes)
+        );
+    }
+    Ok(())
+}
+
 async fn handle_active_version_removal(app: &mut App) -> crate::Result<()> {
     println!();
 
@@ -654,3 +991,68 @@ async fn handle_active_version_removal(app: &mut App) -> crate::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_choices_list_versions_then_downloads() {
+        let temp = tempfile::tempdir().unwrap();
+        let install = |version: &str, is_master: bool| ZigInstall {
+            version: semver::Version::parse(version).unwrap(),
+            path: temp.path().join(version),
+            is_master,
+        };
+        let installs = [install("0.14.1", false), install("0.15.0-dev.7+abc", true)];
+
+        let choices = clean_choices(&installs, Some(&installs[0]), 2048);
+        assert_eq!(choices.len(), 3);
+        assert!(choices[0].label.starts_with("0.14.1 ") && choices[0].label.ends_with("(active)"));
+        assert!(choices[0].active && !choices[1].active && !choices[2].active);
+        assert!(matches!(
+            &choices[0].target,
+            CleanTarget::Versions(v) if matches!(v.as_slice(), [ZigVersion::Semver(_)])
+        ));
+        assert!(choices[1].label.starts_with("master/0.15.0-dev.7+abc"));
+        assert_eq!(choices[1].name, "master/0.15.0-dev.7+abc");
+        assert!(matches!(
+            &choices[1].target,
+            CleanTarget::Versions(v) if matches!(v.as_slice(), [ZigVersion::Master(Some(_))])
+        ));
+        assert!(choices[2].label.starts_with("downloads cache"));
+        assert!(matches!(choices[2].target, CleanTarget::Downloads));
+        assert_eq!(choices[2].size, 2048);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn prune_keeps_the_active_version_reached_through_another_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        for version in ["0.13.0", "0.14.1", "0.15.1"] {
+            let dir = versions.join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(crate::Shim::Zig.executable_name()), "").unwrap();
+        }
+        let mut app = App::with_zv_dir(temp.path()).await.unwrap();
+        std::fs::write(app.bin_path().join(crate::Shim::Zv.executable_name()), "zv").unwrap();
+        // Activated through a symlink to the versions dir, so the recorded path differs
+        let linked = temp.path().join("linked-versions");
+        std::os::unix::fs::symlink(&versions, &linked).unwrap();
+        app.toolchain_manager
+            .set_active_version_with_path(
+                &crate::ResolvedZigVersion::Semver(semver::Version::new(0, 13, 0)),
+                linked
+                    .join("0.13.0")
+                    .join(crate::Shim::Zig.executable_name()),
+            )
+            .await
+            .unwrap();
+
+        prune(&mut app, 1, false).await.unwrap();
+        assert!(versions.join("0.13.0").is_dir());
+        assert!(!versions.join("0.14.1").exists());
+        assert!(versions.join("0.15.1").is_dir());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::app::config::{config_template, load_zv_config};
+use crate::{App, Result};
+use color_eyre::eyre::{Context, eyre};
+use yansi::Paint;
+
+/// Open zv.toml in the user's editor, creating it from a commented template if absent,
+/// then make sure the edited file still parses
+pub(crate) fn edit(app: &App) -> Result<()> {
+    let path = &app.paths.config_file;
+    if !path.is_file() {
+        std::fs::write(path, config_template())
+            .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+    }
+
+    let editor = editor_command();
+    let status = editor_process(&editor)
+        .arg(path)
+        .status()
+        .wrap_err_with(|| format!("Failed to launch editor '{editor}'"))?;
+    if !status.success() {
+        return Err(eyre!("Editor '{editor}' exited with {status}"));
+    }
+
+    if let Err(e) = load_zv_config(path) {
+        crate::tools::error(e.to_string());
+        crate::suggest!("Fix it with {}", cmd = "zv config edit");
+        return Err(eyre!("{} is not a valid zv config", path.display()));
+    }
+    println!("✅ {} is valid", Paint::blue(&path.display().to_string()));
+    Ok(())
+}
+
+/// `$VISUAL`, then `$EDITOR`, then the platform's stock editor
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string())
+}
+
+/// A command running `editor`: the program itself when it names a file (spaces and all),
+/// otherwise a shell word list as git runs it, so quoted paths and extra arguments work.
+/// Windows has no `sh`, so there it's split on whitespace.
+fn editor_process(editor: &str) -> std::process::Command {
+    if std::path::Path::new(editor).is_file() {
+        return std::process::Command::new(editor);
+    }
+    if cfg!(unix) {
+        let mut command = std::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{editor} \"$@\""))
+            .arg(editor);
+        return command;
+    }
+    let mut parts = editor.split_whitespace();
+    let mut command = std::process::Command::new(parts.next().unwrap_or(editor));
+    command.args(parts);
+    command
+}
+
+/// Set a persistent zv.toml setting, e.g. `zv config set mirror.priority <url> <rank>`
+pub(crate) fn set(app: &mut App, key: &str, values: &[String]) -> Result<()> {
+    match key {
+        "mirror.priority" => set_mirror_priority(app, values),
+        _ => {
+            crate::tools::error(format!("Unknown config key '{key}'"));
+            crate::suggest!("Supported keys: {}", cmd = "mirror.priority <url> <rank>");
+            Err(eyre!("Unknown config key '{key}'"))
+        }
+    }
+}
+
+fn set_mirror_priority(app: &mut App, values: &[String]) -> Result<()> {
+    let [url, rank] = values else {
+        crate::suggest!(
+            "Usage: {}",
+            cmd = "zv config set mirror.priority <url> <rank>"
+        );
+        return Err(eyre!("mirror.priority expects a mirror URL and a rank"));
+    };
+
+    let parsed = url::Url::parse(url).map_err(|e| eyre!("Invalid mirror URL '{url}': {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(eyre!("Mirror URL must use http or https: {url}"));
+    }
+    let rank = rank
+        .parse::<u8>()
+        .ok()
+        .filter(|r| *r >= 1)
+        .ok_or_else(|| eyre!("Rank must be a number between 1 and 255, got '{rank}'"))?;
+
+    app.set_mirror_priority(url, rank)?;
+    println!(
+        "✅ Mirror {} pinned to rank {} {}",
+        Paint::blue(url),
+        Paint::green(&rank.to_string()),
+        Paint::new("(lower rank = preferred)").dim()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn editor_path_with_spaces_and_arguments_is_run() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("my editors");
+        std::fs::create_dir(&dir).unwrap();
+        let editor = dir.join("ed");
+        std::fs::write(&editor, "#!/bin/sh\necho \"$1\" > \"$2\"\n").unwrap();
+        std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let file = temp.path().join("zv.toml");
+
+        let run = |command: String| {
+            let status = editor_process(&command).arg(&file).status().unwrap();
+            assert!(status.success(), "{command}");
+            std::fs::read_to_string(&file).unwrap()
+        };
+        assert_eq!(run(format!("'{}' --wait", editor.display())), "--wait\n");
+        // Unquoted, the whole value is the program, as when EDITOR is set to a path
+        std::fs::write(&editor, "#!/bin/sh\necho edited > \"$1\"\n").unwrap();
+        assert_eq!(run(editor.display().to_string()), "edited\n");
+    }
+}
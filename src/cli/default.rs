@@ -0,0 +1,39 @@
+use crate::app::global_default;
+use crate::{App, Result, ZvError};
+use yansi::Paint;
+
+/// Show, set or clear the global default version in `default.json`
+pub fn default(app: &App, version: Option<&str>, unset: bool) -> Result<()> {
+    let default_file = app.paths.global_default_file();
+
+    if unset {
+        if global_default::clear(&default_file)? {
+            println!("{} Cleared the global default version", Paint::green("✓"));
+        } else {
+            println!("{}", "No global default version was set.".italic());
+        }
+        return Ok(());
+    }
+
+    let Some(version) = version else {
+        match global_default::load_raw(&default_file) {
+            Some(version) => println!("{}", version),
+            None => println!("{}", "No global default version set.".italic()),
+        }
+        return Ok(());
+    };
+
+    global_default::save(&default_file, version).map_err(ZvError::ZvConfigError)?;
+    println!(
+        "{} Global default version set to {}",
+        Paint::green("✓"),
+        Paint::cyan(version.trim())
+    );
+    if let Some(active) = app.get_active_version() {
+        println!(
+            "  The active version {} still takes precedence; the default applies when none is active",
+            active
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::{App, ResolvedZigVersion, Result, VersionSeries, ZigVersion};
+use color_eyre::eyre::eyre;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Run `command` with an installed Zig toolchain first on PATH and `ZIG` pointing at its binary
+pub(crate) async fn exec(app: &App, version: ZigVersion, command: Vec<String>) -> Result<()> {
+    let (_, zig_path) = require_installed(app, &version)?;
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| eyre!("No command given after `--`"))?;
+
+    tracing::debug!(target: "zv::cli::exec", "Running {} with zig {}", program, zig_path.display());
+    let status = Command::new(program)
+        .args(args)
+        .env("PATH", toolchain_path(app, &zig_path)?)
+        .env("ZIG", &zig_path)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| eyre!("Failed to launch {}: {}", program, e))?;
+
+    crate::cli::zig::exit_with_status(status)
+}
+
+/// Like [`installed_zig`], but reports a missing install with an install hint
+pub(super) fn require_installed(
+    app: &App,
+    version: &ZigVersion,
+) -> Result<(ResolvedZigVersion, PathBuf)> {
+    installed_zig(app, version).ok_or_else(|| {
+        crate::tools::error(format!("Zig {version} is not installed"));
+        crate::suggest!(
+            "Install it first with {}",
+            cmd = &format!("zv install {version}")
+        );
+        eyre!("Zig {version} is not installed")
+    })
+}
+
+/// Map a version request onto an existing installation without touching the network.
+/// `master`, `stable` and `latest` pick the newest matching install.
+fn installed_zig(app: &App, version: &ZigVersion) -> Option<(ResolvedZigVersion, PathBuf)> {
+    let newest = |want_master: bool, series: Option<&VersionSeries>| {
+        app.toolchain_manager
+            .list_installations()
+            .into_iter()
+            .filter(|(_, _, is_master)| *is_master == want_master)
+            .map(|(v, _, _)| v)
+            .filter(|v| series.is_none_or(|series| series.matches(v)))
+            .max()
+    };
+    let resolved = match version {
+        ZigVersion::Semver(v) | ZigVersion::Stable(Some(v)) | ZigVersion::Latest(Some(v)) => {
+            ResolvedZigVersion::Semver(v.clone())
+        }
+        ZigVersion::Master(Some(v)) => ResolvedZigVersion::Master(v.clone()),
+        ZigVersion::Master(None) => ResolvedZigVersion::Master(newest(true, None)?),
+        ZigVersion::Stable(None) | ZigVersion::Latest(None) => {
+            ResolvedZigVersion::Semver(newest(false, None)?)
+        }
+        ZigVersion::StableSeries(series) => {
+            ResolvedZigVersion::Semver(newest(false, Some(series))?)
+        }
+    };
+    let zig_path = app.check_installed(&resolved)?;
+    Some((resolved, zig_path))
+}
+
+/// PATH for a child process: the toolchain's directory, then `ZV_DIR/bin`, then the current PATH
+pub(super) fn toolchain_path(app: &App, zig_path: &Path) -> Result<OsString> {
+    let zig_dir = zig_path
+        .parent()
+        .ok_or_else(|| eyre!("Invalid zig path: {}", zig_path.display()))?;
+    let mut path_dirs = vec![zig_dir.to_path_buf(), app.bin_path().clone()];
+    if let Some(existing) = std::env::var_os("PATH") {
+        path_dirs.extend(std::env::split_paths(&existing));
+    }
+    std::env::join_paths(path_dirs).map_err(|e| eyre!("Invalid PATH entry: {e}"))
+}
@@ -0,0 +1,36 @@
+use crate::app::utils::zig_tarball;
+use crate::{App, Result, ZigVersion};
+use color_eyre::eyre::eyre;
+use std::path::PathBuf;
+use yansi::Paint;
+
+/// Package an installed Zig version into a portable archive, optionally signed with minisign
+pub(crate) async fn export(
+    app: &App,
+    version: ZigVersion,
+    output: Option<PathBuf>,
+    sign: Option<PathBuf>,
+) -> Result<()> {
+    let (resolved, _) = super::exec::require_installed(app, &version)?;
+    // Default to the official archive name, e.g. zig-x86_64-linux-0.14.1.tar.xz
+    let output = match output {
+        Some(output) => output,
+        None => zig_tarball(resolved.version(), None)
+            .map(PathBuf::from)
+            .ok_or_else(|| eyre!("Unsupported host platform; pass --output"))?,
+    };
+
+    let signature = app
+        .export_toolchain(&resolved, &output, sign.as_deref())
+        .await?;
+    println!(
+        "{} Exported Zig {} to {}",
+        Paint::green("✓"),
+        resolved,
+        output.display()
+    );
+    if let Some(signature) = signature {
+        println!("  Signature written to {}", signature.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::{App, Result, ZigVersion};
+use color_eyre::eyre::WrapErr;
+use std::path::Path;
+use yansi::Paint;
+
+/// Download and verify the source tarball of `version`, leaving it in the downloads cache or `out`
+pub(crate) async fn fetch_src(
+    app: &mut App,
+    version: ZigVersion,
+    out: Option<&Path>,
+    force_ziglang: bool,
+) -> Result<()> {
+    let resolved = super::r#use::resolve_zig_version(app, &version, false)
+        .await
+        .wrap_err_with(|| format!("Failed to resolve version '{version}'"))?;
+    let download = crate::app::zig_source::fetch_zig_source(app, out, force_ziglang)
+        .await
+        .wrap_err_with(|| format!("Failed to fetch sources for Zig {resolved}"))?;
+
+    println!(
+        "{} Fetched and verified the Zig {} source tarball",
+        Paint::green("✓"),
+        resolved.version()
+    );
+    println!("  Tarball:   {}", download.tarball_path.display());
+    println!("  Signature: {}", download.minisig_path.display());
+    Ok(())
+}
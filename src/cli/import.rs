@@ -0,0 +1,22 @@
+use crate::{App, Result};
+use std::path::Path;
+use yansi::Paint;
+
+/// Install a portable Zig archive as `version` without touching the network
+pub(crate) async fn import(
+    app: &mut App,
+    archive: &Path,
+    version: &semver::Version,
+    is_master: bool,
+) -> Result<()> {
+    let zig = app.import_toolchain(archive, version, is_master).await?;
+    println!(
+        "{} Imported Zig {} from {}",
+        Paint::green("✓"),
+        version,
+        archive.display()
+    );
+    println!("  Installed at {}", zig.parent().unwrap_or(&zig).display());
+    println!("  Run `zv use {version}` to make it the active version");
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::app::Either;
+use crate::app::network::ZigRelease;
+use crate::app::utils::host_target;
+use crate::tools::human_size;
+use crate::types::TargetTriple;
+use crate::{App, Result, ZigVersion, ZvError};
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::Serialize;
+use std::path::PathBuf;
+use yansi::Paint;
+
+/// Release details shown by `zv info`, also its `--json` output
+#[derive(Debug, Serialize)]
+struct ReleaseInfo {
+    version: String,
+    is_master: bool,
+    date: String,
+    /// Where the version is installed, `null` if it isn't
+    installed: Option<PathBuf>,
+    /// Host target key, `null` if zv doesn't know the host
+    host: Option<String>,
+    /// Archive name for the host, `null` if the release has no host build
+    tarball: Option<String>,
+    size: Option<u64>,
+    shasum: Option<String>,
+    targets: Vec<String>,
+    notes: Option<String>,
+    /// Age of the cached index these details come from, `null` if it isn't cached
+    index: Option<String>,
+}
+
+impl ReleaseInfo {
+    fn new(release: &ZigRelease, host: Option<String>, installed: Option<PathBuf>) -> Self {
+        let key = host
+            .as_deref()
+            .and_then(|host| release.resolve_target_key(host));
+        let artifact = key.as_deref().and_then(|key| release.target_artifact(key));
+        Self {
+            version: release.resolved_version().version().to_string(),
+            is_master: release.is_master(),
+            date: release.date().to_string(),
+            installed,
+            tarball: key
+                .as_deref()
+                .and_then(TargetTriple::from_key)
+                .and_then(|triple| release.zig_tarball_for_target(&triple)),
+            size: artifact.map(|a| a.size),
+            shasum: artifact.map(|a| a.shasum.clone()),
+            host,
+            targets: release.get_available_targets(),
+            notes: release.notes_url().map(str::to_string),
+            index: None,
+        }
+    }
+}
+
+/// Show what the index knows about `version` and whether it is installed
+pub(crate) async fn info(app: &mut App, version: ZigVersion, json: bool) -> Result<()> {
+    let resolved = super::r#use::resolve_zig_version(app, &version, false)
+        .await
+        .wrap_err_with(|| format!("Failed to resolve version '{version}'"))?;
+    let Some(Either::Release(release)) = app.to_install.take() else {
+        return Err(ZvError::ZigNotFound(eyre!(
+            "Zig {} is not listed in the download index",
+            resolved.version()
+        ))
+        .into());
+    };
+    let installed = app
+        .get_version_install_path(release.resolved_version())
+        .map(|path| crate::tools::canonicalize(&path).unwrap_or(path));
+    let mut info = ReleaseInfo::new(&release, host_target(), installed);
+    info.index = super::stats::index_freshness(app)
+        .await
+        .map(|freshness| freshness.describe());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    let title = if info.is_master {
+        format!("Zig master ({})", info.version)
+    } else {
+        format!("Zig {}", info.version)
+    };
+    println!("{}", Paint::cyan(&title).bold());
+    println!("  Released:  {}", info.date);
+    match &info.installed {
+        Some(path) => println!("  Installed: {} {}", "yes".green(), path.display()),
+        None => println!("  Installed: no"),
+    }
+    let host = info.host.as_deref().unwrap_or("unknown host");
+    match (&info.tarball, info.size, &info.shasum) {
+        (Some(tarball), Some(size), Some(shasum)) => {
+            println!("  Tarball:   {tarball} ({host})");
+            println!("  Size:      {}", human_size(size));
+            println!("  SHA-256:   {shasum}");
+        }
+        _ => println!("  Tarball:   {}", format!("no build for {host}").yellow()),
+    }
+    println!("  Targets:   {}", info.targets.join(", "));
+    if let Some(notes) = &info.notes {
+        println!("  Notes:     {notes}");
+    }
+    if let Some(index) = &info.index {
+        println!("  Index:     {}", format!("cached, {index}").dim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::network::ArtifactInfo;
+    use crate::types::ResolvedZigVersion;
+
+    #[test]
+    fn release_info_reports_the_host_artifact_only_when_listed() {
+        let release = ZigRelease::new(
+            ResolvedZigVersion::Semver(semver::Version::new(0, 14, 1)),
+            "2025-05-21".to_string(),
+            [(
+                TargetTriple::new("sparc64".to_string(), "plan9".to_string()),
+                ArtifactInfo {
+                    ziglang_org_tarball: "https://ziglang.org/zig-sparc64-plan9-0.14.1.tar.xz"
+                        .to_string(),
+                    shasum: "a".repeat(64),
+                    size: 42,
+                },
+            )]
+            .into(),
+        );
+
+        let listed = ReleaseInfo::new(&release, Some("sparc64-plan9".to_string()), None);
+        assert_eq!(
+            listed.tarball.as_deref(),
+            Some("zig-sparc64-plan9-0.14.1.tar.xz")
+        );
+        assert_eq!(listed.size, Some(42));
+        assert_eq!(listed.shasum.as_deref(), Some("a".repeat(64).as_str()));
+        assert_eq!(listed.targets, ["sparc64-plan9"]);
+
+        let other = ReleaseInfo::new(&release, Some("riscv64-haiku".to_string()), None);
+        assert_eq!(
+            (other.tarball, other.size, other.shasum),
+            (None, None, None)
+        );
+        assert_eq!(other.version, "0.14.1");
+        assert!(!other.is_master);
+    }
+}
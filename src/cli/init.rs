@@ -3,6 +3,26 @@ use crate::Template;
 use color_eyre::Result;
 use yansi::Paint;
 
+/// If `name` can't name a project, offer a sanitized one instead. When the offer is declined,
+/// or there's no terminal to ask on, `name` is kept and project creation reports why it's invalid.
+pub(crate) fn offer_valid_name(name: String) -> String {
+    let Some(reason) = crate::tools::invalid_project_name(&name) else {
+        return name;
+    };
+    if !crate::tools::supports_interactive_prompts() {
+        return name;
+    }
+    let suggestion = crate::tools::suggest_project_name(&name);
+    let accepted = dialoguer::Confirm::new()
+        .with_prompt(format!(
+            "Project name '{name}' {reason}. Use '{suggestion}' instead?"
+        ))
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+    if accepted { suggestion } else { name }
+}
+
 pub(crate) async fn init_project(template: Template, app: App) -> Result<()> {
     let template_result = template.execute(app).await?;
     let project_name = template_result.project_name;
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     ResolvedZigVersion, ZigVersion, ZvError,
-    app::{App, Either},
+    app::{App, Either, utils::is_quiet},
     cli::r#use::resolve_zig_version,
 };
 use color_eyre::eyre::{Context, Result, eyre};
@@ -35,7 +35,7 @@ pub(crate) async fn install_versions(
     let mut resolution_errors: Vec<(ZigVersion, ZvError)> = Vec::new();
 
     for zig_version in zig_versions {
-        match resolve_zig_version(app, &zig_version).await {
+        match resolve_zig_version(app, &zig_version, false).await {
             Ok(resolved) => {
                 // Get the Either that was set by resolve_zig_version
                 let install_either = app.to_install.take().ok_or_else(|| {
@@ -77,22 +77,25 @@ pub(crate) async fn install_versions(
         return Err(eyre!("Failed to resolve any versions"));
     }
 
-    let resolved_versions: Vec<_> = resolved_map.keys().collect();
-    if should_set_active {
-        println!(
-            "📦 Installing {} (will be set as active zig)...",
-            Paint::blue(&resolved_versions[0].to_string())
-        );
-    } else if is_single_version {
+    let quiet = is_quiet();
+    if !quiet {
+        let resolved_versions: Vec<_> = resolved_map.keys().collect();
+        if should_set_active {
+            println!(
+                "📦 Installing {} (will be set as active zig)...",
+                Paint::blue(&resolved_versions[0].to_string())
+            );
+        } else if is_single_version {
+            println!(
+                "📦 Installing {}...",
+                Paint::blue(&resolved_versions[0].to_string())
+            );
+        }
         println!(
-            "📦 Installing {}...",
-            Paint::blue(&resolved_versions[0].to_string())
+            "📦 Installing {} version(s)...",
+            Paint::blue(&resolved_map.keys().len().to_string())
         );
     }
-    println!(
-        "📦 Installing {} version(s)...",
-        Paint::blue(&resolved_map.keys().len().to_string())
-    );
 
     let mut installed_versions = Vec::new();
     let mut failed_versions = Vec::new();
@@ -125,7 +128,19 @@ pub(crate) async fn install_versions(
     }
 
     // Report results
-    if !installed_versions.is_empty() {
+    if quiet {
+        for resolved in &installed_versions {
+            let verb = if should_set_active {
+                "activated"
+            } else {
+                "installed"
+            };
+            match app.check_installed(resolved) {
+                Some(zig) => println!("{verb} zig {} at {}", resolved.version(), zig.display()),
+                None => println!("{verb} zig {}", resolved.version()),
+            }
+        }
+    } else if !installed_versions.is_empty() {
         println!();
         for resolved in &installed_versions {
             if should_set_active {
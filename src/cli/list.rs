@@ -1,12 +1,123 @@
+use crate::app::toolchain::ToolchainManager;
+use crate::cli::ListSort;
+use crate::tools::{dir_size, human_size};
 use crate::{App, Result};
 use semver::Version;
+use serde::Serialize;
+use std::path::PathBuf;
 use yansi::Paint;
 
 const SEPARATOR: &str = "\n----------------------------------------\n";
 
-pub async fn list_opts(mut app: App, all: bool, mirrors: bool, refresh: bool) -> Result<()> {
-    if !all && !mirrors {
-        list_versions(&app).await
+/// One installed version in `zv list --json`
+#[derive(Debug, Serialize)]
+struct InstalledVersion {
+    version: String,
+    is_master: bool,
+    is_active: bool,
+    path: PathBuf,
+    installed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Disk usage in bytes, only computed for `--sort size`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// Why the version is stale, only with `--stale`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stale: Option<String>,
+}
+
+/// How `zv list` shows installed versions: output style, order and filters
+#[derive(Debug, Clone, Default)]
+pub struct InstalledListing {
+    pub json: bool,
+    pub compact: bool,
+    pub sort: ListSort,
+    pub master_only: bool,
+    pub stable_only: bool,
+    /// `--stale`: only list versions the index has moved past
+    pub stale: Option<StaleCheck>,
+}
+
+/// What `zv list --stale` compares installed versions against
+#[derive(Debug, Clone, Default)]
+pub struct StaleCheck {
+    /// A stable version is stale once it is more than this many minor versions behind
+    pub max_minors_behind: u64,
+    /// Latest stable release in the index
+    latest_stable: Option<Version>,
+    /// Current master build in the index and its date
+    master: Option<(Version, String)>,
+}
+
+impl StaleCheck {
+    pub fn behind(max_minors_behind: u64) -> Self {
+        Self {
+            max_minors_behind,
+            ..Default::default()
+        }
+    }
+
+    /// Take the latest stable and current master from the index
+    async fn load_index(&mut self, app: &mut App, refresh: bool) -> Result<()> {
+        let cache_strategy = if refresh {
+            crate::app::CacheStrategy::AlwaysRefresh
+        } else {
+            crate::app::CacheStrategy::RespectTtl
+        };
+        let index = app.index_manager().await?;
+        let zig_index = index.ensure_loaded(cache_strategy).await?;
+        self.latest_stable = zig_index
+            .get_latest_stable(false)
+            .map(|v| v.version().clone());
+        self.master = zig_index.get_master_version().map(|release| {
+            (
+                release.resolved_version().version().clone(),
+                release.date().to_string(),
+            )
+        });
+        Ok(())
+    }
+
+    /// Why `version` is stale, or `None` if it is current enough
+    fn reason(&self, version: &Version, is_master: bool) -> Option<String> {
+        if is_master {
+            let (current, date) = self.master.as_ref()?;
+            return (version < current)
+                .then(|| format!("older than the current master {current} ({date})"));
+        }
+        let latest = self.latest_stable.as_ref()?;
+        if version >= latest {
+            return None;
+        }
+        if version.major != latest.major {
+            return Some(format!("a major version behind the latest stable {latest}"));
+        }
+        let behind = latest.minor - version.minor;
+        (behind > self.max_minors_behind)
+            .then(|| format!("{behind} minor versions behind the latest stable {latest}"))
+    }
+}
+
+pub async fn list_opts(
+    mut app: App,
+    all: bool,
+    mirrors: bool,
+    refresh: bool,
+    mut listing: InstalledListing,
+    no_color: bool,
+) -> Result<()> {
+    if no_color || listing.json {
+        yansi::disable();
+    }
+    if let Some(stale) = listing.stale.as_mut() {
+        stale.load_index(&mut app, refresh).await?;
+    }
+    if listing.json {
+        list_versions_json(&app, &listing)
+    } else if listing.compact {
+        list_versions_compact(&app, &listing);
+        Ok(())
+    } else if !all && !mirrors {
+        list_versions(&app, &listing).await
     } else if all && mirrors {
         let mut app = list_all(app, refresh).await?;
         println!("{SEPARATOR}");
@@ -20,11 +131,21 @@ pub async fn list_opts(mut app: App, all: bool, mirrors: bool, refresh: bool) ->
         Ok(())
     }
 }
-pub async fn list_versions(app: &App) -> Result<()> {
-    let installed = app.toolchain_manager.list_installations();
+pub async fn list_versions(app: &App, listing: &InstalledListing) -> Result<()> {
+    let installed = installed_versions(&app.toolchain_manager, listing);
 
     if installed.is_empty() {
-        println!("{}", "No zig versions installed.".italic());
+        if app.toolchain_manager.installations_empty() {
+            println!("{}", "No zig versions installed.".italic());
+        } else if listing.stale.is_some() {
+            println!("{}", "No stale zig versions installed.".italic());
+        } else {
+            println!("{}", "No installed zig versions match the filter.".italic());
+        }
+        return Ok(());
+    }
+    if listing.stale.is_some() {
+        print_stale_versions(&installed);
         return Ok(());
     }
 
@@ -39,7 +160,15 @@ pub async fn list_versions(app: &App) -> Result<()> {
     let mut current_line_width = 0;
     let mut is_first = true;
 
-    for (version, is_active, is_master) in installed.iter() {
+    for InstalledVersion {
+        version,
+        is_active,
+        is_master,
+        installed_at,
+        size,
+        ..
+    } in installed.iter()
+    {
         let active_marker = if *is_active {
             Paint::green("★ ").to_string()
         } else {
@@ -53,15 +182,32 @@ pub async fn list_versions(app: &App) -> Result<()> {
         };
 
         let version_display = if *is_active {
-            Paint::green(&version.to_string()).bold().to_string()
+            Paint::green(version).bold().to_string()
         } else {
             version.to_string()
         };
 
-        let full_item = format!("{}{}{}", active_marker, version_display, master_marker);
+        // Show what the list is ordered by when it isn't the version
+        let sort_detail = match listing.sort {
+            ListSort::Version => String::new(),
+            ListSort::Date => installed_at
+                .map(|t| format!(" {}", t.format("%Y-%m-%d")))
+                .unwrap_or_default(),
+            ListSort::Size => size
+                .map(|s| format!(" {}", human_size(s)))
+                .unwrap_or_default(),
+        };
+
+        let full_item = format!(
+            "{}{}{}{}",
+            active_marker,
+            version_display,
+            master_marker,
+            sort_detail.dim()
+        );
 
         // Calculate visible width (approximate, not accounting for ANSI codes)
-        let visible_width = version.to_string().len() + 2 + master_marker.len(); // +2 for active_marker space
+        let visible_width = version.len() + 2 + master_marker.len() + sort_detail.len(); // +2 for active_marker space
         let item_width = visible_width + 3; // +3 for separator padding
 
         // Check if adding this version would exceed target width
@@ -85,6 +231,106 @@ pub async fn list_versions(app: &App) -> Result<()> {
 
     Ok(())
 }
+/// One stale version per line with the reason, and how to remove them
+fn print_stale_versions(installed: &[InstalledVersion]) {
+    println!("{}", "Stale zig versions:".italic());
+    for installed in installed {
+        let version = if installed.is_master {
+            format!("master/{}", installed.version)
+        } else {
+            installed.version.clone()
+        };
+        let active = if installed.is_active { " (active)" } else { "" };
+        println!(
+            "  {:<28} {}{}",
+            version,
+            installed.stale.as_deref().unwrap_or_default().dim(),
+            Paint::green(active)
+        );
+    }
+    let stable: Vec<&str> = installed
+        .iter()
+        .filter(|i| !i.is_master)
+        .map(|i| i.version.as_str())
+        .collect();
+    if !stable.is_empty() {
+        crate::suggest!(
+            "Remove stale releases with {}",
+            cmd = &format!("zv clean {}", stable.join(","))
+        );
+    }
+    if installed.iter().any(|i| i.is_master) {
+        crate::suggest!("Remove old master builds with {}", cmd = "zv prune");
+    }
+}
+
+/// Print installed versions as a JSON array for editor integrations and scripts
+fn list_versions_json(app: &App, listing: &InstalledListing) -> Result<()> {
+    let installed = installed_versions(&app.toolchain_manager, listing);
+    println!("{}", serde_json::to_string_pretty(&installed)?);
+    Ok(())
+}
+
+/// Print one installed version per line, `*` marking the active one, for shell scripts
+fn list_versions_compact(app: &App, listing: &InstalledListing) {
+    for line in compact_lines(&app.toolchain_manager, listing) {
+        println!("{line}");
+    }
+}
+
+fn compact_lines(manager: &ToolchainManager, listing: &InstalledListing) -> Vec<String> {
+    installed_versions(manager, listing)
+        .into_iter()
+        .map(|installed| {
+            let marker = if installed.is_active {
+                Paint::green("*").to_string()
+            } else {
+                " ".to_string()
+            };
+            let version = if installed.is_master {
+                format!("master/{}", installed.version)
+            } else {
+                installed.version
+            };
+            format!("{marker} {version}")
+        })
+        .collect()
+}
+
+/// Installed versions filtered and ordered as `listing` asks, the same for every output style.
+/// Installations come sorted by version, so that order needs no extra work.
+fn installed_versions(
+    manager: &ToolchainManager,
+    listing: &InstalledListing,
+) -> Vec<InstalledVersion> {
+    let active = manager.get_active_install();
+    let mut installed: Vec<InstalledVersion> = manager
+        .installations()
+        .iter()
+        .filter(|install| !listing.master_only || install.is_master)
+        .filter(|install| !listing.stable_only || !install.is_master)
+        .map(|install| InstalledVersion {
+            version: install.version.to_string(),
+            is_master: install.is_master,
+            is_active: active.is_some_and(|a| a.version_matches(install)),
+            path: install.path.clone(),
+            installed_at: install.installed_at(),
+            size: matches!(listing.sort, ListSort::Size).then(|| dir_size(&install.path)),
+            stale: listing
+                .stale
+                .as_ref()
+                .and_then(|check| check.reason(&install.version, install.is_master)),
+        })
+        .filter(|installed| listing.stale.is_none() || installed.stale.is_some())
+        .collect();
+    match listing.sort {
+        ListSort::Version => {}
+        ListSort::Date => installed.sort_by_key(|i| i.installed_at),
+        ListSort::Size => installed.sort_by_key(|i| i.size),
+    }
+    installed
+}
+
 async fn list_all(mut app: App, refresh: bool) -> Result<App> {
     let installed = app
         .toolchain_manager
@@ -158,6 +404,8 @@ async fn list_mirrors(app: &mut App, refresh: bool) -> Result<()> {
         .await
         .map_err(crate::ZvError::NetworkError)?;
 
+    let last_synced = mirror_manager.last_synced_display();
+
     // Get all mirrors and sort by rank
     let mirrors = mirror_manager
         .sort_by_rank()
@@ -169,7 +417,10 @@ async fn list_mirrors(app: &mut App, refresh: bool) -> Result<()> {
         return Ok(());
     }
 
-    println!("{}", "Community mirrors:".italic());
+    match last_synced {
+        Some(when) => println!("{}", format!("Community mirrors (synced {when}):").italic()),
+        None => println!("{}", "Community mirrors:".italic()),
+    }
     println!();
 
     // Display each mirror with rank and URL
@@ -215,3 +466,193 @@ async fn list_mirrors(app: &mut App, refresh: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::toolchain::manager_with_installs;
+
+    #[tokio::test]
+    async fn json_lists_each_install_with_its_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        let manager = manager_with_installs(temp.path(), &["0.13.0", "0.14.1"]).await;
+
+        let json = serde_json::to_value(installed_versions(&manager, &InstalledListing::default()))
+            .unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["version"], "0.13.0");
+        assert_eq!(entries[0]["is_master"], false);
+        assert_eq!(
+            entries[0]["path"],
+            versions.join("0.13.0").to_string_lossy().as_ref()
+        );
+        let installed_at = entries[0]["installed_at"].as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(installed_at).is_ok());
+        assert!(
+            !installed_at.contains('.'),
+            "{installed_at} has sub-seconds"
+        );
+        assert_eq!(
+            entries.iter().filter(|e| e["is_active"] == true).count(),
+            usize::from(manager.get_active_install().is_some())
+        );
+    }
+
+    #[tokio::test]
+    async fn compact_prints_one_version_per_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager =
+            manager_with_installs(temp.path(), &["0.13.0", "master/0.16.0-dev.1+abc"]).await;
+
+        let lines = compact_lines(&manager, &InstalledListing::default());
+        assert_eq!(lines.len(), 2);
+        for (line, version) in lines.iter().zip(["0.13.0", "master/0.16.0-dev.1+abc"]) {
+            assert!(line.ends_with(&format!(" {version}")), "{line}");
+            let active = manager
+                .get_active_install()
+                .is_some_and(|a| version.ends_with(&a.version.to_string()));
+            assert_eq!(line.contains('*'), active, "{line}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sort_and_filters_apply_to_every_output() {
+        let temp = tempfile::tempdir().unwrap();
+        let installs = [
+            ("0.13.0", 300),
+            ("0.14.1", 100),
+            ("master/0.16.0-dev.1+abc", 200),
+        ];
+        let manager = manager_with_installs(temp.path(), &installs.map(|(dir, _)| dir)).await;
+        for (dir, zig_size) in installs {
+            let zig = temp
+                .path()
+                .join("versions")
+                .join(dir)
+                .join(crate::Shim::Zig.executable_name());
+            std::fs::write(zig, vec![0u8; zig_size]).unwrap();
+        }
+        let versions_of = |listing: InstalledListing| {
+            installed_versions(&manager, &listing)
+                .into_iter()
+                .map(|i| i.version)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            versions_of(InstalledListing {
+                sort: ListSort::Size,
+                ..Default::default()
+            }),
+            ["0.14.1", "0.16.0-dev.1+abc", "0.13.0"]
+        );
+        assert_eq!(
+            versions_of(InstalledListing {
+                stable_only: true,
+                ..Default::default()
+            }),
+            ["0.13.0", "0.14.1"]
+        );
+        assert_eq!(
+            versions_of(InstalledListing {
+                master_only: true,
+                ..Default::default()
+            }),
+            ["0.16.0-dev.1+abc"]
+        );
+
+        let sized = installed_versions(
+            &manager,
+            &InstalledListing {
+                sort: ListSort::Size,
+                ..Default::default()
+            },
+        );
+        let json = serde_json::to_value(&sized).unwrap();
+        assert_eq!(json[0]["size"], 100);
+        let by_version =
+            serde_json::to_value(installed_versions(&manager, &Default::default())).unwrap();
+        assert!(by_version[0].get("size").is_none());
+    }
+
+    #[test]
+    fn stale_check_explains_why_a_version_is_stale() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        let check = StaleCheck {
+            max_minors_behind: 1,
+            latest_stable: Some(v("0.15.1")),
+            master: Some((v("0.16.0-dev.500+abc"), "2025-10-01".to_string())),
+        };
+
+        assert_eq!(check.reason(&v("0.15.0"), false), None);
+        assert_eq!(check.reason(&v("0.14.1"), false), None);
+        assert_eq!(
+            check.reason(&v("0.13.0"), false).as_deref(),
+            Some("2 minor versions behind the latest stable 0.15.1")
+        );
+        assert_eq!(
+            check.reason(&v("0.16.0-dev.100+def"), true).as_deref(),
+            Some("older than the current master 0.16.0-dev.500+abc (2025-10-01)")
+        );
+        assert_eq!(check.reason(&v("0.16.0-dev.500+abc"), true), None);
+        // Without index data nothing is flagged
+        assert_eq!(StaleCheck::behind(0).reason(&v("0.9.0"), false), None);
+    }
+
+    #[tokio::test]
+    async fn stale_listing_keeps_only_stale_versions() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = manager_with_installs(temp.path(), &["0.12.0", "0.15.1"]).await;
+        let listing = InstalledListing {
+            stale: Some(StaleCheck {
+                latest_stable: Some(Version::new(0, 15, 1)),
+                ..StaleCheck::behind(1)
+            }),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(installed_versions(&manager, &listing)).unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["version"], "0.12.0");
+        assert_eq!(
+            entries[0]["stale"],
+            "3 minor versions behind the latest stable 0.15.1"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn active_version_reached_through_another_path_is_marked() {
+        let temp = tempfile::tempdir().unwrap();
+        let versions = temp.path().join("versions");
+        for version in ["0.13.0", "0.14.1"] {
+            let dir = versions.join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(crate::Shim::Zig.executable_name()), "").unwrap();
+        }
+        let mut app = App::with_zv_dir(temp.path()).await.unwrap();
+        std::fs::write(app.bin_path().join(crate::Shim::Zv.executable_name()), "zv").unwrap();
+        let linked = temp.path().join("linked-versions");
+        std::os::unix::fs::symlink(&versions, &linked).unwrap();
+        app.toolchain_manager
+            .set_active_version_with_path(
+                &crate::ResolvedZigVersion::Semver(Version::new(0, 14, 1)),
+                linked
+                    .join("0.14.1")
+                    .join(crate::Shim::Zig.executable_name()),
+            )
+            .await
+            .unwrap();
+
+        let installed = installed_versions(&app.toolchain_manager, &InstalledListing::default());
+        let active: Vec<_> = installed
+            .iter()
+            .filter(|i| i.is_active)
+            .map(|i| i.version.as_str())
+            .collect();
+        assert_eq!(active, ["0.14.1"]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::app::CacheStrategy;
+use crate::app::network::mirror::{Mirror, MirrorStats};
+use crate::tools::human_size;
+use crate::{App, Result, ZvError};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use yansi::Paint;
+
+#[derive(Serialize)]
+struct MirrorReport<'a> {
+    url: &'a str,
+    rank: u8,
+    #[serde(flatten)]
+    stats: &'a MirrorStats,
+}
+
+/// Show per-mirror download history from mirrors.toml, or clear it with `reset`
+pub(crate) async fn stats(app: &mut App, json: bool, reset: bool) -> Result<()> {
+    let mirror_manager = app.mirror_manager().await?;
+    mirror_manager
+        .load_mirrors(CacheStrategy::PreferCache)
+        .await
+        .map_err(ZvError::NetworkError)?;
+
+    if reset {
+        let cleared = mirror_manager.reset_stats();
+        mirror_manager
+            .save_index_to_disk()
+            .await
+            .map_err(ZvError::NetworkError)?;
+        println!(
+            "{} Cleared download stats for {} mirror{}",
+            Paint::green("✓"),
+            cleared,
+            if cleared == 1 { "" } else { "s" }
+        );
+        return Ok(());
+    }
+
+    let mirrors = mirror_manager
+        .sort_by_rank()
+        .await
+        .map_err(ZvError::NetworkError)?;
+
+    if json {
+        let report: Vec<MirrorReport> = mirrors
+            .iter()
+            .map(|m| MirrorReport {
+                url: m.base_url.as_str(),
+                rank: m.rank,
+                stats: &m.stats,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if mirrors.iter().all(|m| m.stats.is_empty()) {
+        println!(
+            "{}",
+            "No downloads recorded from community mirrors yet.".italic()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "  {:<5} {:>4} {:>6} {:>5}  {:<12} {:<12} {:>9}  URL",
+        "RANK", "OK", "FAILED", "RATE", "LAST OK", "LAST FAIL", "SERVED"
+    );
+    for mirror in mirrors.iter() {
+        println!("  {}", render_row(mirror));
+    }
+    Ok(())
+}
+
+fn render_row(mirror: &Mirror) -> String {
+    let stats = &mirror.stats;
+    let rate = match stats.successes + stats.failures {
+        0 => "-".to_string(),
+        total => format!("{}%", stats.successes * 100 / total),
+    };
+    let failures = format!("{:>6}", stats.failures);
+    let failures = if stats.failures > stats.successes {
+        Paint::red(&failures).to_string()
+    } else {
+        failures
+    };
+    format!(
+        "{:<5} {:>4} {} {:>5}  {:<12} {:<12} {:>9}  {}",
+        format!("#{}", mirror.rank),
+        stats.successes,
+        failures,
+        rate,
+        format_when(stats.last_success),
+        format_when(stats.last_failure),
+        human_size(stats.bytes_served),
+        mirror.base_url
+    )
+}
+
+fn format_when(when: Option<DateTime<Utc>>) -> String {
+    when.map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "never".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_shows_counts_rate_and_size() {
+        let mut mirror = Mirror::try_from("https://example.com/zig").unwrap();
+        mirror.rank = 2;
+        mirror.stats.record_success(3 * 1024 * 1024);
+        mirror.stats.record_failure();
+        mirror.stats.record_failure();
+        mirror.stats.record_success(1024 * 1024);
+
+        let row = render_row(&mirror);
+        assert!(row.starts_with("#2"), "{row}");
+        assert!(row.contains("50%"), "{row}");
+        assert!(row.contains("4.0 MB"), "{row}");
+        assert!(row.ends_with("https://example.com/zig"), "{row}");
+
+        let fresh = Mirror::try_from("https://other.example.org").unwrap();
+        assert!(render_row(&fresh).contains("never"));
+    }
+}
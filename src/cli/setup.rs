@@ -10,8 +10,8 @@ use yansi::Paint;
 
 #[cfg(not(target_os = "linux"))]
 /// Print the XDG directory layout table and, if any directories are missing,
-/// prompt the user to create them. Returns `false` if the user declined creation.
-fn print_dir_table_and_ensure(app: &App) -> crate::Result<bool> {
+/// prompt the user to create them (unless `force`). Returns `false` if the user declined creation.
+fn print_dir_table_and_ensure(app: &App, force: bool) -> crate::Result<bool> {
     use crate::shell::path_utils::check_dir_in_path;
     use std::io::{self, Write};
 
@@ -105,8 +105,8 @@ fn print_dir_table_and_ensure(app: &App) -> crate::Result<bool> {
         }
         println!();
 
-        if !crate::tools::supports_interactive_prompts() {
-            // Non-interactive: create without asking
+        if force || !crate::tools::supports_interactive_prompts() {
+            // Non-interactive or --force: create without asking
             for dir in &dirs_to_create {
                 std::fs::create_dir_all(dir)?;
             }
@@ -131,18 +131,34 @@ fn print_dir_table_and_ensure(app: &App) -> crate::Result<bool> {
     Ok(true)
 }
 
-/// Main setup_shell function that orchestrates the three-phase setup process
-/// This is the public interface that maintains backward compatibility and supports interactive mode
+/// `--rc-file` given where setup never writes a startup file, explained by `reason`
+fn rc_file_unused(reason: &str) -> color_eyre::eyre::Report {
+    color_eyre::eyre::eyre!("--rc-file can't be used here: {reason}")
+}
 
+/// Main setup_shell function that orchestrates the three-phase setup process
+/// This is the public interface that maintains backward compatibility and supports interactive mode.
+/// Returns an error when `app` has no shell, e.g. one built from `UserConfig::with_no_shell` in tests.
 pub async fn setup_shell(
     #[allow(unused_variables)] app: &mut App,
     #[allow(unused_variables)] using_env_var: bool,
     #[allow(unused_variables)] dry_run: bool,
     #[allow(unused_variables)] no_interactive: bool,
+    #[allow(unused_variables)] force: bool,
+    #[allow(unused_variables)] rc_file: Option<std::path::PathBuf>,
 ) -> crate::Result<()> {
+    if app.shell.is_none() {
+        return Err(color_eyre::eyre::eyre!(
+            "zv setup needs a shell to configure, but this zv instance was started without one"
+        ));
+    }
+
     // On Linux, zv setup is a no-op — XDG dirs handle everything
     #[cfg(target_os = "linux")]
     {
+        if rc_file.is_some() {
+            return Err(rc_file_unused("zv setup changes nothing on Linux"));
+        }
         println!(
             "{} No setup needed. Your system uses XDG directories. Run {} to initialize.",
             Paint::green("✓"),
@@ -154,6 +170,11 @@ pub async fn setup_shell(
     // On macOS Tier 1 (XDG dirs exist), same as Linux
     #[cfg(target_os = "macos")]
     if app.paths.tier == 1 && !using_env_var {
+        if rc_file.is_some() {
+            return Err(rc_file_unused(
+                "zv setup changes nothing when XDG directories are in use",
+            ));
+        }
         println!(
             "{} No setup needed. Your system uses XDG directories. Run {} to initialize.",
             Paint::green("✓"),
@@ -164,15 +185,25 @@ pub async fn setup_shell(
 
     #[cfg(not(target_os = "linux"))]
     {
+        if let Some(shell) = &app.shell
+            && rc_file.is_some()
+            && shell.is_windows_shell()
+            && !shell.is_powershell_in_unix()
+        {
+            return Err(rc_file_unused(&format!(
+                "{shell} has no startup file to edit, zv setup adds zv to PATH in the registry"
+            )));
+        }
+
         if !dry_run {
-            let proceed = print_dir_table_and_ensure(app)?;
+            let proceed = print_dir_table_and_ensure(app, force)?;
             if !proceed {
                 return Ok(());
             }
         }
 
         // Check if shell environment is already set up
-        if app.source_set {
+        if app.is_setup() {
             println!(
                 "{}",
                 Paint::white("✓ Shell environment PATH already includes path to zv")
@@ -186,13 +217,14 @@ pub async fn setup_shell(
                 using_env_var,
                 dry_run,
                 no_interactive,
-            );
+            )
+            .with_force(force)
+            .with_rc_file(rc_file.clone());
             post_setup_actions(&context).await?;
             return Ok(());
         }
 
-        // App::init() for zv_main() ensures shell is always here
-        // but in the rare case, fallback to default which calls Shell::detect()
+        // Checked at the top, so the default is never used
         let shell = app.shell.clone().unwrap_or_default();
 
         // Create setup context with interactive mode control
@@ -202,7 +234,9 @@ pub async fn setup_shell(
             using_env_var,
             dry_run,
             no_interactive,
-        );
+        )
+        .with_force(force)
+        .with_rc_file(rc_file);
 
         if dry_run {
             println!(
@@ -243,10 +277,8 @@ pub async fn setup_shell(
                             }
                             requirements
                         } else {
-                            // User explicitly cancelled or non-recoverable error
-                            if let Some(suggestion) = handle_interactive_error(zv_error) {
-                                crate::tools::error(suggestion);
-                            }
+                            // User explicitly cancelled or non-recoverable error; zv_main
+                            // prints its recovery suggestion
                             return Err(e);
                         }
                     } else {
@@ -283,13 +315,13 @@ pub async fn setup_shell(
 /// Determine if interactive mode should be used based on context and environment
 ///
 /// Interactive mode is automatically disabled when:
-/// - `--no-interactive` flag is provided
+/// - `--no-interactive` or `--force` flag is provided
 /// - CI environment is detected (CI environment variable is set)
 /// - TERM environment variable is set to "dumb"
 /// - TTY is not available for interactive prompts
 fn should_use_interactive(context: &SetupContext) -> bool {
     // Don't use interactive mode if explicitly disabled
-    if context.no_interactive {
+    if context.no_interactive || context.force {
         return false;
     }
 
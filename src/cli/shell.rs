@@ -0,0 +1,56 @@
+use crate::app::constants::ZV_ACTIVE_OVERRIDE;
+use crate::{App, ResolvedZigVersion, Result, Shell, ZigVersion};
+use color_eyre::eyre::eyre;
+use std::process::{Command, Stdio};
+use yansi::Paint;
+
+/// Spawn the user's shell with an installed Zig toolchain first on PATH.
+/// Nothing is written to zv.toml or the shims; the override ends when the subshell exits.
+/// Nesting is refused unless `allow_shell` (`zv --allow-shell shell`) is set.
+pub(crate) async fn shell(app: &App, version: ZigVersion, allow_shell: bool) -> Result<()> {
+    if let Ok(current) = std::env::var(ZV_ACTIVE_OVERRIDE) {
+        if !allow_shell {
+            crate::tools::error(format!("Already inside a zv shell for Zig {current}"));
+            crate::suggest!(
+                "Leave it with {} before starting another, or pass {} to nest",
+                cmd = "exit",
+                crate::tools::format_cmd("zv --allow-shell shell <version>")
+            );
+            return Err(eyre!("Nested zv shell"));
+        }
+        crate::tools::warn(format!("Nesting inside the zv shell for Zig {current}"));
+    }
+
+    let (resolved, zig_path) = super::exec::require_installed(app, &version)?;
+    let override_version = match &resolved {
+        ResolvedZigVersion::Semver(v) => v.to_string(),
+        ResolvedZigVersion::Master(v) => format!("master@{v}"),
+    };
+    let shell = app.shell.clone().unwrap_or_else(Shell::detect);
+    let program = shell.program();
+
+    println!(
+        "{} Entering {} with Zig {} {}",
+        Paint::cyan("→"),
+        Paint::green(&program),
+        Paint::yellow(&override_version),
+        Paint::new("(type `exit` to leave)").dim()
+    );
+    let status = Command::new(&program)
+        .env("PATH", super::exec::toolchain_path(app, &zig_path)?)
+        .env("ZIG", &zig_path)
+        .env(ZV_ACTIVE_OVERRIDE, &override_version)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| eyre!("Failed to launch {}: {}", program, e))?;
+
+    tracing::debug!(target: "zv::cli::shell", "Subshell exited with {status}");
+    println!(
+        "{} Left zv shell for Zig {}",
+        Paint::cyan("←"),
+        Paint::yellow(&override_version)
+    );
+    Ok(())
+}
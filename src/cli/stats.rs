@@ -1,6 +1,6 @@
 use crate::app::config;
-use crate::shell::path_utils::check_dir_in_path_for_shell;
-use crate::tools::{ZvPaths, canonicalize};
+use crate::app::toolchain::{ActiveZigIntegrity, ShimLink};
+use crate::tools::{ZvPaths, canonicalize, dir_size, human_size};
 use crate::{App, ResolvedZigVersion, Result, Shell};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
@@ -75,6 +75,10 @@ struct StatsReport {
     using_env_var: bool,
     zv_version: &'static str,
     active_zig: Option<String>,
+    /// `None` when no hash was recorded for the active zig
+    active_zig_verified: Option<bool>,
+    /// How the shims in bin/ were made, `null` when there are none to look at
+    shim_link: Option<ShimLink>,
     groups: Vec<Group>,
     path_check: PathCheck,
 }
@@ -85,11 +89,12 @@ fn ser_path<S: serde::Serializer>(p: &Path, s: S) -> std::result::Result<S::Ok,
 
 // ─── entry point ─────────────────────────────────────────────────────────────
 
-pub async fn run(app: &App, verbose: bool, json: bool, no_color: bool) -> Result<()> {
+pub async fn run(app: &mut App, verbose: bool, json: bool, no_color: bool) -> Result<()> {
     if no_color || json {
         yansi::disable();
     }
-    let report = collect(app, verbose);
+    let index_freshness = index_freshness(app).await;
+    let report = collect(app, verbose, index_freshness.as_ref());
     if json {
         println!("{}", serde_json::to_string_pretty(&report)?);
     } else {
@@ -100,9 +105,66 @@ pub async fn run(app: &App, verbose: bool, json: bool, no_color: bool) -> Result
 
 // ─── collectors ──────────────────────────────────────────────────────────────
 
-fn collect(app: &App, verbose: bool) -> StatsReport {
+/// How old the cached index is by its own `last_synced` stamp, and how long until it expires
+pub(super) struct IndexFreshness {
+    age: chrono::Duration,
+    pub(super) ttl_remaining: Option<std::time::Duration>,
+}
+
+impl IndexFreshness {
+    /// e.g. "5 hours old, refreshes in 20 days"
+    pub(super) fn describe(&self) -> String {
+        let age = format_hours(self.age.num_hours());
+        match self.ttl_remaining {
+            Some(remaining) => format!(
+                "{age} old, refreshes in {}",
+                format_hours((remaining.as_secs() / 3600) as i64)
+            ),
+            None => format!("{age} old, expired"),
+        }
+    }
+
+    fn stale_level(&self) -> StaleLevel {
+        match self.ttl_remaining {
+            None => 2,
+            Some(remaining) if remaining.as_secs() <= 3 * 86400 => 1,
+            Some(_) => 0,
+        }
+    }
+}
+
+/// Load the cached index without touching the network and read its freshness
+pub(super) async fn index_freshness(app: &mut App) -> Option<IndexFreshness> {
+    let manager = app.index_manager().await.ok()?;
+    let last_synced = manager
+        .ensure_loaded(crate::app::network::CacheStrategy::OnlyCache)
+        .await
+        .ok()?
+        .last_synced()?;
+    Some(IndexFreshness {
+        age: chrono::Utc::now() - last_synced,
+        ttl_remaining: manager.get_ttl_remaining(),
+    })
+}
+
+/// Hours below two days, whole days above
+pub(super) fn format_hours(hours: i64) -> String {
+    let (n, unit) = if hours < 48 {
+        (hours, "hour")
+    } else {
+        (hours / 24, "day")
+    };
+    format!("{n} {unit}{}", if n == 1 { "" } else { "s" })
+}
+
+fn collect(app: &App, verbose: bool, index_freshness: Option<&IndexFreshness>) -> StatsReport {
     let paths = &app.paths;
     let active_zig = app.get_active_version().map(|v| v.to_string());
+    let active_zig_verified = match app.verify_active_zig() {
+        ActiveZigIntegrity::Unrecorded => None,
+        ActiveZigIntegrity::Verified => Some(true),
+        ActiveZigIntegrity::Mismatch { .. } => Some(false),
+    };
     let layout = detect_layout(paths);
     let fold_config = paths.config_dir == paths.data_dir;
     let fold_cache = paths.cache_dir == paths.data_dir;
@@ -115,12 +177,13 @@ fn collect(app: &App, verbose: bool) -> StatsReport {
         fold_cache,
         &zls_cfg,
         verbose,
+        index_freshness,
     ));
     if !fold_config {
         groups.push(collect_config(paths, &zls_cfg));
     }
     if !fold_cache {
-        groups.push(collect_cache(paths, verbose));
+        groups.push(collect_cache(paths, verbose, index_freshness));
     }
     if let Some(ref pub_dir) = paths.public_bin_dir {
         groups.push(collect_public_bin(pub_dir));
@@ -131,8 +194,10 @@ fn collect(app: &App, verbose: bool) -> StatsReport {
         using_env_var: paths.using_env_var,
         zv_version: env!("CARGO_PKG_VERSION"),
         active_zig,
+        active_zig_verified,
+        shim_link: ShimLink::detect(&paths.bin_dir),
         groups,
-        path_check: build_path_check(paths),
+        path_check: build_path_check(paths, app.is_setup()),
     }
 }
 
@@ -156,6 +221,7 @@ fn collect_data(
     fold_cache: bool,
     zls_cfg: &Option<config::ZvConfig>,
     verbose: bool,
+    index_freshness: Option<&IndexFreshness>,
 ) -> Group {
     let paths = &app.paths;
     let mut entries = Vec::new();
@@ -231,7 +297,7 @@ fn collect_data(
         entries.push(toml_entry(&paths.config_file, zls_cfg));
     }
     if fold_cache {
-        entries.extend(cache_file_entries(paths, verbose));
+        entries.extend(cache_file_entries(paths, verbose, index_freshness));
     }
 
     let size = entries.iter().map(|e| e.size).sum();
@@ -286,8 +352,12 @@ fn toml_entry(config_file: &Path, zls_cfg: &Option<config::ZvConfig>) -> Entry {
     }
 }
 
-fn collect_cache(paths: &ZvPaths, verbose: bool) -> Group {
-    let entries = cache_file_entries(paths, verbose);
+fn collect_cache(
+    paths: &ZvPaths,
+    verbose: bool,
+    index_freshness: Option<&IndexFreshness>,
+) -> Group {
+    let entries = cache_file_entries(paths, verbose, index_freshness);
     let size = entries.iter().map(|e| e.size).sum();
     Group {
         title: "Cache",
@@ -298,7 +368,11 @@ fn collect_cache(paths: &ZvPaths, verbose: bool) -> Group {
     }
 }
 
-fn cache_file_entries(paths: &ZvPaths, verbose: bool) -> Vec<Entry> {
+fn cache_file_entries(
+    paths: &ZvPaths,
+    verbose: bool,
+    index_freshness: Option<&IndexFreshness>,
+) -> Vec<Entry> {
     let mut out = Vec::new();
     let ttl_index = *crate::app::INDEX_TTL_DAYS;
     let ttl_mirrors = *crate::app::MIRRORS_TTL_DAYS;
@@ -350,8 +424,13 @@ fn cache_file_entries(paths: &ZvPaths, verbose: bool) -> Vec<Entry> {
         children: vec![],
     });
 
-    // index.toml
-    out.push(ttl_entry(&paths.index_file, "index.toml", ttl_index));
+    // index.toml.gz, aged by its sync stamp rather than mtime when it can be read
+    let mut index_entry = ttl_entry(&paths.index_file, "index.toml.gz", ttl_index);
+    if let Some(freshness) = index_freshness {
+        index_entry.annotation = Some(freshness.describe());
+        index_entry.stale = freshness.stale_level();
+    }
+    out.push(index_entry);
     // mirrors.toml
     out.push(ttl_entry(&paths.mirrors_file, "mirrors.toml", ttl_mirrors));
 
@@ -509,7 +588,6 @@ fn bin_entries(dir: &Path) -> Vec<Entry> {
 }
 
 fn version_entries(app: &App) -> (Vec<Entry>, u64) {
-    let versions_dir = &app.paths.versions_dir;
     let mut installs = app.toolchain_manager.list_installations();
     installs.sort_by(|a, b| b.0.cmp(&a.0));
 
@@ -523,10 +601,8 @@ fn version_entries(app: &App) -> (Vec<Entry>, u64) {
             ResolvedZigVersion::Semver(version.clone())
         };
         let dir = app
-            .toolchain_manager
-            .is_version_installed(&rzv)
-            .and_then(|p| p.parent().map(|d| d.to_path_buf()))
-            .unwrap_or_else(|| versions_dir.join(version.to_string()));
+            .get_version_install_path(&rzv)
+            .unwrap_or_else(|| app.toolchain_manager.version_install_dir(&rzv));
 
         let size = if dir.is_dir() { dir_size(&dir) } else { 0 };
         total += size;
@@ -625,7 +701,8 @@ fn flat_dir_entries(dir: &Path) -> Vec<Entry> {
 
 // ─── PATH check ──────────────────────────────────────────────────────────────
 
-fn build_path_check(paths: &ZvPaths) -> PathCheck {
+/// `expected_in_path` is [`App::is_setup`], so `zv stats` agrees with the rest of zv
+fn build_path_check(paths: &ZvPaths, expected_in_path: bool) -> PathCheck {
     let shell = Shell::detect();
     let current_exe = std::env::current_exe()
         .ok()
@@ -640,8 +717,6 @@ fn build_path_check(paths: &ZvPaths) -> PathCheck {
         .as_ref()
         .unwrap_or(&paths.bin_dir)
         .clone();
-    let expected_in_path =
-        expected_dir.is_dir() && check_dir_in_path_for_shell(&shell, &expected_dir);
 
     let path_var = std::env::var("PATH").unwrap_or_default();
     let sep = shell.get_path_separator();
@@ -680,32 +755,6 @@ fn find_in_path(path_var: &str, sep: char, target: &Path) -> (bool, Option<Strin
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
-fn dir_size(path: &Path) -> u64 {
-    walkdir::WalkDir::new(path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter_map(|e| e.metadata().ok())
-        .map(|m| m.len())
-        .sum()
-}
-
-fn human_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    let mut v = bytes as f64;
-    let mut i = 0;
-    while v >= 1024.0 && i < UNITS.len() - 1 {
-        v /= 1024.0;
-        i += 1;
-    }
-    if i == 0 {
-        format!("{bytes} B")
-    } else {
-        format!("{v:.1} {}", UNITS[i])
-    }
-}
-
 fn file_age_days(path: &Path) -> Option<i64> {
     let elapsed = std::fs::metadata(path)
         .ok()?
@@ -751,9 +800,26 @@ fn render_tree(report: &StatsReport) {
     );
 
     match &report.active_zig {
-        Some(v) => println!("active zig: {}", Paint::green(v).bold()),
+        Some(v) => {
+            let integrity = match report.active_zig_verified {
+                Some(true) => format!("  {}", Paint::new("(sha256 verified)").dim()),
+                Some(false) => format!("  {}", Paint::red("(sha256 MISMATCH)").bold()),
+                None => String::new(),
+            };
+            println!("active zig: {}{}", Paint::green(v).bold(), integrity);
+        }
         None => println!("active zig: {}", Paint::new("none").dim()),
     }
+    match report.shim_link {
+        Some(ShimLink::Symlink) => println!("shims: {}", Paint::new("symlinks").dim()),
+        Some(ShimLink::HardLink) => println!("shims: {}", Paint::new("hard links").dim()),
+        Some(ShimLink::Copy) => println!(
+            "shims: {}  {}",
+            Paint::yellow("copies of zv"),
+            Paint::new("(bin/ filesystem supports no links)").dim()
+        ),
+        None => println!("shims: {}", Paint::new("unknown (none deployed)").dim()),
+    }
     println!();
 
     for group in &report.groups {
@@ -921,4 +987,28 @@ mod tests {
         let (found, _) = find_in_path(":/nonexistent:", ':', Path::new("/some/dir"));
         assert!(!found);
     }
+
+    #[test]
+    fn index_freshness_reads_in_hours_then_days() {
+        let fresh = IndexFreshness {
+            age: chrono::Duration::hours(5),
+            ttl_remaining: Some(std::time::Duration::from_secs(20 * 86400 + 3600)),
+        };
+        assert_eq!(fresh.describe(), "5 hours old, refreshes in 20 days");
+        assert_eq!(fresh.stale_level(), 0);
+
+        let expiring = IndexFreshness {
+            age: chrono::Duration::days(20),
+            ttl_remaining: Some(std::time::Duration::from_secs(3600)),
+        };
+        assert_eq!(expiring.describe(), "20 days old, refreshes in 1 hour");
+        assert_eq!(expiring.stale_level(), 1);
+
+        let expired = IndexFreshness {
+            age: chrono::Duration::days(30),
+            ttl_remaining: None,
+        };
+        assert_eq!(expired.describe(), "30 days old, expired");
+        assert_eq!(expired.stale_level(), 2);
+    }
 }
@@ -13,7 +13,13 @@
 use crate::Shim;
 use std::path::Path;
 
-pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
+pub async fn sync(
+    app: &mut crate::App,
+    allow_downgrade: bool,
+    add_mirror: Option<&str>,
+    dump_index: Option<&Path>,
+    rescan: bool,
+) -> crate::Result<()> {
     use yansi::Paint;
 
     println!("{}", "Syncing zv...".cyan());
@@ -23,7 +29,8 @@ pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
 
     // Check and update zv binary (self-install to internal bin)
     println!("  {} Checking zv binary...", "→".blue());
-    let binary_updated = check_and_update_zv_binary(app, false).await?;
+    // --allow-downgrade replaces a newer ZV_DIR/bin/zv without asking
+    let binary_updated = check_and_update_zv_binary_impl(app, false, !allow_downgrade).await?;
 
     // Create public bin symlinks (belt-and-suspenders)
     #[cfg(unix)]
@@ -38,10 +45,33 @@ pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
         eprintln!("  {} Warning: Migration failed: {}", "⚠".yellow(), e);
     }
 
+    if rescan {
+        println!("  {} Rescanning installed versions...", "→".blue());
+        let found = app.toolchain_manager.rescan_installations()?;
+        println!("  {} Found {} installed versions", "✓".green(), found);
+    }
+
     // Fetch zig index
     println!("  {} Refreshing Zig index...", "→".blue());
     app.sync_zig_index().await?;
     println!("  {} Zig index synced successfully", "✓".green());
+    if let Some(dest) = dump_index {
+        app.dump_raw_index(dest).await?;
+        println!(
+            "  {} Wrote upstream index.json to {}",
+            "✓".green(),
+            Paint::cyan(&dest.display().to_string())
+        );
+    }
+    match app.prune_masters_seen().await {
+        Ok(0) => {}
+        Ok(n) => println!(
+            "  {} Pruned {} old entries from masters_seen.toml",
+            "✓".green(),
+            n
+        ),
+        Err(e) => tracing::debug!("Failed to prune masters_seen.toml: {e}"),
+    }
 
     // Fetch mirrors list
     println!("  {} Refreshing community mirrors...", "→".blue());
@@ -51,6 +81,10 @@ pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
         "✓".green(),
         mirror_count
     );
+    if let Some(url) = add_mirror {
+        app.add_mirror(url).await?;
+        println!("  {} Added mirror {}", "✓".green(), Paint::cyan(url));
+    }
 
     // Backfill ZLS mappings for any locally installed Zig versions we haven't seen yet.
     // Network-only: no binaries are downloaded or built here. Failures per-version are
@@ -69,7 +103,7 @@ pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
     println!("{}", "Sync completed successfully!".green().bold());
 
     // On Tier 2/3 (macOS Library or ZV_DIR), warn if PATH not configured
-    if !app.source_set {
+    if !app.is_setup() {
         #[cfg(target_os = "linux")]
         {
             // Linux Tier 1: should never happen since ~/.local/bin is in PATH
@@ -82,24 +116,19 @@ pub async fn sync(app: &mut crate::App) -> crate::Result<()> {
                 "⚠".yellow(),
                 Paint::cyan(&target)
             );
+            crate::suggest!("Add it to PATH in your shell's startup file");
         }
         #[cfg(target_os = "macos")]
         {
             if app.paths.tier == 2 {
-                println!(
-                    "{} PATH not configured. Run {} to add zv to your PATH.",
-                    "⚠".yellow(),
-                    Paint::blue("zv setup")
-                );
+                println!("{} PATH not configured.", "⚠".yellow());
+                crate::suggest!("Add zv to your PATH with {}", cmd = "zv setup");
             }
         }
         #[cfg(windows)]
         {
-            println!(
-                "{} PATH not configured. Run {} to add zv to your PATH.",
-                "⚠".yellow(),
-                Paint::blue("zv setup")
-            );
+            println!("{} PATH not configured.", "⚠".yellow());
+            crate::suggest!("Add zv to your PATH with {}", cmd = "zv setup");
         }
     }
 
@@ -170,7 +199,7 @@ async fn check_and_update_zv_binary_impl(
     }
 
     // Compare checksums
-    match files_have_same_hash(&current_exe, &target_exe) {
+    match files_have_same_hash(&current_exe, &target_exe).await {
         Ok(true) => {
             // Checksums match, versions are the same - no update
             if !quiet {
@@ -228,13 +257,8 @@ async fn check_and_update_zv_binary_impl(
 
                             if !quiet {
                                 println!(
-                                    "  {} {} zv binary ({} -> {})",
+                                    "  {} Downgrading zv binary ({} -> {})",
                                     "→".blue(),
-                                    if prompt_on_downgrade {
-                                        "Downgrading"
-                                    } else {
-                                        "Updating"
-                                    },
                                     Paint::green(&target_version),
                                     Paint::yellow(&current_version)
                                 );
@@ -242,15 +266,7 @@ async fn check_and_update_zv_binary_impl(
                             copy_binary_and_regenerate_shims(&current_exe, &target_exe, app, quiet)
                                 .await?;
                             if !quiet {
-                                println!(
-                                    "  {} zv binary {}",
-                                    "✓".green(),
-                                    if prompt_on_downgrade {
-                                        "downgraded"
-                                    } else {
-                                        "updated"
-                                    }
-                                );
+                                println!("  {} zv binary downgraded", "✓".green());
                             }
                             Ok(true)
                         }
@@ -350,8 +366,8 @@ fn get_binary_version(binary_path: &std::path::Path) -> crate::Result<semver::Ve
 fn prompt_user_to_downgrade() -> crate::Result<bool> {
     use dialoguer::Confirm;
 
-    // If not in a TTY or in CI, default to NO
-    if !crate::tools::is_tty() || std::env::var("CI").is_ok() {
+    // Without a terminal to ask on (no TTY, CI, --no-interactive), default to NO
+    if !crate::tools::supports_interactive_prompts() {
         return Ok(false);
     }
 
@@ -380,6 +396,24 @@ async fn copy_binary_and_regenerate_shims(
         .await
         .with_context(|| format!("Failed to create directory {}", app.bin_path().display()))?;
 
+    // Hard-link and copy shims of the old binary would keep its contents after the swap and no
+    // longer look like zv to shim deployment, so drop them now and let it recreate them
+    if let Ok(old_binary) = same_file::Handle::from_path(target) {
+        let old_sha256 = crate::tools::calculate_file_sha256(target).ok();
+        for shim in [Shim::Zig, Shim::Zls] {
+            let shim_path = app.bin_path().join(shim.executable_name());
+            let is_old_binary = same_file::Handle::from_path(&shim_path)
+                .is_ok_and(|h| h == old_binary)
+                || (old_sha256.is_some()
+                    && crate::tools::calculate_file_sha256(&shim_path).ok() == old_sha256);
+            if !shim_path.is_symlink() && is_old_binary {
+                tokio::fs::remove_file(&shim_path).await.with_context(|| {
+                    format!("Failed to remove old shim at {}", shim_path.display())
+                })?;
+            }
+        }
+    }
+
     // Remove the target first to avoid ETXTBSY on Linux when the binary is running
     if target.exists() {
         tokio::fs::remove_file(target)
@@ -521,9 +555,10 @@ async fn backfill_zls_mappings(app: &crate::App) {
     );
 
     const CONCURRENCY: usize = 4;
+    let timeouts = app.net_timeouts();
     let results: Vec<(String, Result<String, crate::ZvError>)> = stream::iter(missing)
         .map(|zig_ver| async move {
-            let result = crate::app::network::zls::select_version(&zig_ver)
+            let result = crate::app::network::zls::select_version(&zig_ver, &timeouts)
                 .await
                 .map(|r| r.version);
             (zig_ver, result)
@@ -537,6 +572,8 @@ async fn backfill_zls_mappings(app: &crate::App) {
         active_zig: None,
         local_master_zig: None,
         zls: None,
+        master_keep: None,
+        mirror: None,
     });
     config.version = env!("CARGO_PKG_VERSION").to_string();
     let zls_config = config.zls.get_or_insert(ZlsConfig {
@@ -1,4 +1,5 @@
 use crate::App;
+use crate::shell::setup::{SetupJournal, remove_setup_lines};
 use tokio::fs;
 use yansi::Paint;
 
@@ -24,6 +25,19 @@ pub async fn uninstall(app: &mut App) -> crate::Result<()> {
         zv_dir.display()
     );
 
+    remove_rc_file_setup(app).await;
+
+    // A ZV_BIN_DIR outside ZV_DIR is shared with other programs, so only zv's files go
+    let shared_bin_dir = !crate::app::utils::is_within(bin_path, zv_dir);
+    if shared_bin_dir {
+        let removed = app.toolchain_manager.remove_shims();
+        println!(
+            "{} Removed {removed} zv shim(s) from {}",
+            Paint::green("✓"),
+            bin_path.display()
+        );
+    }
+
     match fs::remove_dir_all(zv_dir).await {
         Ok(()) => {
             println!("{} Successfully removed zv directory", Paint::green("✓"));
@@ -39,7 +53,7 @@ pub async fn uninstall(app: &mut App) -> crate::Result<()> {
 
     println!();
 
-    if app.source_set {
+    if app.is_setup() && !shared_bin_dir {
         println!(
             "{}",
             Paint::yellow("⚠ Important: PATH cleanup needed").bold()
@@ -58,3 +72,23 @@ pub async fn uninstall(app: &mut App) -> crate::Result<()> {
 
     Ok(())
 }
+
+/// Strip the lines `zv setup` added from every RC file recorded in the setup journal
+async fn remove_rc_file_setup(app: &App) {
+    let journal_file = app.paths.setup_journal_file();
+    let journal = SetupJournal::load(&journal_file).await;
+    for rc_file in journal.rc_files() {
+        match remove_setup_lines(rc_file).await {
+            Ok(true) => println!(
+                "{} Removed zv setup lines from {}",
+                Paint::green("✓"),
+                rc_file.display()
+            ),
+            Ok(false) => {}
+            Err(e) => {
+                crate::tools::warn(format!("Could not clean up {}: {}", rc_file.display(), e))
+            }
+        }
+    }
+    let _ = fs::remove_file(&journal_file).await;
+}
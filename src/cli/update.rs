@@ -6,7 +6,7 @@
 use color_eyre::eyre::{Context, Result, bail, eyre};
 use semver::Version;
 use serde::Deserialize;
-use std::{path::Path, time::Duration};
+use std::path::Path;
 use tokio::task;
 use yansi::Paint;
 
@@ -42,7 +42,7 @@ pub async fn update_zv(app: &mut App, force: bool, include_prerelease: bool) ->
     // Fetch releases from GitHub API
     let client = reqwest::Client::builder()
         .user_agent(utils::zv_agent())
-        .connect_timeout(Duration::from_secs(*crate::app::FETCH_TIMEOUT_SECS))
+        .connect_timeout(app.net_timeouts().fetch)
         .build()
         .wrap_err("Failed to create HTTP client")?;
 
@@ -160,11 +160,10 @@ pub async fn update_zv(app: &mut App, force: bool, include_prerelease: bool) ->
 
     tracing::trace!(target: "zv::update", "Found asset: {}", asset.name);
 
-    // Check if we're running from ZV_DIR/bin/zv or somewhere else
+    // Check if we're running from ZV_DIR/bin/zv (or ZV_BIN_DIR/zv) or somewhere else
     let current_exe = std::env::current_exe().wrap_err("Failed to get current executable path")?;
-    let (zv_dir, _) = tools::fetch_zv_dir()?;
-    let expected_zv_exe_path = zv_dir
-        .join("bin")
+    let expected_zv_exe_path = app
+        .bin_path()
         .join(if cfg!(windows) { "zv.exe" } else { "zv" });
 
     let running_from_zv_dir = tools::canonicalize(&current_exe)
@@ -439,7 +438,7 @@ async fn download_and_replace_binary(
         .trim();
 
     // Verify the downloaded file's checksum
-    utils::verify_checksum(&temp_file_path, expected_shasum)
+    utils::verify_checksum(&temp_file_path, expected_shasum, None)
         .await
         .wrap_err("Checksum verification failed - the downloaded file may be corrupted")?;
 
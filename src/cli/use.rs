@@ -1,4 +1,7 @@
+use super::{UseTarget, parse_use_target};
 use crate::app::Either;
+use crate::app::constants::ZV_DEFAULT_VERSION;
+use crate::app::utils::is_quiet;
 use crate::{ResolvedZigVersion, ZigVersion};
 use crate::{
     Result, ZvError,
@@ -7,17 +10,59 @@ use crate::{
 use color_eyre::eyre::{Context, eyre};
 use yansi::Paint;
 
+/// Read the target for `zv use -` from a single line on stdin
+pub(crate) fn use_target_from_stdin() -> Result<UseTarget> {
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .wrap_err("Failed to read a version from stdin")?;
+    let target = use_target_from("stdin", &line)?;
+    tracing::info!("Using Zig version '{}' read from stdin", line.trim());
+    Ok(target)
+}
+
+/// The target in `ZV_DEFAULT_VERSION`, used when `zv use` is given no version or channel
+pub(crate) fn use_target_from_env() -> Result<Option<UseTarget>> {
+    let Some(value) = std::env::var(ZV_DEFAULT_VERSION)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+    let target = use_target_from(ZV_DEFAULT_VERSION, &value)?;
+    tracing::info!(
+        "Using Zig version '{}' from {ZV_DEFAULT_VERSION}",
+        value.trim()
+    );
+    Ok(Some(target))
+}
+
+/// Parse a version read from `source`, quoting it verbatim if it is invalid
+fn use_target_from(source: &str, raw: &str) -> Result<UseTarget> {
+    let value = raw.trim();
+    if value.is_empty() {
+        return Err(eyre!("No Zig version found on {source}"));
+    }
+    match parse_use_target(value) {
+        Ok(UseTarget::Stdin) => Err(eyre!("Invalid Zig version '{value}' from {source}")),
+        Ok(target) => Ok(target),
+        Err(e) => Err(eyre!("Invalid Zig version '{value}' from {source}: {e}")),
+    }
+}
+
 /// Main entry point for the use command
 pub(crate) async fn use_version(
     zig_version: ZigVersion,
     app: &mut App,
     force_ziglang: bool,
+    reinstall: bool,
     provision_zls: bool,
     zls_download: bool,
+    include_prerelease: bool,
 ) -> Result<()> {
     // Resolve ZigVersion to a validated ResolvedZigVersion
     // This already does all the validation and fetching we need
-    let resolved_version = resolve_zig_version(app, &zig_version).await
+    let resolved_version = resolve_zig_version(app, &zig_version, include_prerelease).await
         .map_err(|e| {
             match e {
                 ZvError::ZigVersionResolveError(err) => {
@@ -30,33 +75,45 @@ pub(crate) async fn use_version(
             }
         })?;
 
-    if let Some(p) = app.check_installed(&resolved_version) {
-        // Version is already installed, just set it as active
+    if let Some(p) = app
+        .check_installed(&resolved_version)
+        .filter(|_| !reinstall)
+    {
+        // Version is already installed (for master, the same build), just set it as active
+        if resolved_version.is_master() && !is_quiet() {
+            println!(
+                "{} Master {} is already installed, reusing it",
+                Paint::cyan("ℹ"),
+                resolved_version.version()
+            );
+        }
         app.set_active_version(&resolved_version, Some(p)).await?
     } else {
-        if let Some(Either::Version(_)) = app.to_install {
-            app.install_direct(force_ziglang).await.wrap_err_with(|| {
+        // Another `zv use` of the same version may be mid-download; wait for it and reuse its work
+        let (zig, reused) = app
+            .install_serialized(&resolved_version, force_ziglang, reinstall)
+            .await
+            .wrap_err_with(|| {
                 format!(
                     "Failed to download and install Zig version {}",
                     resolved_version
                 )
             })?;
+        if reused {
+            if !is_quiet() {
+                println!(
+                    "{} Zig {} was installed by another zv process, reusing it",
+                    Paint::cyan("ℹ"),
+                    resolved_version.version()
+                );
+            }
+            app.set_active_version(&resolved_version, Some(zig)).await?
         } else {
-            app.install_release(force_ziglang).await.wrap_err_with(|| {
-                format!(
-                    "Failed to download and install Zig version {}",
-                    resolved_version
-                )
-            })?;
+            app.set_active_version(&resolved_version, None).await?
         }
-
-        app.set_active_version(&resolved_version, None).await?
     }
 
-    println!(
-        "✅ Active zig version set: {}",
-        Paint::blue(&resolved_version.version().to_string())
-    );
+    print_now_using(app, resolved_version.version());
 
     if provision_zls {
         let active_zig = app
@@ -83,12 +140,126 @@ pub(crate) async fn use_version(
     Ok(())
 }
 
+/// Confirm the switch to `version`: where its zig lives and, while zv's bin dir isn't on PATH
+/// yet, how to get `zig` to resolve to it. With `--quiet`, just the one `activated` line.
+fn print_now_using(app: &App, version: &semver::Version) {
+    if is_quiet() {
+        let zig = app
+            .toolchain_manager
+            .get_active_install()
+            .map(|install| install.path.join(crate::Shim::Zig.executable_name()));
+        match zig {
+            Some(zig) => println!("activated zig {version} at {}", zig.display()),
+            None => println!("activated zig {version}"),
+        }
+        return;
+    }
+    println!("✅ Now using Zig {}", Paint::blue(&version.to_string()));
+    if let Some(install) = app.toolchain_manager.get_active_install() {
+        let zig = install.path.join(crate::Shim::Zig.executable_name());
+        println!("   {}", Paint::new(zig.display()).dim());
+    }
+    if !app.is_setup() {
+        let bin_dir = app.public_bin_path().unwrap_or(app.bin_path());
+        println!(
+            "{} {} is not on your PATH yet. Run {} so `zig` picks this version up.",
+            "→".cyan(),
+            Paint::cyan(&bin_dir.display()),
+            Paint::blue("zv setup")
+        );
+    }
+}
+
+/// Entry point for `zv use <version> --source`: fetch the version's source tarball for
+/// bootstrapping instead of a prebuilt toolchain. The active version is left unchanged.
+pub(crate) async fn use_source(
+    zig_version: ZigVersion,
+    app: &mut App,
+    force_ziglang: bool,
+    reinstall: bool,
+    include_prerelease: bool,
+) -> Result<()> {
+    let resolved_version = resolve_zig_version(app, &zig_version, include_prerelease)
+        .await
+        .wrap_err_with(|| format!("Failed to resolve version '{zig_version}'"))?;
+    let source_dir = crate::app::zig_source::install_zig_source(app, force_ziglang, reinstall)
+        .await
+        .wrap_err_with(|| format!("Failed to fetch sources for Zig {resolved_version}"))?;
+
+    println!(
+        "✅ Zig {} sources are in {}",
+        Paint::blue(&resolved_version.version().to_string()),
+        Paint::cyan(&source_dir.display().to_string())
+    );
+    println!("To build them:");
+    crate::suggest!(
+        "with a Zig of about the same version on PATH: {}",
+        cmd = "zig build -p stage3 -Doptimize=ReleaseFast"
+    );
+    crate::suggest!(
+        "from scratch, with CMake and LLVM installed: {}",
+        cmd = "mkdir build && cd build && cmake .. && make install"
+    );
+    println!(
+        "See {} for the dependencies each route needs.",
+        Paint::cyan("https://github.com/ziglang/zig#building-from-source")
+    );
+    Ok(())
+}
+
+/// Entry point for `zv use system`: defer to a non-zv zig found on PATH
+pub(crate) async fn use_system(app: &mut App) -> Result<()> {
+    let Some(zig_path) = app.toolchain_manager.find_system_zig() else {
+        crate::tools::error("No system zig found on PATH outside of zv");
+        crate::suggest!(
+            "Install zig with your package manager, or let zv manage one with {}",
+            cmd = "zv use <version>"
+        );
+        return Err(eyre!("No system zig found"));
+    };
+
+    let output = std::process::Command::new(&zig_path)
+        .arg("version")
+        .output()
+        .wrap_err_with(|| format!("Failed to run {} version", zig_path.display()))?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "{} version exited with {}",
+            zig_path.display(),
+            output.status
+        ));
+    }
+    let reported = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let version = semver::Version::parse(&reported).wrap_err_with(|| {
+        format!(
+            "Unrecognized version '{}' reported by {}",
+            reported,
+            zig_path.display()
+        )
+    })?;
+
+    crate::cli::sync::check_and_update_zv_binary(app, true)
+        .await
+        .wrap_err("Failed to update zv binary")?;
+    app.toolchain_manager
+        .set_active_external(&zig_path, version.clone())
+        .await?;
+
+    println!(
+        "✅ Now using Zig {} {}",
+        Paint::blue(&version.to_string()),
+        Paint::new(format!("(system zig at {})", zig_path.display())).dim()
+    );
+    Ok(())
+}
+
 /// Resolves a ZigVersion against the app's index using network operations when needed
 ///
 /// # Arguments
 ///
 /// * `app` - Mutable reference to the App instance
 /// * `version` - The ZigVersion to resolve
+/// * `include_prerelease` - Let `latest` pick release candidates too
 ///
 /// # Returns
 ///
@@ -97,6 +268,7 @@ pub(crate) async fn use_version(
 pub async fn resolve_zig_version(
     app: &mut App,
     version: &ZigVersion,
+    include_prerelease: bool,
 ) -> Result<ResolvedZigVersion, ZvError> {
     const TARGET: &str = "zv::resolve_zig_version";
     match version {
@@ -124,6 +296,9 @@ pub async fn resolve_zig_version(
             // Verify the requested version matches the actual master version
             if index_master_version == v {
                 app.to_install = Some(master_release.into());
+            } else if let Some(seen_release) = app.seen_master_release(v).await {
+                tracing::debug!(target: TARGET, "Resolved older master {} from masters_seen.toml", v);
+                app.to_install = Some(seen_release.into());
             } else {
                 tracing::warn!(
                     "Master version mismatch: requested {}, but current master is at {}",
@@ -175,7 +350,9 @@ pub async fn resolve_zig_version(
         ZigVersion::Stable(None) => {
             tracing::trace!(target: TARGET, "Resolving latest stable(none) version");
             // Use RespectTTL strategy for stable versions
-            let stable_release = app.fetch_latest_version(CacheStrategy::RespectTtl).await?;
+            let stable_release = app
+                .fetch_latest_version(CacheStrategy::RespectTtl, false)
+                .await?;
             let stable_version = stable_release.resolved_version().clone();
 
             // Extract the semver from the resolved version
@@ -190,6 +367,15 @@ pub async fn resolve_zig_version(
             }
         }
 
+        // Stable series - pick the newest patch release of it
+        ZigVersion::StableSeries(series) => {
+            tracing::trace!(target: TARGET, "Resolving stable series: {}", series);
+            let release = app.fetch_latest_in_series(series).await?;
+            let version = release.resolved_version().clone();
+            app.to_install = Some(release.into());
+            Ok(version)
+        }
+
         // Latest with specific version - validate it exists (no stability check)
         ZigVersion::Latest(Some(v)) => {
             tracing::trace!(target: TARGET, "Resolving latest version: {}", v);
@@ -198,12 +384,12 @@ pub async fn resolve_zig_version(
             Ok(ResolvedZigVersion::Semver(v.clone()))
         }
 
-        // Latest without version - fetch latest stable version with AlwaysRefresh
+        // Latest without version - fetch latest stable (or pre-release, if asked) version with AlwaysRefresh
         ZigVersion::Latest(None) => {
-            tracing::trace!(target: TARGET, "Resolving latest(none) version");
+            tracing::trace!(target: TARGET, include_prerelease, "Resolving latest(none) version");
             // Use AlwaysRefresh strategy for latest versions
             let latest_release = app
-                .fetch_latest_version(CacheStrategy::AlwaysRefresh)
+                .fetch_latest_version(CacheStrategy::AlwaysRefresh, include_prerelease)
                 .await?;
             let latest_version = latest_release.resolved_version().clone();
 
@@ -220,3 +406,27 @@ pub async fn resolve_zig_version(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_target_from_trims_and_quotes_bad_input() {
+        assert!(matches!(
+            use_target_from("stdin", " 0.13.0\n"),
+            Ok(UseTarget::Version(ZigVersion::Semver(v))) if v == semver::Version::new(0, 13, 0)
+        ));
+        assert!(matches!(
+            use_target_from(ZV_DEFAULT_VERSION, "system"),
+            Ok(UseTarget::System)
+        ));
+
+        let err = use_target_from("stdin", "0.13.x\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'0.13.x' from stdin"), "{err}");
+        assert!(use_target_from("stdin", "-").is_err());
+        assert!(use_target_from("stdin", "\n").is_err());
+    }
+}
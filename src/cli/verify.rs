@@ -0,0 +1,61 @@
+use crate::app::toolchain::ZigInstall;
+use crate::{App, Result, ZigVersion};
+use color_eyre::eyre::eyre;
+use yansi::Paint;
+
+/// Check one installed version, or every one, against the checksums recorded at install time
+pub(crate) async fn verify(app: &mut App, version: Option<ZigVersion>) -> Result<()> {
+    let installs: Vec<ZigInstall> = match version {
+        Some(version) => {
+            let (resolved, zig) = super::exec::require_installed(app, &version)?;
+            vec![ZigInstall {
+                version: resolved.version().clone(),
+                path: zig.parent().map(|p| p.to_path_buf()).unwrap_or(zig),
+                is_master: resolved.is_master(),
+            }]
+        }
+        None => app.toolchain_manager.installations().to_vec(),
+    };
+    if installs.is_empty() {
+        println!("{}", "No Zig versions installed.".italic());
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for install in &installs {
+        let name = if install.is_master {
+            format!("master/{}", install.version)
+        } else {
+            install.version.to_string()
+        };
+        match app.validate_installed_checksum(install).await {
+            Ok(true) => println!("{} {name}", Paint::green("✓")),
+            Ok(false) => {
+                println!(
+                    "{} {name} {}",
+                    Paint::red("✗"),
+                    "differs from the archive it was installed from".dim()
+                );
+                failed.push(install);
+            }
+            Err(e) => println!("{} {name} {}", Paint::yellow("?"), e.to_string().dim()),
+        }
+    }
+
+    if failed.is_empty() {
+        return Ok(());
+    }
+    let reinstall = if failed[0].is_master {
+        format!("master@{}", failed[0].version)
+    } else {
+        failed[0].version.to_string()
+    };
+    crate::suggest!(
+        "Reinstall with {}",
+        cmd = &format!("zv use --reinstall {reinstall}")
+    );
+    Err(eyre!(
+        "{} installation(s) failed verification",
+        failed.len()
+    ))
+}
@@ -1,8 +1,14 @@
+use crate::app::constants::{ZV_DEFAULT_VERSION, ZV_LAST_SHADOW_WARNING_FILE, ZV_LAST_VERIFY_FILE};
+use crate::app::global_default;
+use crate::app::toolchain::ActiveZigIntegrity;
+use crate::app::utils::is_in_dir;
 use crate::cli::r#use::resolve_zig_version;
-use crate::{App, UserConfig, ZigVersion, ZvError, tools};
+use crate::shell::path_utils::first_dir_providing;
+use crate::{App, Shim, UserConfig, ZigVersion, ZvError, tools};
 use color_eyre::eyre::{Context, bail, eyre};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
 pub async fn zig_main() -> crate::Result<()> {
     // Recursion guard - check early to prevent infinite loops
@@ -12,14 +18,18 @@ pub async fn zig_main() -> crate::Result<()> {
     let mut args: Vec<String> = std::env::args().collect();
     args.remove(0); // drop program name
 
+    if let Ok(paths) = tools::ZvPaths::resolve() {
+        warn_if_shim_shadowed(&paths);
+    }
+
     // Check for +version override (only if it's the first argument)
     let inline_version_override = if args.first().is_some_and(|arg| arg.starts_with('+')) {
         Some(args.remove(0).strip_prefix('+').unwrap().to_string())
     } else {
         None
     };
-    // Check for .zigversion file in current directory
 
+    // Precedence: +version > ZIG_VERSION > .zigversion > active > ZV_DEFAULT_VERSION > global default
     let zig_path = if let Some(version_str) = inline_version_override {
         // Parse the version override
         let zv = version_str
@@ -27,29 +37,27 @@ pub async fn zig_main() -> crate::Result<()> {
             .map_err(|e| eyre!("Invalid version override '+{}': {}", version_str, e))?;
 
         find_zig_for_version(&zv).await?
-    } else {
+    } else if let Some(zv) = zig_version_from_env()? {
+        find_zig_for_version(&zv).await.wrap_err(eyre!(
+            "Failed to find zig for version {zv} from ZIG_VERSION"
+        ))?
+    } else if let Some((zv, file)) = find_zigversion_from_file() {
         // Check for .zigversion file in current directory
-        if let Some((zv, file)) = find_zigversion_from_file() {
-            find_zig_for_version(&zv).await.wrap_err(eyre!(
-                "Failed to find zig for version {zv} from file {}",
-                file.display(),
-            ))?
-        }
-        // Default to current active zig
-        else {
-            find_default_zig().await?
-        }
+        find_zig_for_version(&zv).await.wrap_err(eyre!(
+            "Failed to find zig for version {zv} from file {}",
+            file.display(),
+        ))?
+    } else {
+        // Default to current active zig, then ZV_DEFAULT_VERSION, then the global default
+        find_default_zig().await?
     };
 
-    // Get current recursion count for incrementing
-    let recursion_count: u32 = std::env::var("ZV_RECURSION_COUNT")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
+    // Every pass through the shim counts, so a wrapper that calls back into it still trips the guard
+    let recursion_count = crate::app::utils::recursion_count() + 1;
 
     let mut child = Command::new(zig_path)
         .args(args)
-        .env("ZV_RECURSION_COUNT", (recursion_count + 1).to_string())
+        .env("ZV_RECURSION_COUNT", recursion_count.to_string())
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -60,6 +68,11 @@ pub async fn zig_main() -> crate::Result<()> {
         .wait()
         .map_err(|e| eyre!("Failed to wait for zig: {}", e))?;
 
+    exit_with_status(status)
+}
+
+/// Exit zv with the child's exit code, mapping signal termination to `128 + signal` on Unix
+pub(crate) fn exit_with_status(status: std::process::ExitStatus) -> ! {
     if let Some(code) = status.code() {
         std::process::exit(code);
     } else {
@@ -82,7 +95,7 @@ async fn find_zig_for_version(zig_version: &ZigVersion) -> crate::Result<PathBuf
     let mut app = App::init(UserConfig { paths, shell: None }).await?;
     // Resolve ZigVersion to a validated ResolvedZigVersion
     // This already does all the validation and fetching we need
-    let resolved_version = resolve_zig_version(&mut app, zig_version).await
+    let resolved_version = resolve_zig_version(&mut app, zig_version, false).await
         .map_err(|e| {
             match e {
                 ZvError::ZigVersionResolveError(err) => {
@@ -105,7 +118,7 @@ async fn find_zig_for_version(zig_version: &ZigVersion) -> crate::Result<PathBuf
                 tracing::warn!("Retrying with community mirrors...");
 
                 // We need to re-resolve the version since install_release consumed to_install
-                let resolved_version_retry = resolve_zig_version(&mut app, zig_version).await
+                let resolved_version_retry = resolve_zig_version(&mut app, zig_version, false).await
                     .map_err(|e| {
                         match e {
                             ZvError::ZigVersionResolveError(err) => {
@@ -132,17 +145,121 @@ async fn find_zig_for_version(zig_version: &ZigVersion) -> crate::Result<PathBuf
     }
 }
 
-/// Find the default Zig executable (zv-managed or system)
+/// Version requested through the `ZIG_VERSION` environment variable, if set
+fn zig_version_from_env() -> crate::Result<Option<ZigVersion>> {
+    zig_version_from_env_var("ZIG_VERSION")
+}
+
+/// Version in the environment variable `var`, if set to anything but whitespace
+fn zig_version_from_env_var(var: &str) -> crate::Result<Option<ZigVersion>> {
+    match std::env::var(var) {
+        Ok(v) if !v.trim().is_empty() => v
+            .trim()
+            .parse::<ZigVersion>()
+            .map(Some)
+            .map_err(|e| eyre!("Invalid {} '{}': {}", var, v, e)),
+        _ => Ok(None),
+    }
+}
+
+/// Find the default Zig executable: the active zv-managed zig, else `ZV_DEFAULT_VERSION`, else
+/// the global default recorded by `zv default`
 async fn find_default_zig() -> crate::Result<PathBuf> {
-    // Try to get zv-managed zig first
     if let Ok(paths) = tools::ZvPaths::resolve()
         && let Ok(app) = App::init(UserConfig { paths, shell: None }).await
-        && let Some(zig_path) = app.zv_zig()
     {
-        tracing::trace!(target: "zig", "Using zv-managed zig at {}", zig_path.display());
-        return Ok(zig_path);
+        if let Some(zig_path) = app.zv_zig() {
+            tracing::trace!(target: "zig", "Using zv-managed zig at {}", zig_path.display());
+            if should_verify_active(&app) {
+                warn_on_active_zig_mismatch(&app);
+            }
+            return Ok(zig_path);
+        }
+        if let Some(zv) = zig_version_from_env_var(ZV_DEFAULT_VERSION)? {
+            tracing::trace!(target: "zig", "No active zig, using {ZV_DEFAULT_VERSION}={}", zv);
+            return find_zig_for_version(&zv).await.wrap_err(eyre!(
+                "Failed to find zig for version {zv} from {ZV_DEFAULT_VERSION}"
+            ));
+        }
+        let default_file = app.paths.global_default_file();
+        if let Some(zv) = global_default::load(&default_file) {
+            tracing::trace!(target: "zig", "No active zig, using global default {}", zv);
+            return find_zig_for_version(&zv).await.wrap_err(eyre!(
+                "Failed to find zig for global default {zv} from {}",
+                default_file.display(),
+            ));
+        }
+    }
+    bail!(
+        "Could not find zig executable. Run `zv use <version>` or `zv default <version>`, or set {ZV_DEFAULT_VERSION}"
+    )
+}
+
+/// Hashing zig on every invocation is wasteful, so only verify when `ZV_VERIFY_ACTIVE=1`
+/// or when the last verification is more than a day old
+fn should_verify_active(app: &App) -> bool {
+    if std::env::var("ZV_VERIFY_ACTIVE").is_ok_and(|v| v == "1") {
+        return true;
+    }
+
+    stamp_due_daily(&app.paths.cache_dir.join(ZV_LAST_VERIFY_FILE))
+}
+
+/// Whether `stamp` is missing or more than a day old; touches it when it is
+fn stamp_due_daily(stamp: &Path) -> bool {
+    let due = std::fs::metadata(stamp)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_none_or(|elapsed| elapsed >= Duration::from_secs(24 * 60 * 60));
+
+    if due && let Err(e) = std::fs::write(stamp, b"") {
+        tracing::debug!(target: "zig", "Failed to update {}: {}", stamp.display(), e);
+    }
+    due
+}
+
+/// Warn, at most once a day, when the first `zig` on PATH is not zv's shim. Setup may have
+/// run, but a system zig listed before `ZV_DIR/bin` still wins in new shells.
+fn warn_if_shim_shadowed(paths: &tools::ZvPaths) {
+    let Ok(path_var) = std::env::var("PATH") else {
+        return;
+    };
+    let separator = if cfg!(windows) { ';' } else { ':' };
+    let exe = Shim::Zig.executable_name();
+    let Some(first_dir) = first_dir_providing(&path_var, separator, exe) else {
+        return;
+    };
+    if is_in_dir(&first_dir.join(exe), &paths.bin_dir)
+        || !stamp_due_daily(&paths.cache_dir.join(ZV_LAST_SHADOW_WARNING_FILE))
+    {
+        return;
+    }
+    tools::warn(format!(
+        "{} comes before zv's shim in {} on PATH, so `zig` may not run the zv-managed version. \
+         Move {} ahead of it in PATH or run `zv setup`.",
+        first_dir.join(exe).display(),
+        paths.bin_dir.display(),
+        paths.bin_dir.display(),
+    ));
+}
+
+fn warn_on_active_zig_mismatch(app: &App) {
+    if let ActiveZigIntegrity::Mismatch {
+        zig_path,
+        expected,
+        actual,
+    } = app.verify_active_zig()
+    {
+        tools::warn(format!(
+            "Active zig binary {} does not match the hash recorded at activation \
+             (expected sha256 {}, found {}). It may have been replaced or tampered with; \
+             run `zv use <version>` to re-activate or reinstall it.",
+            zig_path.display(),
+            expected,
+            actual.as_deref().unwrap_or("<unreadable>"),
+        ));
     }
-    bail!("Could not find zig executable")
 }
 
 /// Search for a .zigversion file in the current directory or its ancestors
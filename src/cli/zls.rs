@@ -13,15 +13,12 @@ pub async fn zls_main() -> crate::Result<()> {
 
     let zls_path = find_compatible_zls().await?;
 
-    // Get current recursion count for incrementing
-    let recursion_count: u32 = std::env::var("ZV_RECURSION_COUNT")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
+    // Every pass through the shim counts, so a wrapper that calls back into it still trips the guard
+    let recursion_count = crate::app::utils::recursion_count() + 1;
 
     let mut child = Command::new(zls_path)
         .args(args)
-        .env("ZV_RECURSION_COUNT", (recursion_count + 1).to_string())
+        .env("ZV_RECURSION_COUNT", recursion_count.to_string())
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
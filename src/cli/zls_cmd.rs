@@ -16,8 +16,10 @@ pub(crate) async fn provision_zls(
     update: bool,
 ) -> crate::Result<()> {
     if !app.is_initialized() {
-        crate::tools::error(
-            "zv is not initialized. Run 'zv sync' first to set up directories and the zv binary.",
+        crate::tools::error("zv is not initialized");
+        crate::suggest!(
+            "Set up its directories and the zv binary with {}",
+            cmd = "zv sync"
         );
         std::process::exit(1);
     }
@@ -79,7 +81,7 @@ pub(crate) async fn provision_zls_for(
         return Ok(());
     }
 
-    let release = zls_api::select_version(&zig_version_string)
+    let release = zls_api::select_version(&zig_version_string, &app.net_timeouts())
         .await
         .map_err(|e| {
             eyre!(
@@ -4,9 +4,11 @@ use color_eyre::{
 };
 use tracing_subscriber::prelude::*;
 
-// We only expect to route to `zig` or `zls` once from `zv`
-// For example: `zv init --zig`  => `zv` spawns `zig`, +1 in [instantiate_zig]
-const ZV_RECURSION_MAX: u32 = 1;
+// Every pass through the `zig`/`zls` shim adds one, e.g. `zv init --zig` => `zv` spawns `zig`,
+// +1 in [instantiate_zig]. The headroom lets `zig build` steps run `zig` through the shim again,
+// while a loop (say, a `zv use system` wrapper script that calls `zig`) still stops quickly.
+// Overridable with the `ZV_RECURSION_MAX` environment variable
+const ZV_RECURSION_MAX: u32 = 8;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -66,7 +68,9 @@ fn init_tracing() -> Result<()> {
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr) // Keep stdout for command output
                     .with_target(true) // Show module paths
+                    .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE) // Report phase timings
                     .with_filter(
                         tracing_subscriber::EnvFilter::try_from_env("ZV_LOG")
                             .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("zv=warn")),
@@ -74,10 +78,14 @@ fn init_tracing() -> Result<()> {
             )
             .init();
     } else {
-        // Simple user-friendly logging mode
+        // Simple user-friendly logging mode; `--quiet` raises the filter to warn
+        let (filter, handle) =
+            tracing_subscriber::reload::Layer::new(tracing_subscriber::EnvFilter::new("zv=info"));
+        app::utils::set_log_filter_handle(handle);
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr) // Keep stdout for command output
                     .with_target(false) // Hide module paths
                     .with_level(true) // Show level
                     .with_thread_ids(false)
@@ -85,7 +93,7 @@ fn init_tracing() -> Result<()> {
                     .with_file(false)
                     .with_line_number(false)
                     .without_time() // No timestamps
-                    .with_filter(tracing_subscriber::EnvFilter::new("zv=info")),
+                    .with_filter(filter),
             )
             .init();
     }
@@ -141,16 +149,18 @@ pub fn apply_windows_security_mitigations() {
 /// Check recursion depth with context for better error messages
 pub fn check_recursion_with_context(context: &str) -> Result<()> {
     // Recursion guard - prevent infinite loops but allow zig subcommands such as zv init --zig :  zv -> zig
-    let recursion_count = std::env::var("ZV_RECURSION_COUNT")
-        .unwrap_or_else(|_| "0".to_string())
-        .parse::<u32>()
-        .unwrap_or(0);
+    let recursion_count = app::utils::recursion_count();
+    let recursion_max = std::env::var("ZV_RECURSION_MAX")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(ZV_RECURSION_MAX);
 
-    if recursion_count > ZV_RECURSION_MAX {
+    if recursion_count > recursion_max {
         eprintln!(
             "Error: Too many recursive calls detected in {} (depth: {}). \
-             The zv binary may be calling itself infinitely.",
-            context, recursion_count
+             The zv binary may be calling itself infinitely. \
+             Raise ZV_RECURSION_MAX (currently {}) if this nesting is intended.",
+            context, recursion_count, recursion_max
         );
         std::process::exit(1);
     }
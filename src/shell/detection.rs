@@ -1,28 +1,98 @@
 use super::ShellType;
 use crate::tools::is_tty;
-use sysinfo::System;
-
-/// Get the parent process name using sysinfo
-pub fn get_parent_process_name() -> Option<String> {
-    let mut system = System::new();
-    system.refresh_processes_specifics(
-        sysinfo::ProcessesToUpdate::All,
-        true,
-        sysinfo::ProcessRefreshKind::everything(),
-    );
-
-    let current_pid = sysinfo::get_current_pid().ok()?;
-    let current_process = system.process(current_pid)?;
-    let parent_pid = current_process.parent()?;
-    let parent_process = system.process(parent_pid)?;
-
-    Some(parent_process.name().to_string_lossy().to_string())
+
+/// PID of this process's parent
+#[cfg(unix)]
+pub fn parent_pid() -> Option<u32> {
+    Some(std::os::unix::process::parent_id())
+}
+
+/// PID of this process's parent, from `NtQueryInformationProcess`
+#[cfg(windows)]
+pub fn parent_pid() -> Option<u32> {
+    use windows_sys::Wdk::System::Threading::{NtQueryInformationProcess, ProcessBasicInformation};
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, PROCESS_BASIC_INFORMATION};
+
+    let mut info: PROCESS_BASIC_INFORMATION = unsafe { std::mem::zeroed() };
+    let mut returned = 0u32;
+    let status = unsafe {
+        NtQueryInformationProcess(
+            GetCurrentProcess(),
+            ProcessBasicInformation,
+            (&mut info as *mut PROCESS_BASIC_INFORMATION).cast(),
+            std::mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+            &mut returned,
+        )
+    };
+    // NTSTATUS values below zero are errors
+    (status >= 0).then(|| info.InheritedFromUniqueProcessId as u32)
+}
+
+/// Executable name of process `pid`, from `/proc/<pid>/comm`
+#[cfg(target_os = "linux")]
+pub fn process_name(pid: u32) -> Option<String> {
+    let comm = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    clean_process_name(&comm)
+}
+
+/// Executable name of process `pid`, from `ps -o comm=`
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn process_name(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "comm=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    clean_process_name(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Executable name of process `pid`, from its full image path
+#[cfg(windows)]
+pub fn process_name(pid: u32) -> Option<String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
+    };
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        return None;
+    }
+    let mut buffer = [0u16; 1024];
+    let mut len = buffer.len() as u32;
+    let ok = unsafe {
+        QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len)
+    };
+    unsafe { CloseHandle(handle) };
+    if ok == 0 {
+        return None;
+    }
+    clean_process_name(&String::from_utf16_lossy(&buffer[..len as usize]))
+}
+
+/// Reduce a reported process name to the bare executable name: drop any directory, the
+/// `.exe` suffix, and the leading `-` of login shells
+fn clean_process_name(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let name = raw.rsplit(['/', '\\']).next().unwrap_or(raw);
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    let name = name.trim_start_matches('-');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Detect the shell running as process `pid`
+pub fn detect_shell_from_pid(pid: u32) -> Option<ShellType> {
+    let name = process_name(pid)?;
+    tracing::debug!(target: "shell_detection", pid, name, "Process name");
+    detect_shell_from_string(&name.to_lowercase())
 }
 
-/// Detect shell from parent process name
-pub fn detect_shell_from_parent() -> Option<ShellType> {
-    let parent_name = get_parent_process_name()?;
-    detect_shell_from_string(&parent_name.to_lowercase())
+/// Detect the shell this process was started from, via the parent PID
+fn detect_shell_from_parent() -> Option<ShellType> {
+    detect_shell_from_pid(parent_pid()?)
 }
 
 /// Detect shell from any string containing shell information
@@ -131,3 +201,29 @@ fn detect_unix_shell() -> ShellType {
 
     ShellType::Unknown
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_process_name_strips_paths_and_login_dash() {
+        assert_eq!(clean_process_name("-zsh\n").as_deref(), Some("zsh"));
+        assert_eq!(
+            clean_process_name("/usr/local/bin/fish").as_deref(),
+            Some("fish")
+        );
+        assert_eq!(
+            clean_process_name(r"C:\Program Files\PowerShell\7\pwsh.exe").as_deref(),
+            Some("pwsh")
+        );
+        assert_eq!(clean_process_name("  \n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn process_name_reads_own_process() {
+        let name = process_name(std::process::id()).unwrap();
+        assert!(name.starts_with("zv"), "{name}");
+    }
+}
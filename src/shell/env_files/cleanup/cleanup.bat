@@ -1,5 +1,5 @@
 REM zv cleanup script for Command Prompt
-set "ZV_DIR="
+{zv_dir_cleanup}
 set "PATH=%PATH:{zv_bin_path}{zv_path_separator}=%"
 set "PATH=%PATH:{zv_path_separator}{zv_bin_path}=%"
 
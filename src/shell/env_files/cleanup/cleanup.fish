@@ -1,7 +1,7 @@
 #!/usr/bin/env fish
 # zv cleanup script for Fish shell
 
-set -e ZV_DIR
+{zv_dir_cleanup}
 if set -l index (contains -i "{zv_bin_path}" $PATH)
     set -e PATH[$index]
 end
@@ -1,7 +1,7 @@
 # zv cleanup script for PowerShell
 # Remove zv from environment variables
 
-Remove-Item Env:ZV_DIR -ErrorAction SilentlyContinue
+{zv_dir_cleanup}
 $env:PATH = ($env:PATH -split '{zv_path_separator}' | Where-Object {{ $_ -ne "{zv_bin_path}" }}) -join '{zv_path_separator}'
 
 Write-Host "zv environment cleaned up for current session"
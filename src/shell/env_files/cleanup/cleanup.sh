@@ -1,7 +1,7 @@
 #!/bin/sh
 # zv cleanup script
 
-unset ZV_DIR
+{zv_dir_cleanup}
 case ":$PATH:" in
     *:"{zv_bin_path}":*)
         PATH=$(echo "$PATH" | sed -e "s|{zv_bin_path}:||g" -e "s|:{zv_bin_path}||g")
@@ -244,6 +244,46 @@ mod tests {
         assert!(content.contains("C:\\zv\\bin"));
     }
 
+    #[test]
+    fn test_windows_env_files_with_unicode_and_spaces() {
+        let zv_dir = "C:\\Users\\Müller\\My Tools\\zv";
+        let bin_path = "C:\\Users\\Müller\\My Tools\\zv\\bin";
+
+        let cmd = generate_cmd_content(zv_dir, bin_path);
+        assert!(cmd.contains("set \"ZV_DIR=C:\\Users\\Müller\\My Tools\\zv\""));
+        assert!(cmd.contains("set \"PATH=C:\\Users\\Müller\\My Tools\\zv\\bin;%PATH%\""));
+
+        let powershell = Shell {
+            shell_type: ShellType::PowerShell,
+            context: ShellContext {
+                target_os: OsFlavor::Windows,
+                is_wsl: false,
+                is_emulated: false,
+            },
+        };
+        let ps1 = powershell.generate_env_content(zv_dir, bin_path, true);
+        assert!(ps1.contains("$env:ZV_DIR = \"C:\\Users\\Müller\\My Tools\\zv\""));
+        assert!(ps1.contains("$env:PATH = \"C:\\Users\\Müller\\My Tools\\zv\\bin;$env:PATH\""));
+        assert!(!ps1.contains('\''));
+    }
+
+    #[test]
+    fn test_windows_env_files_escape_expansion_characters() {
+        let cmd = generate_cmd_content("C:\\100%\\zv", "C:\\100%\\zv\\bin");
+        assert!(cmd.contains("set \"ZV_DIR=C:\\100%%\\zv\""));
+
+        let powershell = Shell {
+            shell_type: ShellType::PowerShell,
+            context: ShellContext {
+                target_os: OsFlavor::Windows,
+                is_wsl: false,
+                is_emulated: false,
+            },
+        };
+        let ps1 = powershell.generate_env_content("C:\\$zv", "C:\\$zv\\bin", true);
+        assert!(ps1.contains("$env:ZV_DIR = \"C:\\`$zv\""));
+    }
+
     #[cfg(not(target_os = "linux"))]
     #[test]
     fn test_generate_fish_content() {
@@ -8,7 +8,6 @@ mod generators;
 pub mod path_utils;
 pub mod setup;
 
-pub use detection::detect_shell_from_parent;
 pub use generators::*;
 pub use path_utils::*;
 pub use setup::*;
@@ -63,7 +62,16 @@ pub struct Shell {
 impl Shell {
     /// Detect shell from environment with enhanced context
     pub fn detect() -> Shell {
-        let shell_type = detection::detect_shell();
+        Self::with_detected_context(detection::detect_shell())
+    }
+
+    /// Detect the shell running as process `pid` (e.g. this process's parent), rather than the
+    /// login shell in `$SHELL`. `None` if the process can't be inspected or isn't a known shell.
+    pub fn detect_from_pid(pid: u32) -> Option<Shell> {
+        detection::detect_shell_from_pid(pid).map(Self::with_detected_context)
+    }
+
+    fn with_detected_context(shell_type: ShellType) -> Shell {
         let context = ShellContext {
             target_os: if cfg!(target_os = "windows") {
                 OsFlavor::Windows
@@ -101,6 +109,29 @@ impl Shell {
         false
     }
 
+    /// Program to launch for an interactive instance of this shell
+    pub fn program(&self) -> String {
+        match self.shell_type {
+            ShellType::Bash => "bash".to_string(),
+            ShellType::Zsh => "zsh".to_string(),
+            ShellType::Fish => "fish".to_string(),
+            ShellType::PowerShell if self.context.target_os == OsFlavor::Windows => {
+                "powershell.exe".to_string()
+            }
+            ShellType::PowerShell => "pwsh".to_string(),
+            ShellType::Cmd => "cmd.exe".to_string(),
+            ShellType::Tcsh => "tcsh".to_string(),
+            ShellType::Nu => "nu".to_string(),
+            ShellType::Posix | ShellType::Unknown => std::env::var("SHELL").unwrap_or_else(|_| {
+                if self.context.target_os == OsFlavor::Windows {
+                    "cmd.exe".to_string()
+                } else {
+                    "sh".to_string()
+                }
+            }),
+        }
+    }
+
     /// Is non-windows shell?
     #[inline]
     pub fn is_unix_shell(&self) -> bool {
@@ -201,7 +232,8 @@ impl Shell {
         }
     }
 
-    /// Get shell RC files that should be modified for this shell type
+    /// Candidate RC files for this shell type, most preferred first. This is the one place that
+    /// resolves `ZDOTDIR` and `XDG_CONFIG_HOME`; setup, checks and cleanup all go through it.
     pub fn get_rc_files(&self) -> Vec<PathBuf> {
         let home_dir = match self.get_home_dir() {
             Some(dir) => dir,
@@ -214,28 +246,20 @@ impl Shell {
                 rc_file(".bash_profile"),
                 rc_file(".profile"),
             ],
-            ShellType::Zsh => {
-                // For zsh, check ZDOTDIR environment variable for .zshenv location
-                let zshenv = match std::env::var("ZDOTDIR") {
-                    Ok(zdotdir) if !zdotdir.is_empty() => PathBuf::from(zdotdir).join(".zshenv"),
-                    _ => rc_file(".zshenv"),
-                };
-                vec![zshenv, rc_file(".zshrc"), rc_file(".zprofile")]
-            }
+            ShellType::Zsh => zsh_rc_files(&home_dir, std::env::var_os("ZDOTDIR")),
             ShellType::Fish => {
                 // For fish, check XDG_CONFIG_HOME first, then fall back to ~/.config
-                // Use conf.d directory as recommended by fish shell documentation
                 let mut fish_files = Vec::new();
 
-                // Try XDG_CONFIG_HOME/fish/conf.d/zv.fish
+                // Try XDG_CONFIG_HOME/fish/config.fish
                 if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME")
                     && !xdg_config.is_empty()
                 {
-                    fish_files.push(PathBuf::from(xdg_config).join("fish/conf.d/zv.fish"));
+                    fish_files.push(PathBuf::from(xdg_config).join("fish/config.fish"));
                 }
 
-                // Always include ~/.config/fish/conf.d/zv.fish as fallback
-                fish_files.push(rc_file(".config/fish/conf.d/zv.fish"));
+                // Always include ~/.config/fish/config.fish as fallback
+                fish_files.push(rc_file(".config/fish/config.fish"));
 
                 fish_files
             }
@@ -256,17 +280,11 @@ impl Shell {
 
                 nu_files
             }
-            ShellType::Posix | ShellType::Unknown => {
-                vec![rc_file(".bash_profile"), rc_file(".profile")]
-            }
+            ShellType::Posix | ShellType::Unknown => vec![rc_file(".profile")],
             ShellType::PowerShell => {
-                // PowerShell on Unix should use Unix-style RC files
+                // PowerShell on Unix picks up the POSIX login profile
                 if self.is_powershell_in_unix() {
-                    std::env::var_os("PROFILE")
-                        .map(PathBuf::from)
-                        .into_iter()
-                        .chain(vec![rc_file(".bashrc"), rc_file(".profile")])
-                        .collect::<Vec<PathBuf>>()
+                    vec![rc_file(".profile")]
                 } else {
                     std::env::var_os("PROFILE")
                         .map(PathBuf::from)
@@ -278,6 +296,21 @@ impl Shell {
         }
     }
 
+    /// The RC file setup should write to: the first candidate from [`Self::get_rc_files`] that
+    /// exists, otherwise the one a fresh install should create
+    pub fn select_rc_file(&self) -> Option<PathBuf> {
+        self.rc_file_choice().map(|(rc_file, _)| rc_file)
+    }
+
+    /// Like [`Self::select_rc_file`], along with why that file was picked
+    pub fn rc_file_choice(&self) -> Option<(PathBuf, &'static str)> {
+        choose_rc_file(
+            &self.shell_type,
+            &self.get_rc_files(),
+            cfg!(target_os = "macos"),
+        )
+    }
+
     /// Generate the source command for this shell type
     pub fn get_source_command(&self, env_file: &Path) -> String {
         match self.shell_type {
@@ -377,7 +410,7 @@ impl Shell {
                 }
                 ShellType::Cmd => "set \"ZV_DIR=\"".to_string(),
                 ShellType::Fish => "set -e ZV_DIR".to_string(),
-                ShellType::Nu => "$env.ZV_DIR = null".to_string(),
+                ShellType::Nu => "hide-env ZV_DIR".to_string(),
                 ShellType::Tcsh => "unsetenv ZV_DIR".to_string(),
                 _ => "unset ZV_DIR".to_string(), // POSIX shells
             }
@@ -408,10 +441,50 @@ impl Shell {
             }
         };
 
+        let template = match self.shell_type {
+            ShellType::Zsh => zsh_setup_instructions(
+                template,
+                std::env::var_os("ZDOTDIR"),
+                cfg!(target_os = "macos"),
+            ),
+            _ => template.to_string(),
+        };
         template.replace("{env_file_path}", env_file_path)
     }
 }
 
+/// Point the zsh instructions at the file [`choose_rc_file`] picks for setup, inside `ZDOTDIR`
+/// when it is set and non-empty
+#[cfg(not(target_os = "linux"))]
+fn zsh_setup_instructions(
+    template: &str,
+    zdotdir: Option<std::ffi::OsString>,
+    macos: bool,
+) -> String {
+    let zdotdir = zdotdir.filter(|zdotdir| !zdotdir.is_empty());
+    let has_zdotdir = zdotdir.is_some();
+    // Relative to an empty home, so files outside ZDOTDIR can be shown under `~`
+    let rc_files = zsh_rc_files(Path::new(""), zdotdir);
+    let (rc_file, reason) = choose_rc_file(&ShellType::Zsh, &rc_files, macos)
+        .expect("zsh always has a startup file to choose");
+    let rc_file = if has_zdotdir {
+        rc_file.display().to_string()
+    } else {
+        format!("~/{}", rc_file.display())
+    };
+    let template = template
+        .replace("{zsh_rc_file}", &rc_file)
+        .replace("{zsh_rc_reason}", reason);
+    if has_zdotdir {
+        template.replace(
+            "{zdotdir_note}",
+            "   ZDOTDIR itself must be set in /etc/zshenv or /etc/zsh/zshenv to take effect\n",
+        )
+    } else {
+        template.replace("{zdotdir_note}\n", "")
+    }
+}
+
 impl std::fmt::Display for Shell {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self.shell_type {
@@ -446,6 +519,91 @@ impl std::fmt::Display for ShellType {
     }
 }
 
+/// zsh startup files, `.zshenv` first since every zsh reads it, interactive or not.
+///
+/// zsh reads all of them from `ZDOTDIR` when that is set and non-empty, `.zshenv` included.
+fn zsh_rc_files(home_dir: &Path, zdotdir: Option<std::ffi::OsString>) -> Vec<PathBuf> {
+    let dir = zdotdir
+        .filter(|zdotdir| !zdotdir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir.to_path_buf());
+    [".zshenv", ".zshrc", ".zprofile"]
+        .map(|name| dir.join(name))
+        .to_vec()
+}
+
+/// Pick the RC file to write to from `rc_files`, the candidates [`Shell::get_rc_files`] returns.
+///
+/// macOS terminals start login shells, which read `.zprofile` and `.bash_profile` but not
+/// `.zshrc` or `.bashrc`; elsewhere terminals start interactive non-login shells, which do the
+/// opposite.
+fn choose_rc_file(
+    shell_type: &ShellType,
+    rc_files: &[PathBuf],
+    macos: bool,
+) -> Option<(PathBuf, &'static str)> {
+    let named = |name: &str| {
+        rc_files
+            .iter()
+            .find(|rc_file| rc_file.file_name().is_some_and(|n| n == name))
+            .cloned()
+    };
+    let first_existing = |names: &[&str]| {
+        names
+            .iter()
+            .filter_map(|name| named(name))
+            .find(|rc_file| rc_file.exists())
+    };
+    match shell_type {
+        ShellType::Zsh if macos => named(".zprofile").map(|rc_file| {
+            (
+                rc_file,
+                "macOS terminals start zsh as a login shell, which reads .zprofile",
+            )
+        }),
+        ShellType::Bash if macos => {
+            if let Some(rc_file) = first_existing(&[".bash_profile", ".profile"]) {
+                return Some((
+                    rc_file,
+                    "macOS terminals start bash as a login shell, which reads the first of .bash_profile and .profile",
+                ));
+            }
+            named(".bash_profile").map(|rc_file| {
+                (
+                    rc_file,
+                    "macOS terminals start bash as a login shell, which reads .bash_profile",
+                )
+            })
+        }
+        ShellType::Zsh => named(".zshenv").map(|rc_file| {
+            (
+                rc_file,
+                "every zsh session reads .zshenv, including non-interactive ones",
+            )
+        }),
+        ShellType::Bash => {
+            if let Some(rc_file) = first_existing(&[".bashrc", ".bash_profile", ".profile"]) {
+                return Some((rc_file, "existing bash startup file, preferring .bashrc"));
+            }
+            named(".bashrc").map(|rc_file| (rc_file, "interactive bash sessions read .bashrc"))
+        }
+        _ => {
+            if let Some(existing) = rc_files.iter().find(|rc_file| rc_file.exists()) {
+                return Some((
+                    existing.clone(),
+                    "first existing startup file for this shell",
+                ));
+            }
+            // tcsh falls back to the shared .profile; other shells create their primary file
+            let rc_file = match shell_type {
+                ShellType::Tcsh => rc_files.last(),
+                _ => rc_files.first(),
+            };
+            rc_file.map(|rc_file| (rc_file.clone(), "default startup file for this shell"))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,6 +638,17 @@ mod tests {
         assert!(!zsh_unix.is_windows_shell());
     }
 
+    #[test]
+    fn test_program() {
+        let pwsh_unix = create_test_shell(ShellType::PowerShell, OsFlavor::Unix, false, true);
+        let pwsh_win = create_test_shell(ShellType::PowerShell, OsFlavor::Windows, false, false);
+        let fish_unix = create_test_shell(ShellType::Fish, OsFlavor::Unix, false, false);
+
+        assert_eq!(pwsh_unix.program(), "pwsh");
+        assert_eq!(pwsh_win.program(), "powershell.exe");
+        assert_eq!(fish_unix.program(), "fish");
+    }
+
     #[test]
     fn test_is_unix_shell() {
         let powershell_win =
@@ -549,4 +718,151 @@ mod tests {
         assert!(!bash_unix.is_emulated());
         assert!(!powershell_win.is_emulated());
     }
+
+    /// Golden copies of every generated env file; a diff here changes what existing users source
+    #[test]
+    fn env_content_snapshots() {
+        for shell_type in [
+            ShellType::Bash,
+            ShellType::Zsh,
+            ShellType::Fish,
+            ShellType::PowerShell,
+            ShellType::Cmd,
+            ShellType::Tcsh,
+            ShellType::Posix,
+            ShellType::Nu,
+            ShellType::Unknown,
+        ] {
+            let (target_os, zv_dir, zv_bin_path) = match shell_type {
+                ShellType::PowerShell | ShellType::Cmd => (
+                    OsFlavor::Windows,
+                    r"C:\Users\zig\.zv",
+                    r"C:\Users\zig\.zv\bin",
+                ),
+                _ => (OsFlavor::Unix, "/home/zig/.zv", "/home/zig/.zv/bin"),
+            };
+            let shell = create_test_shell(shell_type, target_os, false, false);
+
+            insta::assert_snapshot!(
+                format!("env_{shell_type:?}").to_lowercase(),
+                shell.generate_env_content(zv_dir, zv_bin_path, true)
+            );
+        }
+
+        // Default ZV_DIR: no export line
+        let bash = create_test_shell(ShellType::Bash, OsFlavor::Unix, false, false);
+        insta::assert_snapshot!(
+            "env_bash_default_zv_dir",
+            bash.generate_env_content("/home/zig/.zv", "/home/zig/.zv/bin", false)
+        );
+    }
+
+    #[test]
+    fn cleanup_unsets_zv_dir_only_when_it_was_exported() {
+        for shell_type in [
+            ShellType::Bash,
+            ShellType::Fish,
+            ShellType::PowerShell,
+            ShellType::Cmd,
+            ShellType::Tcsh,
+            ShellType::Nu,
+        ] {
+            let shell = create_test_shell(shell_type, OsFlavor::Unix, false, false);
+            let exported = shell.generate_cleanup_content("/opt/zv", "/opt/zv/bin", true);
+            let default = shell.generate_cleanup_content("/opt/zv", "/opt/zv/bin", false);
+            // The only difference is the line unsetting ZV_DIR
+            let only_exported: Vec<&str> = exported
+                .lines()
+                .filter(|line| !default.lines().any(|other| other == *line))
+                .collect();
+            assert!(
+                matches!(only_exported.as_slice(), [line] if line.contains("ZV_DIR")),
+                "{shell_type:?}: {only_exported:?}"
+            );
+            assert!(!default.contains("{zv_dir_cleanup}"));
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[test]
+    fn zsh_setup_instructions_follow_zdotdir() {
+        let template = include_str!("env_files/setup_instructions/zsh.txt");
+        let home = zsh_setup_instructions(template, Some("".into()), false);
+        assert!(home.contains("add to ~/.zshenv ("));
+        assert!(!home.contains("ZDOTDIR") && !home.contains("{zdotdir_note}"));
+
+        let zdotdir =
+            zsh_setup_instructions(template, Some("/Users/ziggy/.config/zsh".into()), false);
+        let zshenv = Path::new("/Users/ziggy/.config/zsh").join(".zshenv");
+        assert!(zdotdir.contains(&format!("add to {} (", zshenv.display())));
+        assert!(zdotdir.contains("/etc/zshenv"));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[test]
+    fn zsh_setup_instructions_name_the_file_setup_writes_on_macos() {
+        let template = include_str!("env_files/setup_instructions/zsh.txt");
+        let home = zsh_setup_instructions(template, None, true);
+        assert!(home.contains("add to ~/.zprofile (macOS terminals"));
+        assert!(!home.contains(".zshenv"));
+
+        let zdotdir = zsh_setup_instructions(template, Some("/Users/ziggy/.zsh".into()), true);
+        let zprofile = Path::new("/Users/ziggy/.zsh").join(".zprofile");
+        assert!(zdotdir.contains(&format!("add to {} (", zprofile.display())));
+    }
+
+    #[test]
+    fn zsh_rc_files_follow_a_non_empty_zdotdir() {
+        let home = Path::new("/home/ziggy");
+        let in_dir = |dir: &str| [".zshenv", ".zshrc", ".zprofile"].map(|f| Path::new(dir).join(f));
+        assert_eq!(zsh_rc_files(home, None), in_dir("/home/ziggy"));
+        assert_eq!(zsh_rc_files(home, Some("".into())), in_dir("/home/ziggy"));
+        assert_eq!(
+            zsh_rc_files(home, Some("/home/ziggy/.config/zsh".into())),
+            in_dir("/home/ziggy/.config/zsh")
+        );
+    }
+
+    #[test]
+    fn rc_file_choice_prefers_login_files_on_macos() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path();
+        let zsh_files = [".zshenv", ".zshrc", ".zprofile"].map(|f| home.join(f));
+        let bash_files = [".bashrc", ".bash_profile", ".profile"].map(|f| home.join(f));
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+        std::fs::write(home.join(".bashrc"), "").unwrap();
+
+        let pick = |shell_type, files: &[PathBuf], macos| {
+            choose_rc_file(&shell_type, files, macos).unwrap().0
+        };
+        assert_eq!(
+            pick(ShellType::Zsh, &zsh_files, true),
+            home.join(".zprofile")
+        );
+        // .zshenv wins over an existing .zshrc, scripts and editors never read the latter
+        assert_eq!(
+            pick(ShellType::Zsh, &zsh_files, false),
+            home.join(".zshenv")
+        );
+        // .bashrc is never read by a macOS login shell, even if it exists
+        assert_eq!(
+            pick(ShellType::Bash, &bash_files, true),
+            home.join(".bash_profile")
+        );
+        assert_eq!(
+            pick(ShellType::Bash, &bash_files, false),
+            home.join(".bashrc")
+        );
+
+        std::fs::write(home.join(".profile"), "").unwrap();
+        assert_eq!(
+            pick(ShellType::Bash, &bash_files, true),
+            home.join(".profile")
+        );
+        std::fs::remove_file(home.join(".bashrc")).unwrap();
+        assert_eq!(
+            pick(ShellType::Bash, &bash_files, false),
+            home.join(".profile")
+        );
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
     app::App,
     tools::{canonicalize, warn},
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Helper method to determine path string formatting based on shell and environment
 /// Returns a tuple of (zv_dir_str, zv_bin_path_str)
@@ -30,6 +30,31 @@ pub fn format_absolute_paths(shell: &Shell, zv_dir: &Path, bin_path: &Path) -> (
     )
 }
 
+/// `path` with a leading `home` replaced by the shell's own reference to the home directory, so
+/// an env file synced to another machine or account still finds the default zv directory.
+/// Shells whose env template can't expand a variable there, and paths outside `home`, get the
+/// plain normalized path.
+pub fn home_relative_path_for_shell(shell: &Shell, path: &Path, home: Option<&Path>) -> String {
+    let home_var = match shell.shell_type {
+        ShellType::Bash
+        | ShellType::Zsh
+        | ShellType::Posix
+        | ShellType::Unknown
+        | ShellType::Tcsh => Some("${HOME}"),
+        ShellType::Fish => Some("$HOME"),
+        ShellType::PowerShell if shell.is_powershell_in_unix() => Some("${HOME}"),
+        _ => None,
+    };
+    let relative = home_var.zip(home.and_then(|home| path.strip_prefix(home).ok()));
+    match relative {
+        Some((home_var, relative)) if relative.as_os_str().is_empty() => home_var.to_string(),
+        Some((home_var, relative)) => {
+            format!("{home_var}/{}", normalize_path_for_shell(shell, relative))
+        }
+        None => normalize_path_for_shell(shell, path),
+    }
+}
+
 /// Get default path strings using shell-aware home directory detection
 pub fn get_default_path_strings(shell: &Shell) -> (String, String) {
     // Use the shell's home directory detection to validate that we can find a home
@@ -99,6 +124,22 @@ pub fn get_default_path_strings(shell: &Shell) -> (String, String) {
     }
 }
 
+/// Existing directories listed in `path_var`, in PATH order
+fn path_dirs(path_var: &str, separator: char) -> impl Iterator<Item = &Path> {
+    path_var
+        .split(separator)
+        .filter(|p| !p.is_empty()) // Skip empty entries
+        .map(Path::new)
+        .filter(|p| p.is_dir()) // Only consider existing directories
+}
+
+/// First directory in `path_var` that contains `exe_name`, i.e. the one a shell would run it from
+pub fn first_dir_providing(path_var: &str, separator: char, exe_name: &str) -> Option<PathBuf> {
+    path_dirs(path_var, separator)
+        .find(|dir| dir.join(exe_name).is_file())
+        .map(Path::to_path_buf)
+}
+
 /// Check if path/to/dir is in system PATH
 pub fn check_dir_in_path(path: &Path) -> bool {
     if !path.is_dir() {
@@ -122,11 +163,7 @@ pub fn check_dir_in_path(path: &Path) -> bool {
     // For shell-specific behavior, use check_dir_in_path_for_shell
     let separator = if cfg!(windows) { ';' } else { ':' };
 
-    path_var
-        .split(separator)
-        .filter(|p| !p.is_empty()) // Skip empty entries
-        .map(Path::new)
-        .filter(|p| p.is_dir()) // Only consider existing directories
+    path_dirs(&path_var, separator)
         .filter_map(|p| canonicalize(p).ok()) // Only consider paths we can canonicalize
         .any(|candidate_path| candidate_path == target_path)
 }
@@ -152,11 +189,7 @@ pub fn check_dir_in_path_for_shell(shell: &Shell, path: &Path) -> bool {
     // Use shell-specific separator
     let separator = shell.get_path_separator();
 
-    path_var
-        .split(separator)
-        .filter(|p| !p.is_empty()) // Skip empty entries
-        .map(Path::new)
-        .filter(|p| p.is_dir()) // Only consider existing directories
+    path_dirs(&path_var, separator)
         .filter_map(|p| canonicalize(p).ok()) // Only consider paths we can canonicalize
         .any(|candidate_path| candidate_path == target_path)
 }
@@ -195,21 +228,15 @@ pub fn escape_path_for_shell(shell: &Shell, path: &str) -> String {
                     path.to_string()
                 }
             } else {
-                // Windows PowerShell uses single quotes for literal strings or escapes special chars
-                if path.contains(' ') || path.contains('\'') || path.contains('`') {
-                    format!("'{}'", path.replace('\'', "''"))
-                } else {
-                    path.to_string()
-                }
+                // The env.ps1 templates already wrap paths in double quotes, so only the
+                // characters PowerShell expands inside them need a backtick escape
+                path.replace('`', "``").replace('$', "`$")
             }
         }
         ShellType::Cmd => {
-            // CMD uses double quotes and doesn't need much escaping
-            if path.contains(' ') {
-                format!("\"{}\"", path)
-            } else {
-                path.to_string()
-            }
+            // The env.bat templates already wrap paths in double quotes; spaces and non-ASCII
+            // characters are safe there, but `%` would start a variable expansion
+            path.replace('%', "%%")
         }
         ShellType::Fish => {
             // Fish shell quoting
@@ -303,7 +330,7 @@ mod tests {
 
         assert_eq!(
             escape_path_for_shell(&cmd, path_with_spaces),
-            "\"/path with spaces/bin\""
+            "/path with spaces/bin"
         );
 
         let simple_path = "/simple/path";
@@ -385,6 +412,48 @@ mod tests {
         assert!(zv_bin.contains("${HOME}"));
     }
 
+    #[test]
+    fn home_relative_path_uses_the_shells_home_variable() {
+        let home = Path::new("/home/zig");
+        let bin = Path::new("/home/zig/.local/share/zv/bin");
+        let relative = |shell_type, is_emulated| {
+            let shell = create_test_shell(shell_type, OsFlavor::Unix, false, is_emulated);
+            home_relative_path_for_shell(&shell, bin, Some(home))
+        };
+
+        assert_eq!(
+            relative(ShellType::Bash, false),
+            "${HOME}/.local/share/zv/bin"
+        );
+        assert_eq!(
+            relative(ShellType::Tcsh, false),
+            "${HOME}/.local/share/zv/bin"
+        );
+        assert_eq!(
+            relative(ShellType::Fish, false),
+            "$HOME/.local/share/zv/bin"
+        );
+        assert_eq!(
+            relative(ShellType::PowerShell, true),
+            "${HOME}/.local/share/zv/bin"
+        );
+        // Nushell's template takes the path as a plain string
+        assert_eq!(
+            relative(ShellType::Nu, false),
+            "/home/zig/.local/share/zv/bin"
+        );
+
+        let bash = create_test_shell(ShellType::Bash, OsFlavor::Unix, false, false);
+        assert_eq!(
+            home_relative_path_for_shell(&bash, Path::new("/opt/zv/bin"), Some(home)),
+            "/opt/zv/bin"
+        );
+        assert_eq!(
+            home_relative_path_for_shell(&bash, bin, None),
+            bin.display().to_string()
+        );
+    }
+
     #[test]
     fn test_format_absolute_paths_utilizes_normalize() {
         let bash = create_test_shell(ShellType::Bash, OsFlavor::Unix, false, false);
@@ -444,4 +513,32 @@ mod tests {
         assert!(zv_dir.contains(".zv"));
         assert!(!zv_dir.contains("$env:HOME"));
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_first_dir_providing_follows_path_order() {
+        let temp = tempfile::tempdir().unwrap();
+        let system_bin = temp.path().join("usr-bin");
+        let zv_bin = temp.path().join("zv-bin");
+        std::fs::create_dir_all(&system_bin).unwrap();
+        std::fs::create_dir_all(&zv_bin).unwrap();
+        std::fs::write(system_bin.join("zig"), "").unwrap();
+        std::fs::write(zv_bin.join("zig"), "").unwrap();
+
+        let missing = temp.path().join("missing");
+        let path_var = format!(
+            "{}::{}:{}",
+            missing.display(),
+            zv_bin.display(),
+            system_bin.display()
+        );
+        assert_eq!(
+            first_dir_providing(&path_var, ':', "zig"),
+            Some(zv_bin.clone())
+        );
+
+        let path_var = format!("{}:{}", system_bin.display(), zv_bin.display());
+        assert_eq!(first_dir_providing(&path_var, ':', "zig"), Some(system_bin));
+        assert_eq!(first_dir_providing(&path_var, ':', "zls"), None);
+    }
 }
@@ -22,6 +22,8 @@ pub enum PathAction {
     GenerateEnvFile {
         env_file_path: PathBuf,
         rc_file: PathBuf,
+        /// Why `rc_file` was picked, shown in previews
+        rc_reason: &'static str,
         bin_path: PathBuf,
     },
 }
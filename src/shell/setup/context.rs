@@ -15,6 +15,10 @@ pub struct SetupContext {
     pub dry_run: bool,
     /// Whether to disable interactive prompts and use defaults
     pub no_interactive: bool,
+    /// Whether to skip confirmation prompts and assume their default answer (`--force`)
+    pub force: bool,
+    /// RC file to write to instead of the shell's usual choice (`--rc-file`)
+    pub rc_file_override: Option<std::path::PathBuf>,
     /// Files modified during setup (for post-setup instructions)
     /// Uses Arc<Mutex<>> to allow modification through immutable references
     /// since setup functions take &SetupContext but need to track modifications
@@ -30,6 +34,8 @@ impl SetupContext {
             using_env_var,
             dry_run,
             no_interactive: false,
+            force: false,
+            rc_file_override: None,
             modified_files: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
@@ -48,10 +54,24 @@ impl SetupContext {
             using_env_var,
             dry_run,
             no_interactive,
+            force: false,
+            rc_file_override: None,
             modified_files: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
+    /// Skip confirmation prompts, assuming their default answer
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Write to `rc_file` instead of the RC file the shell would pick
+    pub fn with_rc_file(mut self, rc_file: Option<std::path::PathBuf>) -> Self {
+        self.rc_file_override = rc_file;
+        self
+    }
+
     /// Add a modified file to the context
     pub fn add_modified_file(&self, modified_file: ModifiedFile) {
         if let Ok(mut files) = self.modified_files.lock() {
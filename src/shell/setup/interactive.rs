@@ -65,6 +65,32 @@ impl Theme for ZvTheme {
         }
     }
 
+    /// Format an item in a multi-select list, with a check mark when selected
+    fn format_multi_select_prompt_item(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        text: &str,
+        checked: bool,
+        active: bool,
+    ) -> std::fmt::Result {
+        let mark = if checked {
+            Paint::green("◉").to_string()
+        } else {
+            Paint::new("○").dim().to_string()
+        };
+        if active {
+            write!(
+                f,
+                "{} {} {}",
+                Paint::new("❯").fg(yansi::Color::Rgb(247, 147, 26)).bold(),
+                mark,
+                Paint::new(text).fg(yansi::Color::Rgb(247, 147, 26)).bold()
+            )
+        } else {
+            write!(f, "  {} {}", mark, Paint::new(text).fg(yansi::Color::White))
+        }
+    }
+
     /// Format the confirmation prompt (y/n style prompts)
     fn format_confirm_prompt(
         &self,
@@ -566,13 +592,15 @@ impl InteractiveSetup {
                 Paint::new("Method:").bold(),
                 Paint::dim("Shell profile modification")
             );
-            if let super::PathAction::GenerateEnvFile { rc_file, .. } =
-                &self.requirements.path_action
+            if let super::PathAction::GenerateEnvFile {
+                rc_file, rc_reason, ..
+            } = &self.requirements.path_action
             {
                 println!(
-                    "  {} {}",
+                    "  {} {} {}",
                     Paint::new("Profile:").bold(),
-                    Paint::dim(&rc_file.display())
+                    Paint::dim(&rc_file.display()),
+                    Paint::dim(&format!("({rc_reason})"))
                 );
             }
         }
@@ -716,10 +744,12 @@ pub fn apply_user_choices(
             super::PathAction::GenerateEnvFile {
                 env_file_path,
                 rc_file,
+                rc_reason,
                 ..
             } => super::PathAction::GenerateEnvFile {
                 env_file_path,
                 rc_file,
+                rc_reason,
                 bin_path: bin_path.clone(),
             },
         };
@@ -736,7 +766,7 @@ pub fn apply_user_choices(
 /// Handle interactive setup errors with appropriate fallback behavior
 pub fn handle_interactive_error(error: &crate::ZvError) -> Option<String> {
     match error {
-        crate::ZvError::ShellError(shell_err) => match shell_err {
+        crate::ZvError::ShellError(shell_err) => match shell_err.root() {
             crate::types::error::ShellErr::InteractiveModeNotAvailable { reason } => Some(format!(
                 "Interactive mode not available ({}). Use --no-interactive flag to skip interactive prompts.",
                 reason
@@ -758,7 +788,8 @@ pub fn handle_interactive_error(error: &crate::ZvError) -> Option<String> {
 pub fn is_recoverable_interactive_error(error: &crate::ZvError) -> bool {
     matches!(
         error,
-        crate::ZvError::ShellError(
+        crate::ZvError::ShellError(shell_err) if matches!(
+            shell_err.root(),
             crate::types::error::ShellErr::InteractiveModeNotAvailable { .. }
                 | crate::types::error::ShellErr::InteractivePromptFailed { .. }
         )
@@ -0,0 +1,139 @@
+//! Record of the shell RC files `zv setup` wrote to (`setup_journal.toml`)
+//!
+//! Which RC file setup picks depends on `ZDOTDIR`, `XDG_CONFIG_HOME` and which files exist at
+//! the time. Later checks and cleanup read this journal so they look at exactly those files,
+//! even if the environment has changed since.
+
+use crate::CfgErr;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Comment zv writes above every line it adds to an RC file
+pub const SETUP_MARKER: &str = "# Added by zv setup";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetupJournal {
+    #[serde(default)]
+    rc_files: Vec<PathBuf>,
+}
+
+impl SetupJournal {
+    /// Load the journal from `path`. A missing or unreadable file yields an empty journal.
+    pub async fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match tokio::fs::read_to_string(path).await {
+            Ok(contents) => toml::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// RC files setup has modified, oldest first
+    pub fn rc_files(&self) -> &[PathBuf] {
+        &self.rc_files
+    }
+
+    /// Remember RC files setup just modified. Returns `true` if any of them is new.
+    pub fn record(&mut self, rc_files: impl IntoIterator<Item = PathBuf>) -> bool {
+        let before = self.rc_files.len();
+        for rc_file in rc_files {
+            if !self.rc_files.contains(&rc_file) {
+                self.rc_files.push(rc_file);
+            }
+        }
+        self.rc_files.len() != before
+    }
+
+    /// Persist the journal to `path`
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), CfgErr> {
+        let path = path.as_ref();
+        let toml_str = toml::to_string_pretty(self).map_err(|e| CfgErr::ParseFail(e.into()))?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|io_err| {
+                CfgErr::WriteFail(io_err.into(), parent.to_string_lossy().to_string())
+            })?;
+        }
+        tokio::fs::write(path, toml_str)
+            .await
+            .map_err(|io_err| CfgErr::WriteFail(io_err.into(), path.to_string_lossy().to_string()))
+    }
+}
+
+/// Remove every line zv setup added to `rc_file`: each [`SETUP_MARKER`] comment and the line
+/// after it. Returns `true` if the file changed; a missing file is left alone.
+pub async fn remove_setup_lines(rc_file: &Path) -> std::io::Result<bool> {
+    let content = match tokio::fs::read_to_string(rc_file).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    let mut kept = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == SETUP_MARKER {
+            lines.next();
+        } else {
+            kept.push(line);
+        }
+    }
+    if kept.len() == content.lines().count() {
+        return Ok(false);
+    }
+
+    let mut updated = kept.join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    tokio::fs::write(rc_file, updated).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn journal_round_trips_without_duplicates() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("setup_journal.toml");
+        let zdotdir_zshenv = temp.path().join("zdotdir/.zshenv");
+
+        let mut journal = SetupJournal::load(&path).await;
+        assert!(journal.rc_files().is_empty());
+        assert!(journal.record([zdotdir_zshenv.clone()]));
+        assert!(!journal.record([zdotdir_zshenv.clone()]));
+        journal.save(&path).await.unwrap();
+
+        let journal = SetupJournal::load(&path).await;
+        assert_eq!(journal.rc_files(), [zdotdir_zshenv]);
+    }
+
+    #[tokio::test]
+    async fn removes_only_lines_added_by_setup() {
+        let temp = tempfile::tempdir().unwrap();
+        let rc_file = temp.path().join(".zshenv");
+        tokio::fs::write(
+            &rc_file,
+            format!(
+                "export EDITOR=vi\n{SETUP_MARKER}\nexport ZV_DIR=/opt/zv\nalias ll='ls -l'\n{SETUP_MARKER}\nsource \"/opt/zv/env\"\n"
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert!(remove_setup_lines(&rc_file).await.unwrap());
+        assert_eq!(
+            tokio::fs::read_to_string(&rc_file).await.unwrap(),
+            "export EDITOR=vi\nalias ll='ls -l'\n"
+        );
+        assert!(!remove_setup_lines(&rc_file).await.unwrap());
+        assert!(
+            !remove_setup_lines(&temp.path().join("missing"))
+                .await
+                .unwrap()
+        );
+    }
+}
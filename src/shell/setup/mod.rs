@@ -5,6 +5,7 @@ pub mod actions;
 pub mod context;
 pub mod instructions;
 pub mod interactive;
+pub mod journal;
 pub mod requirements;
 #[cfg(not(target_os = "linux"))]
 pub mod unix;
@@ -14,6 +15,7 @@ pub use actions::*;
 pub use context::*;
 pub use instructions::*;
 pub use interactive::*;
+pub use journal::*;
 pub use requirements::*;
 
 #[cfg(not(target_os = "linux"))]
@@ -67,7 +69,8 @@ pub async fn determine_zv_dir_action(context: &SetupContext) -> crate::Result<Zv
             false
         } // This branch should never be reached due to cfg!(windows) check above
     } else {
-        unix::check_zv_dir_permanent_unix(&context.shell, zv_dir).await?
+        let journal = SetupJournal::load(context.app.paths.setup_journal_file()).await;
+        unix::check_zv_dir_permanent_unix(&context.shell, zv_dir, journal.rc_files()).await?
     };
 
     if is_permanent {
@@ -79,6 +82,11 @@ pub async fn determine_zv_dir_action(context: &SetupContext) -> crate::Result<Zv
             Ok(ZvDirAction::MakePermanent {
                 current_path: zv_dir.clone(),
             })
+        } else if context.force {
+            // --force takes the default answer, which is to make it permanent
+            Ok(ZvDirAction::MakePermanent {
+                current_path: zv_dir.clone(),
+            })
         } else if will_use_interactive_mode(context) {
             // Interactive mode will handle the user choice, so return MakePermanent
             // as a placeholder - the interactive flow will determine the actual choice
@@ -102,7 +110,7 @@ pub async fn determine_zv_dir_action(context: &SetupContext) -> crate::Result<Zv
 /// Check if interactive mode will be used based on context
 fn will_use_interactive_mode(context: &SetupContext) -> bool {
     // Don't use interactive mode if explicitly disabled
-    if context.no_interactive {
+    if context.no_interactive || context.force {
         return false;
     }
 
@@ -145,11 +153,12 @@ pub fn determine_path_action(context: &SetupContext, bin_path_in_path: bool) ->
             .cloned()
             .unwrap_or_else(|| context.app.bin_path().clone());
         let env_file_path = context.app.env_path().clone();
-        let rc_file = unix::select_rc_file(&context.shell);
+        let (rc_file, rc_reason) = unix::select_rc_file(context);
 
         PathAction::GenerateEnvFile {
             env_file_path,
             rc_file,
+            rc_reason,
             bin_path,
         }
     }
@@ -311,13 +320,15 @@ pub async fn execute_path_setup(context: &SetupContext, action: &PathAction) ->
         PathAction::GenerateEnvFile {
             env_file_path,
             rc_file,
+            rc_reason,
             bin_path,
         } => {
             if context.dry_run {
                 println!(
-                    "Would generate env file at {} and modify {}",
+                    "Would generate env file at {} and modify {} {}",
                     Paint::blue(&env_file_path.display()),
-                    Paint::blue(&rc_file.display())
+                    Paint::blue(&rc_file.display()),
+                    Paint::dim(&format!("({rc_reason})"))
                 );
                 return Ok(());
             }
@@ -352,6 +363,10 @@ pub async fn execute_setup(
         .await
         .with_context(|| "PATH setup failed")?;
 
+    if !context.dry_run {
+        record_modified_rc_files(context).await;
+    }
+
     // Execute post-setup actions if needed
     if requirements.needs_post_setup {
         post_setup_actions(context)
@@ -362,6 +377,27 @@ pub async fn execute_setup(
     Ok(())
 }
 
+#[cfg(not(target_os = "linux"))]
+/// Add the RC files this setup touched to the setup journal for later checks and cleanup
+async fn record_modified_rc_files(context: &SetupContext) {
+    let journal_file = context.app.paths.setup_journal_file();
+    let mut journal = SetupJournal::load(&journal_file).await;
+    let rc_files = context
+        .get_modified_files()
+        .into_iter()
+        .filter(|file| matches!(file.file_type, FileType::RcFile))
+        .map(|file| file.path);
+    if journal.record(rc_files)
+        && let Err(e) = journal.save(&journal_file).await
+    {
+        tracing::warn!(
+            "Failed to update setup journal {}: {}",
+            journal_file.display(),
+            e
+        );
+    }
+}
+
 #[cfg(not(target_os = "linux"))]
 /// Post-setup actions phase - handle binary management and shim regeneration
 pub async fn post_setup_actions(context: &SetupContext) -> crate::Result<()> {
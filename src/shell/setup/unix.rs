@@ -4,95 +4,24 @@ use yansi::Paint;
 
 const TARGET: &str = "zv::shell::setup::unix";
 
-/// Select the appropriate RC file for the shell with shell-specific preferences
-pub fn select_rc_file(shell: &Shell) -> PathBuf {
-    let home_dir = match dirs::home_dir() {
-        Some(dir) => dir,
-        None => {
-            // Fallback to .profile if home directory cannot be determined
-            return PathBuf::from(".profile");
-        }
-    };
-    match shell.shell_type {
-        ShellType::Bash => {
-            // Bash preference order: .bashrc (interactive), .bash_profile (login), .profile (fallback)
-            let bashrc = home_dir.join(".bashrc");
-            if bashrc.exists() {
-                return bashrc;
-            }
-
-            let bash_profile = home_dir.join(".bash_profile");
-            if bash_profile.exists() {
-                return bash_profile;
-            }
-
-            home_dir.join(".profile")
-        }
-        ShellType::Zsh => {
-            // Zsh preference order: .zshenv (always sourced), .zshrc (interactive), .zprofile (login)
-            let zshenv = home_dir.join(".zshenv");
-            if zshenv.exists() {
-                return zshenv;
-            }
-
-            let zshrc = home_dir.join(".zshrc");
-            if zshrc.exists() {
-                return zshrc;
-            }
-
-            let zprofile = home_dir.join(".zprofile");
-            if zprofile.exists() {
-                return zprofile;
-            }
-
-            // Default to .zshenv for new installations
-            home_dir.join(".zshenv")
-        }
-        ShellType::Fish => {
-            // Fish uses config.fish in the config directory
-            let config_dir = home_dir.join(".config/fish");
-            config_dir.join("config.fish")
-        }
-        ShellType::Tcsh => {
-            // Tcsh preference order: .tcshrc, .cshrc, .profile
-            let tcshrc = home_dir.join(".tcshrc");
-            if tcshrc.exists() {
-                return tcshrc;
-            }
-
-            let cshrc = home_dir.join(".cshrc");
-            if cshrc.exists() {
-                return cshrc;
-            }
-
-            home_dir.join(".profile")
-        }
-        ShellType::Nu => {
-            // Nushell uses config.nu in the config directory
-            let config_dir = home_dir.join(".config/nushell");
-            config_dir.join("config.nu")
-        }
-        ShellType::PowerShell => {
-            // PowerShell on Unix should use .profile
-            if shell.is_powershell_in_unix() {
-                home_dir.join(".profile")
-            } else {
-                // This shouldn't happen for Unix setup, but provide a fallback
-                home_dir.join(".profile")
-            }
-        }
-        ShellType::Posix | ShellType::Unknown => {
-            // Use .profile for POSIX-compliant shells and unknown shells
-            home_dir.join(".profile")
-        }
-        ShellType::Cmd => {
-            // CMD shouldn't be handled by Unix setup, but provide a fallback
-            home_dir.join(".profile")
-        }
+/// Select the RC file setup writes to, along with why it was picked. `--rc-file` wins over the
+/// shell's platform-specific preference.
+pub fn select_rc_file(context: &crate::shell::setup::SetupContext) -> (PathBuf, &'static str) {
+    if let Some(rc_file) = &context.rc_file_override {
+        return (rc_file.clone(), "set with --rc-file");
     }
+    context.shell.rc_file_choice().unwrap_or_else(|| {
+        // Fallback to .profile if no RC file can be determined
+        let rc_file = dirs::home_dir()
+            .map(|home| home.join(".profile"))
+            .unwrap_or_else(|| PathBuf::from(".profile"));
+        (rc_file, "no startup file known for this shell")
+    })
 }
 
-/// Generate Unix environment file with proper escaping and shell-specific content
+/// Generate Unix environment file with proper escaping and shell-specific content. Without
+/// `export_zv_dir` (zv lives in its default directory) the file only extends PATH, with the bin
+/// dir spelled relative to the home directory where the shell allows it.
 pub async fn generate_unix_env_file(
     shell: &Shell,
     env_file_path: &Path,
@@ -100,11 +29,17 @@ pub async fn generate_unix_env_file(
     bin_path: &Path,
     export_zv_dir: bool,
 ) -> crate::Result<()> {
-    use crate::shell::path_utils::{escape_path_for_shell, normalize_path_for_shell};
+    use crate::shell::path_utils::{
+        escape_path_for_shell, home_relative_path_for_shell, normalize_path_for_shell,
+    };
 
     // Normalize and escape paths for the shell
     let zv_dir_str = normalize_path_for_shell(shell, zv_dir);
-    let bin_path_str = normalize_path_for_shell(shell, bin_path);
+    let bin_path_str = if export_zv_dir {
+        normalize_path_for_shell(shell, bin_path)
+    } else {
+        home_relative_path_for_shell(shell, bin_path, shell.get_home_dir().as_deref())
+    };
     let escaped_zv_dir = escape_path_for_shell(shell, &zv_dir_str);
     let escaped_bin_path = escape_path_for_shell(shell, &bin_path_str);
 
@@ -169,7 +104,8 @@ pub async fn add_source_to_rc_file(
     if !content.is_empty() && !content.ends_with('\n') {
         content.push('\n');
     }
-    content.push_str("# Added by zv setup\n");
+    content.push_str(super::SETUP_MARKER);
+    content.push('\n');
     content.push_str(&source_line);
     content.push('\n');
 
@@ -245,7 +181,8 @@ pub async fn add_zv_dir_export_to_rc_file(
     if !content.is_empty() && !content.ends_with('\n') {
         content.push('\n');
     }
-    content.push_str("# Added by zv setup\n");
+    content.push_str(super::SETUP_MARKER);
+    content.push('\n');
     content.push_str(&export_line);
     content.push('\n');
 
@@ -266,15 +203,35 @@ pub async fn add_zv_dir_export_to_rc_file(
     Ok(())
 }
 
-/// Check if ZV_DIR is permanently set in Unix environment
-pub async fn check_zv_dir_permanent_unix(shell: &Shell, zv_dir: &Path) -> crate::Result<bool> {
-    let rc_file = select_rc_file(shell);
+/// Check if ZV_DIR is permanently set in Unix environment, looking in the RC files recorded in
+/// the setup journal as well as every RC file the shell may read
+pub async fn check_zv_dir_permanent_unix(
+    shell: &Shell,
+    zv_dir: &Path,
+    journaled_rc_files: &[PathBuf],
+) -> crate::Result<bool> {
+    let mut rc_files = journaled_rc_files.to_vec();
+    for rc_file in shell.get_rc_files() {
+        if !rc_files.contains(&rc_file) {
+            rc_files.push(rc_file);
+        }
+    }
 
-    if !rc_file.exists() {
-        return Ok(false);
+    for rc_file in rc_files.iter().filter(|rc_file| rc_file.exists()) {
+        if rc_file_exports_zv_dir(shell, rc_file, zv_dir).await? {
+            return Ok(true);
+        }
     }
+    Ok(false)
+}
 
-    let content = tokio::fs::read_to_string(&rc_file).await.map_err(|e| {
+/// Check if `rc_file` exports ZV_DIR as `zv_dir`
+async fn rc_file_exports_zv_dir(
+    shell: &Shell,
+    rc_file: &Path,
+    zv_dir: &Path,
+) -> crate::Result<bool> {
+    let content = tokio::fs::read_to_string(rc_file).await.map_err(|e| {
         crate::ZvError::shell_rc_file_modification_failed(&rc_file.display().to_string(), e)
     })?;
 
@@ -350,7 +307,7 @@ pub async fn execute_zv_dir_setup_unix(
         }
     }
 
-    let rc_file = select_rc_file(&context.shell);
+    let (rc_file, _) = select_rc_file(context);
 
     add_zv_dir_export_to_rc_file(&context.shell, &rc_file, zv_dir).await?;
 
@@ -377,13 +334,13 @@ pub async fn execute_path_setup_unix(
         FileAction, create_env_file_entry, create_rc_file_entry,
     };
 
-    // Generate the environment file
+    // Generate the environment file, exporting ZV_DIR only for a custom directory
     generate_unix_env_file(
         &context.shell,
         env_file_path,
         context.app.path(),
         bin_path,
-        context.using_env_var,
+        !crate::tools::is_default_zv_dir(context.app.path()),
     )
     .await?;
 
@@ -57,7 +57,7 @@ impl WindowsPathManager {
 
         let new_path_value: Vec<&str> = current_path
             .split(';')
-            .filter(|p| !p.trim().eq_ignore_ascii_case(target_path.trim()))
+            .filter(|p| !same_windows_path(p, target_path))
             .collect();
 
         let new_path_string = new_path_value.join(";");
@@ -85,15 +85,7 @@ impl WindowsPathManager {
 
     /// Check if PATH contains the specified path
     fn path_contains(&self, path_value: &str, target_path: &str) -> bool {
-        // Handle empty strings
-        if path_value.is_empty() || target_path.is_empty() {
-            return false;
-        }
-
-        path_value.split(';').any(|p| {
-            let trimmed = p.trim();
-            !trimmed.is_empty() && trimmed.eq_ignore_ascii_case(target_path.trim())
-        })
+        windows_path_list_contains(path_value, target_path)
     }
 
     /// Broadcast environment variable changes to notify running applications
@@ -134,6 +126,30 @@ impl WindowsPathManager {
     }
 }
 
+/// Compare two Windows PATH entries the way Windows resolves them: case-insensitively
+/// (including non-ASCII letters such as `Ü`/`ü`), ignoring surrounding quotes, which PATH
+/// entries containing spaces sometimes carry, and trailing separators.
+fn same_windows_path(entry: &str, target: &str) -> bool {
+    fn normalize(path: &str) -> String {
+        path.trim()
+            .trim_matches('"')
+            .trim_end_matches(['\\', '/'])
+            .to_lowercase()
+    }
+    let entry = normalize(entry);
+    !entry.is_empty() && entry == normalize(target)
+}
+
+/// Check if a `;`-separated PATH value contains `target_path`
+fn windows_path_list_contains(path_value: &str, target_path: &str) -> bool {
+    if path_value.is_empty() || target_path.is_empty() {
+        return false;
+    }
+    path_value
+        .split(';')
+        .any(|p| same_windows_path(p, target_path))
+}
+
 /// Broadcast environment variable changes on Windows
 #[cfg(windows)]
 pub fn broadcast_environment_change() -> crate::Result<()> {
@@ -243,3 +259,27 @@ impl WindowsPathManager {
 pub fn check_path_in_windows_path(_target_path: &Path) -> crate::Result<bool> {
     unreachable!("Windows PATH check should not be called on non-Windows platforms")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZV_BIN: &str = r"C:\Users\Müller\My Tools\zv\bin";
+
+    #[test]
+    fn path_list_matches_unicode_and_space_entries() {
+        let path = r"C:\Windows\system32;C:\USERS\MÜLLER\My Tools\zv\bin\;C:\Tools";
+        assert!(windows_path_list_contains(path, ZV_BIN));
+
+        let quoted = r#"C:\Windows;"C:\Users\Müller\My Tools\zv\bin""#;
+        assert!(windows_path_list_contains(quoted, ZV_BIN));
+    }
+
+    #[test]
+    fn path_list_does_not_match_prefixes_or_empty_entries() {
+        let path = r"C:\Users\Müller\My Tools\zv;;C:\Users\Müller\My Tools\zv\bin2";
+        assert!(!windows_path_list_contains(path, ZV_BIN));
+        assert!(!windows_path_list_contains(";;", ""));
+        assert!(!same_windows_path("", ""));
+    }
+}
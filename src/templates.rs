@@ -45,6 +45,9 @@ pub struct TemplateContext {
     pub target_dir: PathBuf,
     /// Did we create a new dir or are we reusing one?
     pub created_new_dir: bool,
+    /// Where a new project directory goes once instantiation succeeds. Until then it is
+    /// built in `target_dir`, inside a hidden staging directory next to it.
+    pub destination: Option<PathBuf>,
 }
 
 impl Template {
@@ -52,45 +55,61 @@ impl Template {
     /// Also initializes [TemplateContext] for [Template] storing the info for the same
     pub fn prepare_directory(&mut self) -> Result<Option<String>, ZvError> {
         let mut created_new_dir = false;
+        let mut destination = None;
         let mut pre_exec_msg: Option<String> = None;
+        let cwd = std::env::current_dir()
+            .map_err(|e| ZvError::TemplateError(eyre!("Failed to get current directory: {}", e)))?;
         let target_dir = match &self.name {
             Some(name) => {
-                let dir = std::env::current_dir()
-                    .map_err(|e| {
-                        ZvError::TemplateError(eyre!("Failed to get current directory: {}", e))
-                    })?
-                    .join(name);
-
-                if dir.is_dir() {
+                if let Some(reason) = tools::invalid_project_name(name) {
+                    return Err(ZvError::TemplateError(eyre!(
+                        "Invalid project name '{name}': it {reason}. Try '{}'",
+                        tools::suggest_project_name(name)
+                    )));
+                }
+                let dir = cwd.join(name);
+
+                if dir.exists() && !dir.is_dir() {
+                    return Err(ZvError::TemplateError(eyre!(
+                        "{} already exists and is not a directory",
+                        dir.display()
+                    )));
+                } else if dir.is_dir() {
+                    if !self.force {
+                        return Err(ZvError::TemplateError(eyre!(
+                            "{} already exists. Pass --force to initialize the project inside it",
+                            dir.display()
+                        )));
+                    }
                     // Directory exists, we'll use it as-is
                     pre_exec_msg = Some(format!("Using existing directory: {}", dir.display()));
                     dir
                 } else {
-                    // Try to create the directory
-                    match std::fs::create_dir(&dir) {
-                        Ok(()) => {
-                            created_new_dir = true;
-                            pre_exec_msg =
-                                Some(format!("Creating new directory: {}", dir.display()));
-                            dir
-                        }
-                        Err(err) => {
-                            return Err(ZvError::TemplateError(eyre!(
+                    // Build the project in a staging directory so a failure leaves nothing behind
+                    let staged = tempfile::Builder::new()
+                        .prefix(".zv-init-")
+                        .tempdir_in(&cwd)
+                        .map(|staging| staging.keep().join(name))
+                        .and_then(|staged| std::fs::create_dir(&staged).map(|()| staged))
+                        .map_err(|err| {
+                            ZvError::TemplateError(eyre!(
                                 "Failed to create project directory at {}: {}",
                                 dir.display(),
                                 err
-                            )));
-                        }
-                    }
+                            ))
+                        })?;
+                    created_new_dir = true;
+                    pre_exec_msg = Some(format!("Creating new directory: {}", dir.display()));
+                    destination = Some(dir);
+                    staged
                 }
             }
-            None => std::env::current_dir().map_err(|e| {
-                ZvError::TemplateError(eyre!("Failed to get current directory: {}", e))
-            })?,
+            None => cwd,
         };
         self.context = Some(TemplateContext {
             target_dir,
             created_new_dir,
+            destination,
         });
         Ok(pre_exec_msg)
     }
@@ -158,7 +177,51 @@ impl Template {
     /// Convenience method that handles directory preparation and instantiation
     pub async fn execute(mut self, app: App) -> Result<TemplateResult, ZvError> {
         let pre_exec_msg = self.prepare_directory()?;
-        self.instantiate_with_context(pre_exec_msg, app).await
+        let context = self.context.clone().expect("Context should be initialized");
+        let result = self.instantiate_with_context(pre_exec_msg, app).await;
+        let Some(destination) = context.destination else {
+            return result;
+        };
+
+        let staging = context
+            .target_dir
+            .parent()
+            .expect("staged project has a staging parent");
+        let published = result.and_then(|mut result| {
+            if destination.exists() {
+                return Err(ZvError::TemplateError(eyre!(
+                    "{} was created while the project was being initialized",
+                    destination.display()
+                )));
+            }
+            fs::rename(&context.target_dir, &destination).map_err(|e| {
+                ZvError::TemplateError(eyre!(
+                    "Failed to move the project into {}: {}",
+                    destination.display(),
+                    e
+                ))
+            })?;
+            result.file_statuses = result
+                .file_statuses
+                .into_iter()
+                .map(|status| {
+                    let path = destination.join(
+                        status
+                            .path()
+                            .strip_prefix(&context.target_dir)
+                            .unwrap_or(status.path()),
+                    );
+                    match status {
+                        FileStatus::Created(_) => FileStatus::Created(path),
+                        FileStatus::Preserved(_) => FileStatus::Preserved(path),
+                    }
+                })
+                .collect();
+            result.context.target_dir = destination;
+            Ok(result)
+        });
+        let _ = rda::remove_dir_all(staging);
+        published
     }
 
     fn instantiate_minimal(&self) -> Result<Vec<FileStatus>, ZvError> {
@@ -304,7 +367,7 @@ impl Template {
         } else {
             // Fetch latest version asynchronously
             if let Ok(stable) = app
-                .fetch_latest_version(crate::app::CacheStrategy::OnlyCache)
+                .fetch_latest_version(crate::app::CacheStrategy::OnlyCache, false)
                 .await
             {
                 tracing::debug!(
@@ -384,6 +447,8 @@ pub struct Template {
     name: Option<String>,
     context: Option<TemplateContext>,
     r#type: TemplateType,
+    /// Initialize inside a project directory that already exists
+    force: bool,
 }
 
 impl Template {
@@ -392,8 +457,15 @@ impl Template {
             name,
             context: None,
             r#type,
+            force: false,
         }
     }
+
+    /// Allow the project directory to exist already; its files are preserved
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
 }
 
 impl Default for TemplateType {
@@ -8,6 +8,7 @@ use std::{
     borrow::Cow,
     io,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 use yansi::Paint;
 
@@ -18,10 +19,12 @@ use yansi::Paint;
 pub struct ZvPaths {
     /// Primary data directory: `XDG_DATA_HOME/zv` (`~/.local/share/zv`) or `~/.zv` on Windows
     pub data_dir: PathBuf,
-    /// Internal binary dir (`data_dir/bin`) — actual zv binary and shims
+    /// Internal binary dir (`data_dir/bin`, or `ZV_BIN_DIR`) — actual zv binary and shims
     pub bin_dir: PathBuf,
     /// Installed zig versions (`data_dir/versions`)
     pub versions_dir: PathBuf,
+    /// Installations found by the last scan of `versions_dir` (`data_dir/installations.toml`)
+    pub installations_file: PathBuf,
     /// Config directory: `XDG_CONFIG_HOME/zv` (`~/.config/zv`) or `data_dir` on Windows
     pub config_dir: PathBuf,
     /// Active config file (`config_dir/zv.toml`)
@@ -31,12 +34,14 @@ pub struct ZvPaths {
     pub cache_dir: PathBuf,
     /// Download cache (`cache_dir/downloads`)
     pub downloads_dir: PathBuf,
-    /// Cached zig version index (`cache_dir/index.toml`)
+    /// Cached zig version index, gzip-compressed (`cache_dir/index.toml.gz`)
     pub index_file: PathBuf,
     /// Cached mirrors list (`cache_dir/mirrors.toml`)
     pub mirrors_file: PathBuf,
     /// Cached master version string (`cache_dir/master`)
     pub master_file: PathBuf,
+    /// Every master release zv has observed (`data_dir/masters_seen.toml`)
+    pub masters_seen_file: PathBuf,
     /// Public bin dir for XDG symlinks (`~/.local/bin`). `None` on Windows.
     pub public_bin_dir: Option<PathBuf>,
     /// Whether `ZV_DIR` was set via environment variable
@@ -106,14 +111,23 @@ impl ZvPaths {
             2
         };
 
+        // ZV_BIN_DIR puts the shims in a directory that is already on PATH, so there is
+        // nothing to publish
+        let (bin_dir, public_bin_dir) = match bin_dir_override() {
+            Some(bin_dir) => (bin_dir, None),
+            None => (data_dir.join("bin"), public_bin_dir),
+        };
+
         Ok(Self {
-            bin_dir: data_dir.join("bin"),
+            bin_dir,
             versions_dir: data_dir.join("versions"),
+            installations_file: data_dir.join("installations.toml"),
             config_file: config_dir.join("zv.toml"),
             downloads_dir: cache_dir.join("downloads"),
-            index_file: cache_dir.join("index.toml"),
+            index_file: cache_dir.join("index.toml.gz"),
             mirrors_file: cache_dir.join("mirrors.toml"),
             master_file: cache_dir.join("master"),
+            masters_seen_file: data_dir.join("masters_seen.toml"),
             public_bin_dir,
             config_dir,
             cache_dir,
@@ -124,16 +138,64 @@ impl ZvPaths {
         })
     }
 
+    /// Self-contained layout rooted at `root`, as if `ZV_DIR` pointed there
+    pub fn self_contained(root: &Path) -> Self {
+        Self {
+            bin_dir: root.join("bin"),
+            versions_dir: root.join("versions"),
+            installations_file: root.join("installations.toml"),
+            config_dir: root.to_path_buf(),
+            config_file: root.join("zv.toml"),
+            cache_dir: root.to_path_buf(),
+            downloads_dir: root.join("downloads"),
+            index_file: root.join("index.toml.gz"),
+            mirrors_file: root.join("mirrors.toml"),
+            master_file: root.join("master"),
+            masters_seen_file: root.join("masters_seen.toml"),
+            public_bin_dir: None,
+            data_dir: root.to_path_buf(),
+            using_env_var: true,
+            #[cfg(target_os = "macos")]
+            tier: 3,
+        }
+    }
+
     /// Default env file path (`data_dir/env`) when shell type is unknown.
     pub fn env_file_default(&self) -> PathBuf {
         self.data_dir.join("env")
     }
 
+    /// Zig source trees fetched by `zv use --source` (`data_dir/sources`).
+    pub fn sources_dir(&self) -> PathBuf {
+        self.data_dir.join("sources")
+    }
+
     /// Directory where cached ZLS binaries are stored.
     pub fn zls_dir(&self) -> PathBuf {
         self.data_dir.join("zls")
     }
 
+    /// Global default Zig version set by `zv default` (`config_dir/default.json`).
+    pub fn global_default_file(&self) -> PathBuf {
+        self.config_dir.join("default.json")
+    }
+
+    /// Upstream index.json exactly as last fetched, gzip-compressed (`cache_dir/index.json.gz`).
+    pub fn raw_index_file(&self) -> PathBuf {
+        self.index_file
+            .with_file_name(crate::app::constants::ZV_RAW_INDEX_FILE)
+    }
+
+    /// Lock held by a zv process while it installs a Zig version (`data_dir/install.lock`).
+    pub fn install_lock_file(&self) -> PathBuf {
+        self.data_dir.join("install.lock")
+    }
+
+    /// Journal of the shell RC files `zv setup` has modified.
+    pub fn setup_journal_file(&self) -> PathBuf {
+        self.config_dir.join("setup_journal.toml")
+    }
+
     /// Shared ZLS git checkout reused across source-builds of every version.
     pub fn zls_src_dir(&self) -> PathBuf {
         self.cache_dir.join("zls-src")
@@ -208,8 +270,24 @@ pub(crate) fn is_tty() -> bool {
     yansi::is_enabled()
 }
 
+/// Set by the global `--no-interactive` flag
+static NO_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Answer every prompt from now on with its non-interactive default
+pub(crate) fn set_no_interactive(enabled: bool) {
+    NO_INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
 /// Check if the current environment supports interactive prompts
 pub(crate) fn supports_interactive_prompts() -> bool {
+    // Explicitly disabled with --no-interactive or ZV_NON_INTERACTIVE
+    if NO_INTERACTIVE.load(Ordering::Relaxed)
+        || std::env::var(crate::app::constants::ZV_NON_INTERACTIVE)
+            .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+    {
+        return false;
+    }
+
     // Check basic TTY availability
     if !is_tty() {
         return false;
@@ -310,8 +388,8 @@ pub(crate) fn fetch_zv_dir() -> Result<(PathBuf, bool)> {
                         )
                     })?;
             } else {
-                // create_dir should be enough for default directory
-                std::fs::create_dir(&zv_dir)
+                // The XDG data dir itself may not exist yet on a fresh account
+                std::fs::create_dir_all(&zv_dir)
                     .map_err(ZvError::Io)
                     .wrap_err_with(|| {
                         format!("Failed to create default .zv at {}", zv_dir.display())
@@ -333,6 +411,13 @@ pub(crate) fn fetch_zv_dir() -> Result<(PathBuf, bool)> {
     Ok((zv_dir, using_env))
 }
 
+/// Shim directory set with `ZV_BIN_DIR`, if any
+pub(crate) fn bin_dir_override() -> Option<PathBuf> {
+    std::env::var_os("ZV_BIN_DIR")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
 /// Get the default ZV data directory.
 /// On Linux/macOS: `$XDG_DATA_HOME/zv` (defaults to `~/.local/share/zv`).
 /// On Windows: `~/.zv` (unchanged).
@@ -347,6 +432,18 @@ pub(crate) fn get_default_zv_dir() -> Result<PathBuf> {
     }
 }
 
+/// Whether `zv_dir` is the directory zv would pick without `ZV_DIR` set
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn is_default_zv_dir(zv_dir: &Path) -> bool {
+    get_default_zv_dir().is_ok_and(|default| {
+        default == zv_dir
+            || canonicalize(&default)
+                .ok()
+                .zip(canonicalize(zv_dir).ok())
+                .is_some_and(|(default, zv_dir)| default == zv_dir)
+    })
+}
+
 /// Print a warning message in yellow if stderr is a TTY
 #[inline]
 pub fn warn(message: impl Into<Cow<'static, str>>) {
@@ -387,13 +484,94 @@ pub fn calculate_file_hash(path: &Path) -> Result<u32> {
     Ok(hasher.finalize())
 }
 
-/// Compare file hashes to determine if files are identical
-pub fn files_have_same_hash(path1: &Path, path2: &Path) -> Result<bool> {
+/// Calculate the hex-encoded SHA-256 digest of a file
+pub fn calculate_file_sha256(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)
+        .wrap_err_with(|| format!("Failed to open file for hashing: {}", path.display()))?;
+
+    let mut hasher = <Sha256 as Digest>::new();
+    let mut buffer = [0; 8192]; // 8KB buffer
+
+    loop {
+        let bytes_read = file
+            .read(&mut buffer)
+            .wrap_err_with(|| format!("Failed to read file for hashing: {}", path.display()))?;
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Total size in bytes of all files under `path` (symlinks are not followed)
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Format a byte count with binary units, e.g. `1.2 GB`
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut v = bytes as f64;
+    let mut i = 0;
+    while v >= 1024.0 && i < UNITS.len() - 1 {
+        v /= 1024.0;
+        i += 1;
+    }
+    if i == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{v:.1} {}", UNITS[i])
+    }
+}
+
+/// Parse an age such as `36h`, `14d` or `2w` (hours, days, weeks)
+pub(crate) fn parse_duration(s: &str) -> std::result::Result<std::time::Duration, String> {
+    let (amount, unit) = match s.char_indices().next_back() {
+        Some((i, _)) => s.split_at(i),
+        None => (s, ""),
+    };
+    let hours = match unit {
+        "h" => 1,
+        "d" => 24,
+        "w" => 24 * 7,
+        _ => return Err(format!("'{s}' needs a unit suffix: h, d or w (e.g. 14d)")),
+    };
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("'{s}' is not a whole number followed by h, d or w"))?;
+    amount
+        .checked_mul(hours * 3600)
+        .map(std::time::Duration::from_secs)
+        .ok_or_else(|| format!("'{s}' is too long a duration"))
+}
+
+/// Compare file hashes to determine if files are identical.
+/// Hashing runs on the blocking pool so large binaries don't stall the runtime.
+pub async fn files_have_same_hash(path1: &Path, path2: &Path) -> Result<bool> {
     if !path1.exists() || !path2.exists() {
         return Ok(false);
     }
 
-    Ok(calculate_file_hash(path1)? == calculate_file_hash(path2)?)
+    let (path1, path2) = (path1.to_path_buf(), path2.to_path_buf());
+    tokio::task::spawn_blocking(move || {
+        Ok(calculate_file_hash(&path1)? == calculate_file_hash(&path2)?)
+    })
+    .await
+    .wrap_err("File hashing task did not complete")?
 }
 /// Build.zig.zon files have a .name field that expect an enum literal v0.13 onwards
 /// 0.12 expects a string literal. 0.11 and below don't come with build.zig.zon files.
@@ -405,32 +583,121 @@ pub fn sanitize_build_zig_zon_name(name: Option<&str>, zig_version: &Version) ->
     // Default .name
     let default_name = "app";
 
-    // Extract and clean provided name
-    let raw = name.unwrap_or(default_name).trim();
+    let mut sanitized = sanitize_identifier(name.unwrap_or(default_name));
+    sanitized.truncate(MAX_PROJECT_NAME_LEN);
 
-    // Basic normalization: lowercase and replace invalid chars
-    let mut sanitized = raw
+    // Check Zig version to decide output form
+    Some(if *zig_version >= Version::new(0, 13, 0) {
+        format!(".{sanitized}") // enum literal preferred from v0.13..
+    } else {
+        format!("\"{sanitized}\"") // only v0.12
+    })
+}
+
+/// Longest package name Zig accepts in build.zig.zon
+const MAX_PROJECT_NAME_LEN: usize = 32;
+
+/// Zig keywords, which can't be a bare identifier
+const ZIG_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+];
+
+/// Lowercase `raw` and make it a Zig identifier: invalid characters become `_` and a leading
+/// digit gets a `_` prefix
+fn sanitize_identifier(raw: &str) -> String {
+    let sanitized = raw
+        .trim()
         .chars()
         .map(|c| match c {
             'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => c,
-            '-' | ' ' | '.' => '_',
-            _ => '_', // fallback for all invalid chars
+            _ => '_',
         })
         .collect::<String>()
         .to_lowercase();
 
-    // Must not start with a digit
-    if let Some(first_char) = sanitized.chars().next()
-        && first_char.is_ascii_digit()
-    {
-        sanitized = format!("_{}", sanitized);
+    match sanitized.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("_{sanitized}"),
+        _ => sanitized,
     }
-    // Check Zig version to decide output form
-    Some(if *zig_version >= Version::new(0, 13, 0) {
-        format!(".{sanitized}") // enum literal preferred from v0.13..
+}
+
+/// Why `name` can't name a new project, if it can't. The directory keeps `name` as given,
+/// while build.zig.zon gets its sanitized form as `.name`.
+pub fn invalid_project_name(name: &str) -> Option<String> {
+    let sanitized = sanitize_identifier(name);
+    let reason = if name.trim().is_empty() {
+        "is empty"
+    } else if name.contains(['/', '\\']) || matches!(name, "." | "..") {
+        "must be a plain directory name, not a path"
+    } else if sanitized.trim_matches('_').is_empty() {
+        "has no letters or digits to use as the build.zig.zon name"
+    } else if ZIG_KEYWORDS.contains(&sanitized.as_str()) {
+        "is a Zig keyword"
     } else {
-        format!("\"{sanitized}\"") // only v0.12
-    })
+        return None;
+    };
+    Some(reason.to_string())
+}
+
+/// A valid project name close to `name`, see [invalid_project_name]
+pub fn suggest_project_name(name: &str) -> String {
+    let mut suggestion = sanitize_identifier(name);
+    suggestion.truncate(MAX_PROJECT_NAME_LEN);
+    if suggestion.trim_matches('_').is_empty() {
+        return "app".to_string();
+    }
+    if ZIG_KEYWORDS.contains(&suggestion.as_str()) {
+        suggestion.push_str("_app");
+    }
+    suggestion
 }
 
 /// Deduplicate semver variants before resolution
@@ -460,6 +727,7 @@ pub fn deduplicate_semver_variants(versions: Vec<crate::ZigVersion>) -> Vec<crat
             // Non-semver versions (latest, stable, master) need resolution to deduplicate
             crate::ZigVersion::Latest(None)
             | crate::ZigVersion::Stable(None)
+            | crate::ZigVersion::StableSeries(_)
             | crate::ZigVersion::Master(_) => {
                 non_semver_versions.push(version);
             }
@@ -471,3 +739,81 @@ pub fn deduplicate_semver_variants(versions: Vec<crate::ZigVersion>) -> Vec<crat
     result.extend(non_semver_versions);
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("36h"), Ok(Duration::from_secs(36 * 3600)));
+        assert_eq!(
+            parse_duration("14d"),
+            Ok(Duration::from_secs(14 * 24 * 3600))
+        );
+        assert_eq!(
+            parse_duration("2w"),
+            Ok(Duration::from_secs(14 * 24 * 3600))
+        );
+        for bad in ["", "d", "14", "14m", "-1d", "1.5d", "ħ"] {
+            assert!(parse_duration(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn project_names_only_need_a_usable_zon_name() {
+        for good in [
+            "app",
+            "my-project",
+            "my.project",
+            "123abc",
+            "My Project",
+            "_private",
+        ] {
+            assert_eq!(
+                invalid_project_name(good),
+                None,
+                "{good:?} should be accepted"
+            );
+        }
+        for bad in [
+            "",
+            " ",
+            "_",
+            "...",
+            "..",
+            "../escape",
+            "a/b",
+            "a\\b",
+            "test",
+            "Const",
+        ] {
+            assert!(
+                invalid_project_name(bad).is_some(),
+                "{bad:?} should be rejected"
+            );
+        }
+
+        assert_eq!(suggest_project_name("test"), "test_app");
+        assert_eq!(suggest_project_name("..."), "app");
+        for name in ["test", "../escape", "..."] {
+            assert_eq!(invalid_project_name(&suggest_project_name(name)), None);
+        }
+    }
+
+    #[test]
+    fn zon_name_is_the_sanitized_project_name() {
+        let name = |raw: &str| sanitize_build_zig_zon_name(Some(raw), &Version::new(0, 14, 1));
+        assert_eq!(name("my-project").as_deref(), Some(".my_project"));
+        assert_eq!(name("123abc").as_deref(), Some("._123abc"));
+        assert_eq!(
+            name("a_name_that_is_much_too_long_for_zig").as_deref(),
+            Some(".a_name_that_is_much_too_long_for")
+        );
+        assert_eq!(
+            sanitize_build_zig_zon_name(Some("my.project"), &Version::new(0, 12, 0)).as_deref(),
+            Some("\"my_project\"")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use color_eyre::Report;
+use std::path::PathBuf;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ShellErr {
@@ -62,6 +63,14 @@ pub enum ShellErr {
 
     #[error("User cancelled interactive setup")]
     UserCancelledInteractive,
+
+    /// Added by [ZvError::with_context]
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<ShellErr>,
+    },
 }
 
 impl ShellErr {
@@ -186,9 +195,17 @@ impl ShellErr {
         Self::UserCancelledInteractive
     }
 
+    /// The error underneath any context added by [ZvError::with_context]
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
     /// Get error recovery suggestions for common failure modes
     pub fn recovery_suggestion(&self) -> Option<String> {
-        match self {
+        match self.root() {
             Self::RegistryFailed { operation } => {
                 Some(format!(
                     "Registry operation '{}' failed. Try running as administrator or check Windows permissions.",
@@ -256,14 +273,22 @@ pub enum ZvError {
     CannotSetActiveVersion {
         version: semver::Version,
         reason: String,
+        /// Added by [ZvError::with_context]
+        #[source]
+        context: Option<Report>,
     },
     /// Cache not found at location
-    #[error("File not found at: {0}")]
-    CacheNotFound(String),
+    #[error("File not found at: {path}")]
+    CacheNotFound {
+        path: String,
+        /// Added by [ZvError::with_context]
+        #[source]
+        context: Option<Report>,
+    },
 
     /// Failure type for parse Zig version
     #[error("failed to parse semantic version")]
-    ZigVersionError(#[from] semver::Error),
+    ZigVersionError(#[source] Report),
 
     /// Failure to resolve Zig Version
     #[error("Failed to resolve Zig version")]
@@ -283,7 +308,11 @@ pub enum ZvError {
 
     /// Zv bin path doesn't exist
     #[error("Zv bin path not found")]
-    ZvBinPathNotFound,
+    ZvBinPathNotFound {
+        /// Added by [ZvError::with_context]
+        #[source]
+        context: Option<Report>,
+    },
 
     /// Zv Export Failed
     #[error("Failed to export environment")]
@@ -313,6 +342,27 @@ pub enum ZvError {
     #[error("Shell error")]
     ShellError(#[from] ShellErr),
 
+    /// A file in bin/ that zv did not create sits where a shim should go
+    #[error(
+        "{} is not a zv shim; refusing to overwrite it. Move it out of ZV_DIR/bin or rerun with --force-shims to replace it",
+        path.display()
+    )]
+    ShimConflict {
+        path: PathBuf,
+        /// Added by [ZvError::with_context]
+        #[source]
+        context: Option<Report>,
+    },
+
+    /// zv can't tell how to unpack the archive from its file name
+    #[error("Unsupported archive format: {file}")]
+    UnsupportedArchive {
+        file: String,
+        /// Added by [ZvError::with_context]
+        #[source]
+        context: Option<Report>,
+    },
+
     /// Minisign signature verification failed
     #[error("Minisign error")]
     MinisignError(#[source] Report),
@@ -408,24 +458,148 @@ impl ZvError {
         Self::ShellError(ShellErr::user_cancelled_interactive())
     }
 
-    /// Get error recovery suggestions if available
-    pub fn recovery_suggestion(&self) -> Option<String> {
+    /// Add `msg` to the error chain, keeping the variant (and so its recovery suggestion).
+    /// `msg` becomes the outermost cause under the variant's own message. zv's own inner error
+    /// types gain a `Context` wrapper; match on their `root()` to see past it.
+    pub fn with_context(self, msg: impl Into<String>) -> Self {
+        let msg = msg.into();
+        let add_to = |context: Option<Report>| {
+            Some(match context {
+                Some(report) => report.wrap_err(msg.clone()),
+                None => Report::msg(msg.clone()),
+            })
+        };
         match self {
-            Self::ShellError(shell_err) => shell_err.recovery_suggestion(),
-            Self::ZvBinPathNotFound => {
-                Some("The zv bin directory was not found. Try reinstalling zv or check your installation.".to_string())
+            Self::ZigVersionError(report) => Self::ZigVersionError(report.wrap_err(msg)),
+            Self::ZigVersionResolveError(report) => {
+                Self::ZigVersionResolveError(report.wrap_err(msg))
+            }
+            Self::ZvAppInitError(report) => Self::ZvAppInitError(report.wrap_err(msg)),
+            Self::ZvExportError(report) => Self::ZvExportError(report.wrap_err(msg)),
+            Self::ZigExecuteError { command, source } => Self::ZigExecuteError {
+                command,
+                source: source.wrap_err(msg),
+            },
+            Self::TemplateError(report) => Self::TemplateError(report.wrap_err(msg)),
+            Self::ZigNotFound(report) => Self::ZigNotFound(report.wrap_err(msg)),
+            Self::MinisignError(report) => Self::MinisignError(report.wrap_err(msg)),
+            Self::General(report) => Self::General(report.wrap_err(msg)),
+            Self::Io(err) => Self::Io(std::io::Error::new(
+                err.kind(),
+                Report::new(err).wrap_err(msg),
+            )),
+            Self::NetworkError(err) => Self::NetworkError(NetErr::Context {
+                context: msg,
+                source: Box::new(err),
+            }),
+            Self::ShellError(err) => Self::ShellError(ShellErr::Context {
+                context: msg,
+                source: Box::new(err),
+            }),
+            Self::ZvConfigError(err) => Self::ZvConfigError(CfgErr::Context {
+                context: msg,
+                source: Box::new(err),
+            }),
+            Self::CacheNotFound { path, context } => Self::CacheNotFound {
+                path,
+                context: add_to(context),
+            },
+            Self::ShimConflict { path, context } => Self::ShimConflict {
+                path,
+                context: add_to(context),
+            },
+            Self::UnsupportedArchive { file, context } => Self::UnsupportedArchive {
+                file,
+                context: add_to(context),
+            },
+            Self::CannotSetActiveVersion {
+                version,
+                reason,
+                context,
+            } => Self::CannotSetActiveVersion {
+                version,
+                reason,
+                context: add_to(context),
+            },
+            Self::ZvBinPathNotFound { context } => Self::ZvBinPathNotFound {
+                context: add_to(context),
+            },
+        }
+    }
+
+    /// What the user can do about this error. Every variant has one, so new variants must too.
+    pub fn recovery_suggestion(&self) -> String {
+        match self {
+            Self::CannotSetActiveVersion { .. } => {
+                "Check installed versions with `zv list`, then run `zv use <version>`".to_string()
+            }
+            Self::CacheNotFound { .. } => {
+                "Run `zv sync` to download the index and mirrors list again".to_string()
+            }
+            Self::ZigVersionError(_) => {
+                "Use a version like `0.15.1`, `latest`, `stable` or `master`".to_string()
+            }
+            Self::ZigVersionResolveError(_) => {
+                "Run `zv sync` to update the index, or `zv list --all` to see available versions".to_string()
+            }
+            Self::ZvAppInitError(_) => {
+                "Check that ZV_DIR (or the XDG data dir) is writable, then rerun `zv sync`".to_string()
+            }
+            Self::Io(_) => {
+                "Check file permissions and free disk space under ZV_DIR".to_string()
+            }
+            Self::ZvConfigError(_) => "Fix zv.toml with `zv config edit`".to_string(),
+            Self::ZvBinPathNotFound { .. } => {
+                "Run `zv sync` to recreate the bin directory, or `zv setup` to add it to PATH".to_string()
             }
             Self::ZvExportError(_) => {
-                Some("Environment export failed. Check your shell configuration and permissions.".to_string())
+                "Environment export failed. Check your shell configuration and permissions.".to_string()
+            }
+            Self::ZigExecuteError { .. } => {
+                "Check the active Zig with `zv list`, or reinstall it with `zv use <version> --reinstall`".to_string()
+            }
+            Self::TemplateError(_) => {
+                "Make sure the target directory is writable and not already a Zig project".to_string()
+            }
+            Self::NetworkError(err) if matches!(err.root(), NetErr::Checksum(_)) => {
+                "The download was corrupt; retry, or pass `--force-ziglang` to fetch from ziglang.org".to_string()
             }
             Self::NetworkError(_) => {
-                Some("Network operation failed. Check your internet connection and try again.".to_string())
+                "Check your internet connection and retry, or pass `--force-ziglang` to skip community mirrors".to_string()
             }
-            _ => None,
+            Self::ZigNotFound(_) => {
+                "Install a version with `zv use <version>`, or see what's available with `zv list --all`".to_string()
+            }
+            Self::ShellError(shell_err) => shell_err
+                .recovery_suggestion()
+                .unwrap_or_else(|| "Run `zv setup` again".to_string()),
+            Self::ShimConflict { .. } => {
+                "Move the file out of the bin directory, or rerun with `--force-shims`".to_string()
+            }
+            Self::UnsupportedArchive { .. } => {
+                "zv only unpacks .tar.xz, .tar.gz and .zip archives; update zv to install this build".to_string()
+            }
+            Self::MinisignError(_) => {
+                "The signature did not verify; run `zv sync` and retry, or pass `--force-ziglang`".to_string()
+            }
+            Self::General(_) => "Rerun with `ZV_LOG=zv=debug` for more details".to_string(),
         }
     }
 }
 
+/// [ZvError::with_context] for results, so context can be added without going through [Report].
+/// Named apart from eyre's `WrapErr::with_context`, which also applies to `Result<T, ZvError>`.
+pub trait ZvResultExt<T> {
+    /// Add the message `f` returns to the error chain, only computing it on failure
+    fn zv_context<S: Into<String>>(self, f: impl FnOnce() -> S) -> Result<T, ZvError>;
+}
+
+impl<T> ZvResultExt<T> for Result<T, ZvError> {
+    fn zv_context<S: Into<String>>(self, f: impl FnOnce() -> S) -> Result<T, ZvError> {
+        self.map_err(|e| e.with_context(f()))
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum NetErr {
     #[error("Invalid Mirror: {0}")]
@@ -446,12 +620,20 @@ pub enum NetErr {
     #[error("Download stalled: no progress for {duration:?}")]
     Stalled { duration: std::time::Duration },
 
+    #[error("Download size mismatch: expected {expected} bytes, got {got}")]
+    SizeMismatch { expected: u64, got: u64 },
+
     #[error("Too many retries: {attempts} attempts failed")]
     TooManyRetries { attempts: usize },
 
     #[error("HTTP request failed with status: {0}")]
     HTTP(reqwest::StatusCode),
 
+    #[error("Rate limited by mirror (HTTP 429), retry after: {retry_after:?}")]
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+    },
+
     #[error("JSON parse error: {0}")]
     JsonParse(#[source] serde_json::Error),
 
@@ -473,10 +655,28 @@ pub enum NetErr {
     #[error("Checksum verification failed")]
     Checksum(#[source] Report),
 
+    /// Added by [ZvError::with_context]
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<NetErr>,
+    },
+
     #[error(transparent)]
     Other(#[from] Report),
 }
 
+impl NetErr {
+    /// The error underneath any context added by [ZvError::with_context]
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            other => other,
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 /// Zv config error type
 pub enum CfgErr {
@@ -499,4 +699,81 @@ pub enum CfgErr {
     /// Cache expired
     #[error("Cache expired for {0}")]
     CacheExpired(String),
+
+    /// Added by [ZvError::with_context]
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<CfgErr>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::eyre;
+
+    #[test]
+    fn with_context_keeps_the_variant_and_adds_to_the_chain() {
+        let err = ZvError::ZigNotFound(eyre!("no zig in PATH")).with_context("resolving zig");
+        let ZvError::ZigNotFound(report) = &err else {
+            panic!("variant changed: {err:?}");
+        };
+        let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+        assert_eq!(chain, ["resolving zig", "no zig in PATH"]);
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "zig.tar.xz");
+        let err = ZvError::Io(io).with_context("verifying zig.tar.xz");
+        let ZvError::Io(io) = &err else {
+            panic!("variant changed: {err:?}");
+        };
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(io.to_string(), "verifying zig.tar.xz");
+    }
+
+    #[test]
+    fn with_context_leaves_typed_errors_matchable() {
+        let err = ZvError::NetworkError(NetErr::EmptyMirrors).with_context("downloading 0.15.1");
+        let ZvError::NetworkError(net_err) = &err else {
+            panic!("variant changed: {err:?}");
+        };
+        assert!(matches!(net_err.root(), NetErr::EmptyMirrors));
+        let chain: Vec<_> = Report::new(err).chain().map(|e| e.to_string()).collect();
+        assert_eq!(
+            chain,
+            [
+                "Network error",
+                "downloading 0.15.1",
+                "No valid mirrors found"
+            ]
+        );
+
+        let corrupt = ZvError::NetworkError(NetErr::Checksum(eyre!("sha256 mismatch")))
+            .with_context("downloading 0.15.1");
+        assert!(corrupt.recovery_suggestion().contains("corrupt"));
+
+        let result: Result<(), ZvError> = Err(ZvError::General(eyre!("bad checksum")));
+        let err = result
+            .zv_context(|| format!("verifying {}", "zig.tar.xz"))
+            .unwrap_err();
+        assert_eq!(format!("{err:#}"), "verifying zig.tar.xz: bad checksum");
+    }
+
+    #[test]
+    fn with_context_chains_onto_plain_field_variants() {
+        let err = ZvError::ShimConflict {
+            path: PathBuf::from("zig"),
+            context: None,
+        }
+        .with_context("deploying shims")
+        .with_context("activating 0.15.1");
+        assert!(matches!(err, ZvError::ShimConflict { .. }));
+        let chain: Vec<_> = Report::new(err)
+            .chain()
+            .skip(1)
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(chain, ["activating 0.15.1", "deploying shims"]);
+    }
 }
@@ -17,19 +17,63 @@ pub struct UserConfig {
     pub paths: crate::tools::ZvPaths,
     pub shell: Option<crate::Shell>,
 }
+
+impl UserConfig {
+    /// Config for headless use (servers, CI, automation) rooted at `zv_base_path` as if `ZV_DIR`
+    /// pointed there. No shell is detected, so `zv setup` on an app built from it returns an error.
+    #[cfg(test)]
+    pub fn with_no_shell(zv_base_path: std::path::PathBuf) -> Self {
+        Self {
+            paths: crate::tools::ZvPaths::self_contained(&zv_base_path),
+            shell: None,
+        }
+    }
+}
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ArchiveExt {
     #[default]
     TarXz,
+    TarGz,
     Zip,
 }
 
+impl ArchiveExt {
+    /// Archive format of a file named `name`, going by its extension
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name.ends_with(".tar.xz") {
+            Some(ArchiveExt::TarXz)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveExt::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveExt::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Archive format announced by an HTTP `Content-Type` header. Generic types such as
+    /// `application/octet-stream` say nothing about the format and yield `None`.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/x-xz" | "application/x-xz-compressed-tar" => Some(ArchiveExt::TarXz),
+            "application/gzip" | "application/x-gzip" | "application/x-compressed-tar" => {
+                Some(ArchiveExt::TarGz)
+            }
+            "application/zip" | "application/x-zip-compressed" => Some(ArchiveExt::Zip),
+            _ => None,
+        }
+    }
+}
+
 impl std::str::FromStr for ArchiveExt {
     type Err = ZvError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "tar.xz" => Ok(ArchiveExt::TarXz),
+            "tar.gz" | "tgz" => Ok(ArchiveExt::TarGz),
             "zip" => Ok(ArchiveExt::Zip),
             _ => Err(eyre!("Unsupported archive extension: {s}").into()),
         }
@@ -40,6 +84,7 @@ impl std::fmt::Display for ArchiveExt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ArchiveExt::TarXz => write!(f, "tar.xz"),
+            ArchiveExt::TarGz => write!(f, "tar.gz"),
             ArchiveExt::Zip => write!(f, "zip"),
         }
     }
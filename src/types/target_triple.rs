@@ -64,3 +64,62 @@ impl Hash for TargetTriple {
         self.os.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Target keys published in ziglang.org's download index
+    const INDEX_KEYS: &[&str] = &[
+        "x86_64-linux",
+        "aarch64-linux",
+        "armv7a-linux",
+        "riscv64-linux",
+        "powerpc64le-linux",
+        "x86-linux",
+        "loongarch64-linux",
+        "s390x-linux",
+        "x86_64-macos",
+        "aarch64-macos",
+        "x86_64-windows",
+        "aarch64-windows",
+        "x86-windows",
+        "x86_64-freebsd",
+        "aarch64-freebsd",
+        "x86_64-netbsd",
+    ];
+
+    #[test]
+    fn index_keys_round_trip() {
+        for key in INDEX_KEYS {
+            let triple = TargetTriple::from_key(key).unwrap();
+            assert_eq!(triple.to_key(), *key);
+            assert_eq!(TargetTriple::from_key(&triple.to_key()), Some(triple));
+        }
+
+        let riscv = TargetTriple::from_key("riscv64-linux").unwrap();
+        assert_eq!(
+            (riscv.arch.as_str(), riscv.os.as_str()),
+            ("riscv64", "linux")
+        );
+    }
+
+    #[test]
+    fn non_target_keys_are_rejected() {
+        // Release entries also carry these non-platform fields next to the targets
+        for key in [
+            "src",
+            "bootstrap",
+            "docs",
+            "stdDocs",
+            "notes",
+            "date",
+            "version",
+        ] {
+            assert_eq!(TargetTriple::from_key(key), None, "{key}");
+        }
+        for key in ["", "-", "x86_64-", "-linux", "x86_64-linux-gnu"] {
+            assert_eq!(TargetTriple::from_key(key), None, "{key}");
+        }
+    }
+}
@@ -3,12 +3,13 @@ use color_eyre::eyre::eyre;
 use semver::Version;
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use std::{
+    cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
     str::FromStr,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// A validated Zig version that exists in the index
 pub enum ResolvedZigVersion {
     /// A semantic version that exists in the index
@@ -26,10 +27,58 @@ pub enum ZigVersion {
     Master(Option<Version>),
     /// Latest stable (cached)
     Stable(Option<Version>),
+    /// Newest stable patch release of a series, from `stable@0.14`
+    StableSeries(VersionSeries),
     /// Latest stable (always refresh)
     Latest(Option<Version>),
 }
 
+/// The version forms [ZigVersion]'s parser accepts, quoted in its errors
+const VERSION_GRAMMAR: &str = "Expected master (or nightly/dev/trunk), stable, latest, a semver like 0.15.1, or stable@<version> like stable@0.14 or stable@0.14.1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// A partial version such as `0.14` or `0`, naming the releases that start with it
+pub struct VersionSeries {
+    pub major: u64,
+    pub minor: Option<u64>,
+}
+
+impl VersionSeries {
+    /// Parse `major` or `major.minor`; anything longer or non-numeric is not a series
+    fn parse(s: &str) -> Option<Self> {
+        let number = |part: &str| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            part.parse().ok()
+        };
+        let mut parts = s.split('.');
+        let major = number(parts.next()?)?;
+        let minor = match parts.next() {
+            Some(minor) => Some(number(minor)?),
+            None => None,
+        };
+        parts.next().is_none().then_some(Self { major, minor })
+    }
+
+    /// Whether `version` is a stable release in this series
+    pub fn matches(&self, version: &Version) -> bool {
+        version.pre.is_empty()
+            && version.build.is_empty()
+            && version.major == self.major
+            && self.minor.is_none_or(|minor| version.minor == minor)
+    }
+}
+
+impl fmt::Display for VersionSeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{}", self.major, minor),
+            None => write!(f, "{}", self.major),
+        }
+    }
+}
+
 impl ZigVersion {
     /// Creates a placeholder version (None) for the given variant type
     pub fn placeholder_for_variant(variant: &str) -> Result<Self, ZvError> {
@@ -62,7 +111,7 @@ impl ZigVersion {
         // Combine normalized core with original suffix
         let normalized = format!("{}{}", normalized_core, suffix);
 
-        Version::parse(&normalized).map_err(ZvError::ZigVersionError)
+        Version::parse(&normalized).map_err(|e| ZvError::ZigVersionError(e.into()))
     }
 
     /// Extracts the version from any ZigVersion variant, if available
@@ -72,7 +121,10 @@ impl ZigVersion {
             ZigVersion::Master(Some(v))
             | ZigVersion::Stable(Some(v))
             | ZigVersion::Latest(Some(v)) => Some(v),
-            ZigVersion::Master(None) | ZigVersion::Stable(None) | ZigVersion::Latest(None) => None,
+            ZigVersion::Master(None)
+            | ZigVersion::Stable(None)
+            | ZigVersion::StableSeries(_)
+            | ZigVersion::Latest(None) => None,
         }
     }
 
@@ -83,7 +135,10 @@ impl ZigVersion {
             ZigVersion::Master(Some(_))
             | ZigVersion::Stable(Some(_))
             | ZigVersion::Latest(Some(_)) => true,
-            ZigVersion::Master(None) | ZigVersion::Stable(None) | ZigVersion::Latest(None) => false,
+            ZigVersion::Master(None)
+            | ZigVersion::Stable(None)
+            | ZigVersion::StableSeries(_)
+            | ZigVersion::Latest(None) => false,
         }
     }
 
@@ -92,10 +147,34 @@ impl ZigVersion {
         match self {
             ZigVersion::Semver(_) => "semver",
             ZigVersion::Master(_) => "master",
-            ZigVersion::Stable(_) => "stable",
+            ZigVersion::Stable(_) | ZigVersion::StableSeries(_) => "stable",
             ZigVersion::Latest(_) => "latest",
         }
     }
+
+    /// Reject `stable:0.14`, `latest 0.15.1` and the like, which mean `<keyword>@<version>`
+    fn check_separator(s: &str) -> Result<(), ZvError> {
+        for keyword in ["stable", "latest", "master"] {
+            let Some(rest) = s.strip_prefix(keyword) else {
+                continue;
+            };
+            let mut chars = rest.chars();
+            if let Some(separator) = chars.next()
+                && separator != '@'
+                && !separator.is_ascii_alphanumeric()
+            {
+                let example = match chars.as_str() {
+                    version if VersionSeries::parse(version).is_some() => version,
+                    version if Version::parse(version).is_ok() => version,
+                    _ => "0.14.1",
+                };
+                return Err(ZvError::General(eyre!(
+                    "Unexpected {separator:?} in '{s}'. Pin a version with '@', e.g. {keyword}@{example}"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl FromStr for ZigVersion {
@@ -103,13 +182,43 @@ impl FromStr for ZigVersion {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "master" => Self::placeholder_for_variant("master"),
+            // `nightly`, `dev` and `trunk` are common names for master in other toolchains
+            "master" | "nightly" | "dev" | "trunk" => Self::placeholder_for_variant("master"),
             "stable" => Self::placeholder_for_variant("stable"),
             "latest" => Self::placeholder_for_variant("latest"),
             _ => {
                 // Handle prefixed variants (stable@version)
                 if let Some((prefix, version_str)) = s.split_once('@') {
-                    let version = Self::parse_normalized_version(version_str)?;
+                    if !matches!(prefix, "stable" | "master" | "latest") {
+                        return Err(ZvError::General(eyre!(
+                            "Invalid version prefix '{}' in '{}'. {}",
+                            prefix,
+                            s,
+                            VERSION_GRAMMAR
+                        )));
+                    }
+                    // A partial version picks the newest patch release once resolved
+                    if prefix == "stable"
+                        && let Some(series) = VersionSeries::parse(version_str)
+                    {
+                        return Ok(ZigVersion::StableSeries(series));
+                    }
+                    let version = Self::parse_normalized_version(version_str).map_err(|_| {
+                        let retyped = version_str.trim_start_matches(['@', ':', '=']);
+                        let example = if retyped != version_str && Self::from_str(retyped).is_ok() {
+                            retyped
+                        } else {
+                            "0.14.1"
+                        };
+                        ZvError::General(eyre!(
+                            "'{}' after '{}@' is not a version. Expected {}@<version>, e.g. {}@{}",
+                            version_str,
+                            prefix,
+                            prefix,
+                            prefix,
+                            example
+                        ))
+                    })?;
                     return match prefix {
                         "stable" => {
                             // Validate that the version is stable (no pre-release or dev builds)
@@ -123,20 +232,18 @@ impl FromStr for ZigVersion {
                             }
                         }
                         "master" => Ok(ZigVersion::Master(Some(version))),
-                        "latest" => Ok(ZigVersion::Latest(Some(version))),
-                        _ => Err(ZvError::General(eyre!(
-                            "Invalid version prefix: {}. Supported: stable@<version>",
-                            prefix
-                        ))),
+                        _ => Ok(ZigVersion::Latest(Some(version))),
                     };
                 }
+                Self::check_separator(s)?;
                 // Parse as direct semver if it starts with a digit
                 if s.chars().next().is_some_and(|c| c.is_ascii_digit()) {
                     Self::parse_normalized_version(s).map(ZigVersion::Semver)
                 } else {
                     Err(ZvError::General(eyre!(
-                        "Not a valid Zig version string: {}",
-                        s
+                        "Not a valid Zig version string: {}. {}",
+                        s,
+                        VERSION_GRAMMAR
                     )))
                 }
             }
@@ -163,6 +270,10 @@ impl Hash for ZigVersion {
                 state.write_u8(3);
                 v.hash(state);
             }
+            ZigVersion::StableSeries(series) => {
+                state.write_u8(4);
+                series.hash(state);
+            }
         }
     }
 }
@@ -174,6 +285,7 @@ impl PartialEq for ZigVersion {
             (ZigVersion::Master(a), ZigVersion::Master(b)) => a == b,
             (ZigVersion::Stable(a), ZigVersion::Stable(b)) => a == b,
             (ZigVersion::Latest(a), ZigVersion::Latest(b)) => a == b,
+            (ZigVersion::StableSeries(a), ZigVersion::StableSeries(b)) => a == b,
             // Different variant types are never equal
             _ => false,
         }
@@ -198,23 +310,20 @@ impl Ord for ZigVersion {
             (ZigVersion::Master(a), ZigVersion::Master(b)) => a.cmp(b),
             (ZigVersion::Stable(a), ZigVersion::Stable(b)) => a.cmp(b),
             (ZigVersion::Latest(a), ZigVersion::Latest(b)) => a.cmp(b),
+            (ZigVersion::StableSeries(a), ZigVersion::StableSeries(b)) => a.cmp(b),
 
             // Different variant types - establish ordering
-            // Order: Semver < Stable < Latest < Master
-            (ZigVersion::Semver(_), ZigVersion::Stable(_)) => Ordering::Less,
-            (ZigVersion::Semver(_), ZigVersion::Latest(_)) => Ordering::Less,
-            (ZigVersion::Semver(_), ZigVersion::Master(_)) => Ordering::Less,
+            // Order: Semver < Stable < StableSeries < Latest < Master
+            (ZigVersion::Semver(_), _) => Ordering::Less,
+            (_, ZigVersion::Semver(_)) => Ordering::Greater,
 
-            (ZigVersion::Stable(_), ZigVersion::Semver(_)) => Ordering::Greater,
-            (ZigVersion::Stable(_), ZigVersion::Latest(_)) => Ordering::Less,
-            (ZigVersion::Stable(_), ZigVersion::Master(_)) => Ordering::Less,
+            (ZigVersion::Stable(_), _) => Ordering::Less,
+            (_, ZigVersion::Stable(_)) => Ordering::Greater,
 
-            (ZigVersion::Latest(_), ZigVersion::Semver(_)) => Ordering::Greater,
-            (ZigVersion::Latest(_), ZigVersion::Stable(_)) => Ordering::Greater,
-            (ZigVersion::Latest(_), ZigVersion::Master(_)) => Ordering::Less,
+            (ZigVersion::StableSeries(_), _) => Ordering::Less,
+            (_, ZigVersion::StableSeries(_)) => Ordering::Greater,
 
-            (ZigVersion::Master(_), ZigVersion::Semver(_)) => Ordering::Greater,
-            (ZigVersion::Master(_), ZigVersion::Stable(_)) => Ordering::Greater,
+            (ZigVersion::Latest(_), ZigVersion::Master(_)) => Ordering::Less,
             (ZigVersion::Master(_), ZigVersion::Latest(_)) => Ordering::Greater,
         }
     }
@@ -230,6 +339,7 @@ impl Serialize for ZigVersion {
             ZigVersion::Master(Some(version))
             | ZigVersion::Stable(Some(version))
             | ZigVersion::Latest(Some(version)) => version.to_string(),
+            ZigVersion::StableSeries(series) => format!("stable@{series}"),
             ZigVersion::Master(None) | ZigVersion::Stable(None) | ZigVersion::Latest(None) => {
                 return Err(serde::ser::Error::custom(
                     "Cannot serialize unresolved version",
@@ -259,6 +369,7 @@ impl fmt::Display for ZigVersion {
             ZigVersion::Master(None) => write!(f, "master <version: unknown>"),
             ZigVersion::Stable(Some(v)) => write!(f, "stable <{}>", v),
             ZigVersion::Stable(None) => write!(f, "stable <version: unknown>"),
+            ZigVersion::StableSeries(series) => write!(f, "stable <{}.x>", series),
             ZigVersion::Latest(Some(v)) => write!(f, "latest <{}>", v),
             ZigVersion::Latest(None) => write!(f, "latest <version: unknown>"),
         }
@@ -277,6 +388,22 @@ impl From<&semver::Version> for ZigVersion {
     }
 }
 
+/// Ordered by numeric version first; a master build sorts right after a stable release with the
+/// same version, so the two stay adjacent in a `BTreeMap` keyed by `ResolvedZigVersion`
+impl Ord for ResolvedZigVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.version()
+            .cmp(other.version())
+            .then_with(|| self.is_master().cmp(&other.is_master()))
+    }
+}
+
+impl PartialOrd for ResolvedZigVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for ResolvedZigVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -353,6 +480,31 @@ mod tests {
         assert_eq!(set.len(), 1); // v1 and v2 are equal, so only one entry
     }
 
+    #[test]
+    fn resolved_versions_sort_numerically_with_master_after_stable() {
+        let mut versions = [
+            ResolvedZigVersion::Master(Version::parse("0.15.0").unwrap()),
+            ResolvedZigVersion::Semver(Version::parse("0.15.0").unwrap()),
+            ResolvedZigVersion::Master(Version::parse("0.14.0-dev.1+abc").unwrap()),
+            ResolvedZigVersion::Semver(Version::parse("0.14.1").unwrap()),
+            ResolvedZigVersion::Semver(Version::parse("0.13.0").unwrap()),
+        ];
+        versions.sort();
+
+        let sorted: Vec<String> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            sorted,
+            [
+                "0.13.0",
+                "master <0.14.0-dev.1+abc>",
+                "0.14.1",
+                "0.15.0",
+                "master <0.15.0>"
+            ]
+        );
+        assert_eq!(versions[3].cmp(&versions[3].clone()), Ordering::Equal);
+    }
+
     #[test]
     fn test_resolved_zig_version_display() {
         let semver = ResolvedZigVersion::Semver(Version::parse("1.0.0").unwrap());
@@ -362,6 +514,62 @@ mod tests {
         assert_eq!(format!("{}", master_version), "master <1.5.0>");
     }
 
+    #[test]
+    fn test_master_aliases() {
+        for alias in ["master", "nightly", "dev", "trunk"] {
+            let parsed = ZigVersion::from_str(alias).unwrap();
+            assert_eq!(parsed, ZigVersion::Master(None));
+            assert_eq!(parsed.variant_type(), "master");
+            assert_eq!(parsed.to_string(), "master <version: unknown>");
+        }
+
+        let err = ZigVersion::from_str("beta").unwrap_err().to_string();
+        assert!(err.contains("nightly") && err.contains("stable@<version>"));
+    }
+
+    #[test]
+    fn stable_accepts_a_full_or_partial_version() {
+        assert_eq!(
+            ZigVersion::from_str("stable").unwrap(),
+            ZigVersion::Stable(None)
+        );
+        assert_eq!(
+            ZigVersion::from_str("stable@0.14.1").unwrap(),
+            ZigVersion::Stable(Some(Version::new(0, 14, 1)))
+        );
+
+        let series = ZigVersion::from_str("stable@0.14").unwrap();
+        let expected = VersionSeries {
+            major: 0,
+            minor: Some(14),
+        };
+        assert_eq!(series, ZigVersion::StableSeries(expected.clone()));
+        assert_eq!(series.to_string(), "stable <0.14.x>");
+        assert_eq!(series.variant_type(), "stable");
+        assert!(expected.matches(&Version::new(0, 14, 1)));
+        assert!(!expected.matches(&Version::new(0, 15, 0)));
+        assert!(!expected.matches(&Version::parse("0.14.0-rc.1").unwrap()));
+
+        let round_trip: ZigVersion =
+            serde_json::from_str(&serde_json::to_string(&series).unwrap()).unwrap();
+        assert_eq!(round_trip, series);
+    }
+
+    #[test]
+    fn malformed_stable_separators_show_the_expected_syntax() {
+        for (input, hint) in [
+            ("stable@@0.14.0", "e.g. stable@0.14.0"),
+            ("stable:0.14.0", "e.g. stable@0.14.0"),
+            ("stable 0.14", "e.g. stable@0.14"),
+            ("stable=", "e.g. stable@0.14.1"),
+            ("stable@0.14.x", "Expected stable@<version>"),
+            ("stabel@0.14", "stable@<version> like stable@0.14"),
+        ] {
+            let err = ZigVersion::from_str(input).unwrap_err().to_string();
+            assert!(err.contains(hint), "{input}: {err}");
+        }
+    }
+
     #[test]
     fn test_resolved_zig_version_methods() {
         let semver = ResolvedZigVersion::Semver(Version::parse("1.0.0").unwrap());
@@ -375,4 +583,76 @@ mod tests {
         assert!(semver.is_semver());
         assert!(!master_version.is_semver());
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Full `major.minor.patch` semver strings with optional pre-release and build parts
+        fn semver_string() -> impl Strategy<Value = String> {
+            (
+                any::<u64>(),
+                any::<u64>(),
+                any::<u64>(),
+                proptest::option::of("[a-z][a-z0-9]{0,7}(\\.(0|[1-9][0-9]{0,3}))?"),
+                proptest::option::of("[0-9a-z]{1,8}"),
+            )
+                .prop_map(|(major, minor, patch, pre, build)| {
+                    let mut s = format!("{major}.{minor}.{patch}");
+                    if let Some(pre) = pre {
+                        s.push_str(&format!("-{pre}"));
+                    }
+                    if let Some(build) = build {
+                        s.push_str(&format!("+{build}"));
+                    }
+                    s
+                })
+        }
+
+        /// Strings that parse on their own
+        fn accepted_input() -> impl Strategy<Value = String> {
+            prop_oneof![
+                semver_string(),
+                prop::sample::select(vec![
+                    "master", "nightly", "dev", "trunk", "stable", "latest"
+                ])
+                .prop_map(String::from),
+                semver_string().prop_map(|v| format!("master@{v}")),
+                semver_string().prop_map(|v| format!("latest@{v}")),
+                (any::<u64>(), any::<u64>())
+                    .prop_map(|(major, minor)| format!("stable@{major}.{minor}")),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn valid_semver_parses_as_semver(s in semver_string()) {
+                let parsed = ZigVersion::from_str(&s).unwrap();
+                prop_assert_eq!(parsed, ZigVersion::Semver(Version::parse(&s).unwrap()));
+            }
+
+            #[test]
+            fn master_aliases_parse_as_unversioned_master(
+                alias in prop::sample::select(vec!["master", "nightly", "dev", "trunk"])
+            ) {
+                prop_assert_eq!(ZigVersion::from_str(alias).unwrap(), ZigVersion::Master(None));
+            }
+
+            #[test]
+            fn surrounding_whitespace_is_rejected(
+                s in accepted_input(),
+                ws in "[ \t\r\n]{1,3}",
+                leading in any::<bool>(),
+            ) {
+                prop_assert!(ZigVersion::from_str(&s).is_ok());
+                let padded = if leading { format!("{ws}{s}") } else { format!("{s}{ws}") };
+                prop_assert!(ZigVersion::from_str(&padded).is_err());
+            }
+
+            #[test]
+            fn never_panics_on_arbitrary_input(s in "\\PC*") {
+                let _ = ZigVersion::from_str(&s);
+            }
+        }
+    }
 }
@@ -0,0 +1,100 @@
+//! Recursion guard for the `zig` shim, exercised through the real binary.
+#![cfg(unix)]
+
+use std::os::unix::fs::{PermissionsExt, symlink};
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+/// A zv home whose `bin/zig` shim points at an external (`zv use system`) zig in `fake/`.
+/// `wrapper` is the body of that zig after its `version` handling.
+fn zv_home_with_system_zig(root: &Path, wrapper: &str) {
+    let bin = root.join("zv/bin");
+    let fake = root.join("fake");
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::create_dir_all(&fake).unwrap();
+    symlink(env!("CARGO_BIN_EXE_zv"), bin.join("zig")).unwrap();
+
+    let zig = fake.join("zig");
+    std::fs::write(
+        &zig,
+        format!("#!/bin/sh\nif [ \"$1\" = version ]; then echo 0.14.1; exit 0; fi\n{wrapper}\n"),
+    )
+    .unwrap();
+    std::fs::set_permissions(&zig, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    std::fs::write(
+        root.join("zv/zv.toml"),
+        format!(
+            "version = \"{}\"\n\n[active_zig]\nversion = \"0.14.1\"\npath = \"{}\"\nis_master = false\nexternal = true\n",
+            env!("CARGO_PKG_VERSION"),
+            fake.display()
+        ),
+    )
+    .unwrap();
+}
+
+/// Run `zig build` through the shim, killing it if the guard never trips
+fn run_shim(root: &Path) -> Output {
+    let path = format!(
+        "{}:{}:{}",
+        root.join("zv/bin").display(),
+        root.join("fake").display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let mut child = Command::new(root.join("zv/bin/zig"))
+        .arg("build")
+        .env("ZV_DIR", root.join("zv"))
+        .env("PATH", path)
+        .env_remove("ZV_RECURSION_COUNT")
+        .env_remove("ZV_RECURSION_MAX")
+        .env_remove("ZIG_VERSION")
+        .env_remove("ZV_DEFAULT_VERSION")
+        .current_dir(root)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    while child.try_wait().unwrap().is_none() {
+        if Instant::now() > deadline {
+            child.kill().unwrap();
+            panic!("zig shim kept respawning itself");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn system_zig_wrapper_that_calls_zig_trips_the_guard() {
+    let temp = tempfile::tempdir().unwrap();
+    zv_home_with_system_zig(temp.path(), "exec zig \"$@\"");
+
+    let output = run_shim(temp.path());
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Too many recursive calls"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn zig_that_runs_zig_again_stays_under_the_guard() {
+    let temp = tempfile::tempdir().unwrap();
+    // Goes through the shim twice more before answering, like nested `zig build` steps
+    zv_home_with_system_zig(
+        temp.path(),
+        "DEPTH=${DEPTH:-0}\nif [ \"$DEPTH\" -lt 2 ]; then DEPTH=$((DEPTH + 1)) exec zig \"$@\"; fi\necho \"built at depth $DEPTH\"",
+    );
+
+    let output = run_shim(temp.path());
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("built at depth 2"));
+}